@@ -0,0 +1,354 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    ops::{Add, Mul, Neg, Sub},
+};
+
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 9;
+
+/// Arbitrary-precision signed integer.
+///
+/// Magnitude is stored as little-endian 32-bit digits without leading zeros,
+/// so every number has exactly one representation and zero is never negative.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    digits: Vec<u32>,
+}
+
+fn trim_digits(digits: &mut Vec<u32>) {
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+}
+
+fn compare_digits(a: &[u32], b: &[u32]) -> Ordering {
+    if a.len() != b.len() {
+        return a.len().cmp(&b.len());
+    }
+
+    for (digit_a, digit_b) in a.iter().rev().zip(b.iter().rev()) {
+        if digit_a != digit_b {
+            return digit_a.cmp(digit_b);
+        }
+    }
+
+    Ordering::Equal
+}
+
+fn add_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry: u64 = 0;
+
+    for i in 0..a.len().max(b.len()) {
+        let sum = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> 32;
+    }
+
+    if carry != 0 {
+        result.push(carry as u32);
+    }
+
+    result
+}
+
+/// Subtracts `b` from `a`, where `a` >= `b`.
+fn sub_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow: i64 = 0;
+
+    for (i, digit) in a.iter().enumerate() {
+        let mut difference = *digit as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+
+        if difference < 0 {
+            difference += 1 << 32;
+            borrow = 1;
+        }
+
+        result.push(difference as u32);
+    }
+
+    trim_digits(&mut result);
+    result
+}
+
+fn mul_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let mut result = vec![0u32; a.len() + b.len()];
+
+    for (i, digit_a) in a.iter().enumerate() {
+        let mut carry: u64 = 0;
+
+        for (j, digit_b) in b.iter().enumerate() {
+            let product = *digit_a as u64 * *digit_b as u64 + result[i + j] as u64 + carry;
+            result[i + j] = product as u32;
+            carry = product >> 32;
+        }
+
+        result[i + b.len()] = carry as u32;
+    }
+
+    trim_digits(&mut result);
+    result
+}
+
+/// Divides digits by small divisor in place and returns remainder.
+fn div_small(digits: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut remainder: u64 = 0;
+
+    for digit in digits.iter_mut().rev() {
+        let current = (remainder << 32) | *digit as u64;
+        *digit = (current / divisor as u64) as u32;
+        remainder = current % divisor as u64;
+    }
+
+    trim_digits(digits);
+    remainder as u32
+}
+
+fn mul_small_add(digits: &mut Vec<u32>, factor: u32, addend: u32) {
+    let mut carry = addend as u64;
+
+    for digit in digits.iter_mut() {
+        let product = *digit as u64 * factor as u64 + carry;
+        *digit = product as u32;
+        carry = product >> 32;
+    }
+
+    if carry != 0 {
+        digits.push(carry as u32);
+    }
+}
+
+fn shift_left_one(digits: &mut Vec<u32>, bit: bool) {
+    let mut carry = bit as u32;
+
+    for digit in digits.iter_mut() {
+        let next_carry = *digit >> 31;
+        *digit = (*digit << 1) | carry;
+        carry = next_carry;
+    }
+
+    if carry != 0 {
+        digits.push(carry);
+    }
+}
+
+/// Binary long division of magnitudes. `b` should not be empty.
+fn div_rem_digits(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder: Vec<u32> = Vec::new();
+
+    for i in (0..a.len() * 32).rev() {
+        shift_left_one(&mut remainder, (a[i / 32] >> (i % 32)) & 1 == 1);
+
+        if compare_digits(&remainder, b) != Ordering::Less {
+            remainder = sub_digits(&remainder, b);
+            quotient[i / 32] |= 1 << (i % 32);
+        }
+    }
+
+    trim_digits(&mut quotient);
+    (quotient, remainder)
+}
+
+impl BigInt {
+    fn new(negative: bool, mut digits: Vec<u32>) -> BigInt {
+        trim_digits(&mut digits);
+        BigInt {
+            negative: negative && !digits.is_empty(),
+            digits,
+        }
+    }
+
+    pub fn zero() -> BigInt {
+        BigInt::new(false, Vec::new())
+    }
+
+    pub fn from_i64(number: i64) -> BigInt {
+        let magnitude = number.unsigned_abs();
+        BigInt::new(number < 0, vec![magnitude as u32, (magnitude >> 32) as u32])
+    }
+
+    /// Parses decimal number with optional sign.
+    pub fn parse(text: &str) -> Option<BigInt> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+
+        if digits.is_empty() {
+            return None;
+        }
+
+        let mut magnitude: Vec<u32> = Vec::new();
+
+        for chr in digits.chars() {
+            mul_small_add(&mut magnitude, 10, chr.to_digit(10)?);
+        }
+
+        Some(BigInt::new(negative, magnitude))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns number of significant bits of magnitude, 0 for zero.
+    pub fn bits(&self) -> u64 {
+        match self.digits.last() {
+            None => 0,
+            Some(last) => (self.digits.len() as u64 - 1) * 32 + (32 - last.leading_zeros()) as u64,
+        }
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        if self.digits.len() > 2 {
+            return None;
+        }
+
+        let magnitude = *self.digits.first().unwrap_or(&0) as u64
+            | (*self.digits.get(1).unwrap_or(&0) as u64) << 32;
+
+        if self.negative {
+            if magnitude > i64::MIN.unsigned_abs() {
+                return None;
+            }
+
+            return Some((magnitude as i64).wrapping_neg());
+        }
+
+        i64::try_from(magnitude).ok()
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self
+            .digits
+            .iter()
+            .rev()
+            .fold(0.0, |result, digit| result * 4294967296.0 + *digit as f64);
+
+        if self.negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    /// Divides with truncation toward zero, like `i64` division.
+    /// Returns `None` if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &BigInt) -> Option<(BigInt, BigInt)> {
+        if divisor.is_zero() {
+            return None;
+        }
+
+        let (quotient, remainder) = div_rem_digits(&self.digits, &divisor.digits);
+        Some((
+            BigInt::new(self.negative != divisor.negative, quotient),
+            BigInt::new(self.negative, remainder),
+        ))
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::new(self.negative, add_digits(&self.digits, &other.digits));
+        }
+
+        match compare_digits(&self.digits, &other.digits) {
+            Ordering::Less => BigInt::new(other.negative, sub_digits(&other.digits, &self.digits)),
+            _ => BigInt::new(self.negative, sub_digits(&self.digits, &other.digits)),
+        }
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::new(!self.negative, self.digits.clone())
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::new(
+            self.negative != other.negative,
+            mul_digits(&self.digits, &other.digits),
+        )
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_digits(&self.digits, &other.digits),
+            (true, true) => compare_digits(&other.digits, &self.digits),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.write_str("0");
+        }
+
+        let mut magnitude = self.digits.clone();
+        let mut chunks: Vec<u32> = Vec::new();
+
+        while !magnitude.is_empty() {
+            chunks.push(div_small(&mut magnitude, DECIMAL_CHUNK));
+        }
+
+        if self.negative {
+            f.write_str("-")?;
+        }
+
+        let mut iter = chunks.iter().rev();
+        f.write_fmt(format_args!("{}", unsafe {
+            iter.next().unwrap_unchecked()
+        }))?;
+
+        for chunk in iter {
+            f.write_fmt(format_args!(
+                "{:0width$}",
+                chunk,
+                width = DECIMAL_CHUNK_DIGITS
+            ))?;
+        }
+
+        Ok(())
+    }
+}
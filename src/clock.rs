@@ -0,0 +1,106 @@
+use std::{
+    cell::Cell,
+    fmt::Debug,
+    rc::Rc,
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Source of time for time functions of standard library.
+pub trait Clock: Debug {
+    /// Returns milliseconds since Unix epoch (UTC).
+    fn now_ms(&self) -> i64;
+    /// Returns milliseconds since unspecified moment. Never decreases.
+    fn monotonic_ms(&self) -> i64;
+    fn sleep_ms(&self, duration: u64);
+
+    /// Returns `true`, if time depends only on calls of this clock and its host,
+    /// so clock can be used in deterministic mode.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+/// Real clock of the host.
+#[derive(Debug, Clone)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> SystemClock {
+        SystemClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> i64 {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_millis() as i64,
+            Err(error) => -(error.duration().as_millis() as i64),
+        }
+    }
+
+    fn monotonic_ms(&self) -> i64 {
+        self.start.elapsed().as_millis() as i64
+    }
+
+    fn sleep_ms(&self, duration: u64) {
+        thread::sleep(Duration::from_millis(duration));
+    }
+}
+
+/// Clock, that moves only when host advances it or script sleeps. Clones share the same time.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    now: Rc<Cell<i64>>,
+    monotonic: Rc<Cell<i64>>,
+}
+
+impl ManualClock {
+    pub fn new(now_ms: i64) -> ManualClock {
+        ManualClock {
+            now: Rc::new(Cell::new(now_ms)),
+            monotonic: Rc::new(Cell::new(0)),
+        }
+    }
+
+    /// Sets wall clock time. Monotonic time isn't changed.
+    pub fn set_now_ms(&self, now_ms: i64) {
+        self.now.set(now_ms);
+    }
+
+    /// Moves both wall clock and monotonic time forward.
+    pub fn advance_ms(&self, duration: u64) {
+        let duration = i64::try_from(duration).unwrap_or(i64::MAX);
+        self.now.set(self.now.get().saturating_add(duration));
+        self.monotonic
+            .set(self.monotonic.get().saturating_add(duration));
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_ms(&self) -> i64 {
+        self.now.get()
+    }
+
+    fn monotonic_ms(&self) -> i64 {
+        self.monotonic.get()
+    }
+
+    fn sleep_ms(&self, duration: u64) {
+        self.advance_ms(duration);
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
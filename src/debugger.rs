@@ -0,0 +1,480 @@
+#[cfg(feature = "parser")]
+use crate::{
+    parser::parse,
+    runner::{evaluate_node, run_file_scope, RunnerError},
+    types::NativeException,
+};
+#[cfg(feature = "parser")]
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, BufRead, BufReader, Write},
+};
+
+use crate::{
+    engine::{DebugHook, StackFrame},
+    types::{Scope, Variant},
+};
+use std::{
+    cell::{Cell, Ref, RefCell},
+    collections::BTreeMap,
+    fmt,
+    path::{Component, Path, PathBuf},
+    rc::Rc,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DebugAction {
+    Continue,
+    StepInto,
+    StepOver,
+    StepOut,
+    Stop,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PauseReason {
+    Breakpoint,
+    Step,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum StepMode {
+    Run,
+    Into,
+    Over(usize),
+    Out(usize),
+    Stopped,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Breakpoint {
+    pub file: PathBuf,
+    pub line: u32,
+}
+
+impl Breakpoint {
+    pub fn new(file: &str, line: u32) -> Breakpoint {
+        Breakpoint {
+            file: Path::new(file)
+                .components()
+                .filter(|component| *component != Component::CurDir)
+                .collect(),
+            line,
+        }
+    }
+
+    /// File of breakpoint matches the whole file path or its last components: `main.ep` matches `mods/main.ep`.
+    pub fn matches(&self, frame: &StackFrame) -> bool {
+        frame.line == self.line && Path::new(&frame.file).ends_with(&self.file)
+    }
+}
+
+pub type PauseHandler = Box<dyn FnMut(&mut PausedContext) -> DebugAction>;
+
+/// Pauses execution on breakpoints and steps and passes control to handler.
+/// Attach it with `Engine::set_debug_hook`.
+pub struct Debugger {
+    breakpoints: RefCell<Vec<Breakpoint>>,
+    mode: Cell<StepMode>,
+    paused: Cell<bool>,
+    handler: RefCell<PauseHandler>,
+}
+
+impl Debugger {
+    pub fn new(handler: impl FnMut(&mut PausedContext) -> DebugAction + 'static) -> Debugger {
+        Debugger {
+            breakpoints: RefCell::new(Vec::new()),
+            mode: Cell::new(StepMode::Run),
+            paused: Cell::new(false),
+            handler: RefCell::new(Box::new(handler)),
+        }
+    }
+
+    /// Returns `false`, if breakpoint already exists.
+    pub fn add_breakpoint(&self, file: &str, line: u32) -> bool {
+        let breakpoint = Breakpoint::new(file, line);
+        let mut breakpoints = self.breakpoints.borrow_mut();
+
+        if breakpoints.contains(&breakpoint) {
+            return false;
+        }
+
+        breakpoints.push(breakpoint);
+        true
+    }
+
+    pub fn remove_breakpoint(&self, file: &str, line: u32) -> bool {
+        let breakpoint = Breakpoint::new(file, line);
+        let mut breakpoints = self.breakpoints.borrow_mut();
+        let count = breakpoints.len();
+        breakpoints.retain(|item| *item != breakpoint);
+        breakpoints.len() != count
+    }
+
+    pub fn get_breakpoints(&self) -> Vec<Breakpoint> {
+        self.breakpoints.borrow().clone()
+    }
+
+    /// Pauses before the next statement.
+    pub fn pause(&self) {
+        self.mode.set(StepMode::Into);
+    }
+}
+
+impl fmt::Debug for Debugger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Debugger")
+            .field("breakpoints", &self.breakpoints)
+            .field("mode", &self.mode)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DebugHook for Debugger {
+    fn on_statement(&self, scope: &mut Scope) -> bool {
+        // Statements of expressions, evaluated while paused, don't pause again.
+        if self.paused.get() {
+            return true;
+        }
+
+        let engine = scope.get_engine();
+        let call_stack = engine.get_call_stack();
+        let depth = call_stack.len();
+        let at_breakpoint = call_stack.last().is_some_and(|frame| {
+            self.breakpoints
+                .borrow()
+                .iter()
+                .any(|breakpoint| breakpoint.matches(frame))
+        });
+        drop(engine);
+        let stepped = match self.mode.get() {
+            StepMode::Run => false,
+            StepMode::Into => true,
+            StepMode::Over(frame_depth) => depth <= frame_depth,
+            StepMode::Out(frame_depth) => depth < frame_depth,
+            StepMode::Stopped => return false,
+        };
+
+        if !at_breakpoint && !stepped {
+            return true;
+        }
+
+        let mut context = PausedContext {
+            debugger: self,
+            scope,
+            reason: if at_breakpoint {
+                PauseReason::Breakpoint
+            } else {
+                PauseReason::Step
+            },
+        };
+        self.paused.set(true);
+        let action = (self.handler.borrow_mut())(&mut context);
+        self.paused.set(false);
+        self.mode.set(match action {
+            DebugAction::Continue => StepMode::Run,
+            DebugAction::StepInto => StepMode::Into,
+            DebugAction::StepOver => StepMode::Over(depth),
+            DebugAction::StepOut => StepMode::Out(depth),
+            DebugAction::Stop => StepMode::Stopped,
+        });
+        action != DebugAction::Stop
+    }
+}
+
+/// State of paused program, passed to handler of debugger.
+pub struct PausedContext<'a> {
+    debugger: &'a Debugger,
+    scope: &'a mut Scope,
+    reason: PauseReason,
+}
+
+impl PausedContext<'_> {
+    pub fn get_reason(&self) -> PauseReason {
+        self.reason
+    }
+
+    pub fn get_debugger(&self) -> &Debugger {
+        self.debugger
+    }
+
+    /// Returns frames from the outermost to the innermost one.
+    pub fn get_call_stack(&self) -> Ref<'_, Vec<StackFrame>> {
+        Ref::map(self.scope.get_engine(), |engine| engine.get_call_stack())
+    }
+
+    /// Returns the innermost frame, that is paused before its current statement.
+    pub fn get_frame(&self) -> Option<Ref<'_, StackFrame>> {
+        Ref::filter_map(self.get_call_stack(), |call_stack| call_stack.last()).ok()
+    }
+
+    /// Functions are executed in scope of caller, so locals are all variables, visible from paused statement.
+    pub fn get_locals(&self) -> BTreeMap<String, Rc<dyn Variant>> {
+        self.scope.get_variables()
+    }
+
+    pub fn get_scope(&mut self) -> &mut Scope {
+        self.scope
+    }
+
+    /// Evaluates expression in paused frame. Expression may change variables.
+    #[cfg(feature = "parser")]
+    pub fn evaluate(
+        &mut self,
+        code: &str,
+    ) -> Result<Result<Rc<dyn Variant>, NativeException>, RunnerError> {
+        evaluate(code, self.scope)
+    }
+}
+
+#[cfg(feature = "parser")]
+static EVAL_PREFIX: &str = "eval(";
+
+/// Evaluates single expression: call, variable, constant, list or map.
+#[cfg(feature = "parser")]
+pub fn evaluate(
+    code: &str,
+    scope: &mut Scope,
+) -> Result<Result<Rc<dyn Variant>, NativeException>, RunnerError> {
+    let parse_result = parse(&format!("{}{})", EVAL_PREFIX, code));
+
+    if parse_result.is_err() {
+        let error = unsafe { parse_result.unwrap_err_unchecked() };
+        let column = if error.line == 1 {
+            error.column.saturating_sub(EVAL_PREFIX.len() as u32)
+        } else {
+            error.column
+        };
+        return Err(RunnerError::new(
+            error.line,
+            column,
+            &format!("Parser: {}", error.description),
+        ));
+    }
+
+    let ast = unsafe { parse_result.unwrap_unchecked() };
+
+    if ast.body.len() != 1 || ast.body[0].as_call_func().args.len() != 1 {
+        return Err(RunnerError::new(1, 1, "Expected one expression"));
+    }
+
+    evaluate_node(scope, ast.body[0].as_call_func().args[0].clone())
+}
+
+#[cfg(feature = "parser")]
+static DEBUG_PROMPT: &str = "(debug) ";
+#[cfg(feature = "parser")]
+static DEBUG_HELP: &str = "Commands:
+  break [file:]line   (b)  add breakpoint
+  delete [file:]line  (d)  remove breakpoint
+  breakpoints              list breakpoints
+  continue            (c)  run until breakpoint
+  step                (s)  step into calls
+  next                (n)  step over calls
+  finish              (f)  step out of current frame
+  locals              (l)  print variables
+  backtrace           (bt) print call stack
+  print expression    (p)  evaluate expression in paused frame
+  quit                (q)  stop program";
+
+/// Command line interface of debugger.
+#[cfg(feature = "parser")]
+struct DebugConsole<R: BufRead, W: Write> {
+    input: R,
+    output: W,
+    sources: HashMap<String, Option<Vec<String>>>,
+}
+
+#[cfg(feature = "parser")]
+impl<R: BufRead, W: Write> DebugConsole<R, W> {
+    fn get_source_line(&mut self, file: &str, line: u32) -> Option<String> {
+        let lines = self.sources.entry(file.to_string()).or_insert_with(|| {
+            fs::read_to_string(file)
+                .ok()
+                .map(|code| code.lines().map(str::to_string).collect())
+        });
+        lines
+            .as_ref()?
+            .get((line as usize).checked_sub(1)?)
+            .cloned()
+    }
+
+    fn print_location(&mut self, context: &PausedContext) {
+        let frame = context.get_frame();
+
+        if frame.is_none() {
+            let _ = writeln!(self.output, "Paused");
+            return;
+        }
+
+        let frame = unsafe { frame.unwrap_unchecked() };
+        let _ = writeln!(
+            self.output,
+            "{} at {}:{}:{} in {}",
+            match context.get_reason() {
+                PauseReason::Breakpoint => "Breakpoint",
+                PauseReason::Step => "Paused",
+            },
+            frame.file,
+            frame.line,
+            frame.column,
+            frame.name
+        );
+
+        if let Some(source) = self.get_source_line(&frame.file, frame.line) {
+            let _ = writeln!(self.output, "{:>5} | {}", frame.line, source);
+        }
+    }
+
+    fn parse_location(&self, context: &PausedContext, location: &str) -> Option<(String, u32)> {
+        let (file, line) = match location.rsplit_once(':') {
+            Some((file, line)) => (file.to_string(), line),
+            None => (context.get_frame()?.file.clone(), location),
+        };
+        let line = line.trim().parse::<u32>().ok()?;
+
+        if file.is_empty() || line == 0 {
+            return None;
+        }
+
+        Some((file, line))
+    }
+
+    fn print_value(&mut self, value: &Rc<dyn Variant>) {
+        let _ = writeln!(self.output, "{:?}", value);
+    }
+
+    fn execute_command(&mut self, context: &mut PausedContext, line: &str) -> Option<DebugAction> {
+        let (command, argument) = match line.split_once(char::is_whitespace) {
+            Some((command, argument)) => (command, argument.trim()),
+            None => (line, ""),
+        };
+
+        match command {
+            "c" | "continue" => return Some(DebugAction::Continue),
+            "s" | "step" => return Some(DebugAction::StepInto),
+            "n" | "next" => return Some(DebugAction::StepOver),
+            "f" | "finish" => return Some(DebugAction::StepOut),
+            "q" | "quit" => return Some(DebugAction::Stop),
+            "b" | "break" | "d" | "delete" => match self.parse_location(context, argument) {
+                Some((file, line)) if command.starts_with('b') => {
+                    context.get_debugger().add_breakpoint(&file, line);
+                    let _ = writeln!(self.output, "Breakpoint at {}:{}", file, line);
+                }
+                Some((file, line)) => {
+                    if !context.get_debugger().remove_breakpoint(&file, line) {
+                        let _ = writeln!(self.output, "No breakpoint at {}:{}", file, line);
+                    }
+                }
+                None => {
+                    let _ = writeln!(self.output, "Invalid breakpoint location '{}'", argument);
+                }
+            },
+            "breakpoints" => {
+                for breakpoint in context.get_debugger().get_breakpoints() {
+                    let _ = writeln!(
+                        self.output,
+                        "{}:{}",
+                        breakpoint.file.display(),
+                        breakpoint.line
+                    );
+                }
+            }
+            "l" | "locals" => {
+                for (name, value) in context.get_locals() {
+                    let _ = write!(self.output, "{} = ", name);
+                    self.print_value(&value);
+                }
+            }
+            "bt" | "backtrace" => {
+                let frames: Vec<StackFrame> = context.get_call_stack().clone();
+
+                for (index, frame) in frames.iter().rev().enumerate() {
+                    let _ = writeln!(
+                        self.output,
+                        "#{} {} at {}:{}:{}",
+                        index, frame.name, frame.file, frame.line, frame.column
+                    );
+                }
+            }
+            "p" | "print" => match context.evaluate(argument) {
+                Ok(Ok(value)) => self.print_value(&value),
+                Ok(Err(error)) => {
+                    let _ = writeln!(
+                        self.output,
+                        "Error on line {} column {}: {}",
+                        error.line, error.column, error.description
+                    );
+                }
+                Err(error) => {
+                    let _ = writeln!(
+                        self.output,
+                        "Error on line {} column {}: {}",
+                        error.line, error.column, error.description
+                    );
+                }
+            },
+            "h" | "help" => {
+                let _ = writeln!(self.output, "{}", DEBUG_HELP);
+            }
+            "" => {}
+            _ => {
+                let _ = writeln!(
+                    self.output,
+                    "Unknown command '{}', type 'help' for list of commands",
+                    command
+                );
+            }
+        }
+
+        None
+    }
+
+    fn on_pause(&mut self, context: &mut PausedContext) -> DebugAction {
+        self.print_location(context);
+
+        loop {
+            let _ = write!(self.output, "{}", DEBUG_PROMPT);
+            let _ = self.output.flush();
+            let mut line = String::new();
+
+            // Program is stopped, when input ends.
+            match self.input.read_line(&mut line) {
+                Ok(0) | Err(_) => return DebugAction::Stop,
+                Ok(_) => {}
+            }
+
+            if let Some(action) = self.execute_command(context, line.trim()) {
+                return action;
+            }
+        }
+    }
+}
+
+/// Runs file under debugger, that reads commands from `input`. Program is paused before the first statement.
+#[cfg(feature = "parser")]
+pub fn debug_file_ex<R: BufRead + 'static, W: Write + 'static>(
+    path: &str,
+    scope: &mut Scope,
+    input: R,
+    output: W,
+) -> bool {
+    let mut console = DebugConsole {
+        input,
+        output,
+        sources: HashMap::new(),
+    };
+    let debugger = Rc::new(Debugger::new(move |context| console.on_pause(context)));
+    debugger.pause();
+    let previous_hook = scope.get_engine().get_debug_hook();
+    scope.get_engine_mut().set_debug_hook(Some(debugger));
+    let result = run_file_scope(path, scope);
+    scope.get_engine_mut().set_debug_hook(previous_hook);
+    result
+}
+
+#[cfg(feature = "parser")]
+pub fn debug_file(path: &str, scope: &mut Scope) -> bool {
+    debug_file_ex(path, scope, BufReader::new(io::stdin()), io::stdout())
+}
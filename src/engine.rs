@@ -0,0 +1,399 @@
+use crate::clock::{Clock, SystemClock};
+use crate::filesystem::{FileSystem, OsFileSystem};
+use crate::random::Random;
+use crate::types::{CustomLoader, Scope};
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Debug,
+    fs,
+    path::{Component, Path, PathBuf},
+    rc::Rc,
+};
+
+#[repr(u8)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Permission {
+    Stdio,
+    FileRead,
+    FileWrite,
+    ProcessExit,
+    ProcessSpawn,
+    Debug,
+    Env,
+}
+
+impl Permission {
+    pub fn all() -> Vec<Permission> {
+        vec![
+            Permission::Stdio,
+            Permission::FileRead,
+            Permission::FileWrite,
+            Permission::ProcessExit,
+            Permission::ProcessSpawn,
+            Permission::Debug,
+            Permission::Env,
+        ]
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Permission::Stdio => "stdio",
+            Permission::FileRead => "file read",
+            Permission::FileWrite => "file write",
+            Permission::ProcessExit => "process exit",
+            Permission::ProcessSpawn => "process spawn",
+            Permission::Debug => "debug",
+            Permission::Env => "environment",
+        }
+    }
+}
+
+/// Makes host path absolute and resolves `.`, `..` and symbolic links of its existing part.
+/// Symbolic links are resolved before `..` is applied, so `link/..` is the parent of link target.
+pub fn normalize_path(path: &str) -> PathBuf {
+    let mut result = PathBuf::new();
+
+    if Path::new(path).is_relative() {
+        if let Ok(current_dir) = env::current_dir() {
+            result = fs::canonicalize(&current_dir).unwrap_or(current_dir);
+        }
+    }
+
+    for component in Path::new(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                result.pop();
+            }
+            Component::Normal(name) => {
+                result.push(name);
+
+                // Missing paths can't contain links, so the rest of path is resolved as text.
+                if let Ok(canonical) = fs::canonicalize(&result) {
+                    result = canonical;
+                }
+            }
+            _ => result.push(component),
+        }
+    }
+
+    result
+}
+
+#[derive(Debug, Clone)]
+pub struct Policy {
+    permissions: Vec<Permission>,
+    path_prefixes: Option<Vec<String>>,
+}
+
+impl Policy {
+    pub fn allow_all() -> Policy {
+        Policy {
+            permissions: Permission::all(),
+            path_prefixes: None,
+        }
+    }
+
+    pub fn deny_all() -> Policy {
+        Policy {
+            permissions: Vec::new(),
+            path_prefixes: Some(Vec::new()),
+        }
+    }
+
+    pub fn allow(&mut self, permission: Permission) -> &mut Policy {
+        if !self.is_allowed(permission) {
+            self.permissions.push(permission);
+        }
+
+        self
+    }
+
+    pub fn deny(&mut self, permission: Permission) -> &mut Policy {
+        self.permissions.retain(|allowed| *allowed != permission);
+        self
+    }
+
+    pub fn is_allowed(&self, permission: Permission) -> bool {
+        self.permissions.contains(&permission)
+    }
+
+    /// Restricts file access to files inside of `prefix`.
+    /// Can be called multiple times to allow several directories.
+    pub fn allow_path(&mut self, prefix: &str) -> &mut Policy {
+        if self.path_prefixes.is_none() {
+            self.path_prefixes = Some(vec![prefix.to_string()]);
+        } else {
+            unsafe { self.path_prefixes.as_mut().unwrap_unchecked() }.push(prefix.to_string());
+        }
+
+        self
+    }
+
+    /// Removes all path restrictions.
+    pub fn allow_any_path(&mut self) -> &mut Policy {
+        self.path_prefixes = None;
+        self
+    }
+
+    /// Checks `path` of `file_system`. Path and prefixes are normalized by `file_system`,
+    /// so they refer to the same files, that natives access.
+    pub fn is_path_allowed(&self, file_system: &dyn FileSystem, path: &str) -> bool {
+        if self.path_prefixes.is_none() {
+            return true;
+        }
+
+        let normalized = file_system.normalize_path(path);
+
+        unsafe { self.path_prefixes.as_ref().unwrap_unchecked() }
+            .iter()
+            .any(|prefix| normalized.starts_with(file_system.normalize_path(prefix)))
+    }
+}
+
+impl Default for Policy {
+    /// Allows everything except spawning processes, which host should grant explicitly.
+    fn default() -> Self {
+        let mut policy = Self::allow_all();
+        policy.deny(Permission::ProcessSpawn);
+        policy
+    }
+}
+
+/// Function or block, that is being executed, and position of its current statement.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct StackFrame {
+    pub name: String,
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl StackFrame {
+    pub fn new(name: &str, file: &str, line: u32, column: u32) -> StackFrame {
+        StackFrame {
+            name: name.to_string(),
+            file: file.to_string(),
+            line,
+            column,
+        }
+    }
+}
+
+/// Receives control before each statement, while it's attached with `Engine::set_debug_hook`.
+pub trait DebugHook: Debug {
+    /// Returns `false` to stop execution.
+    fn on_statement(&self, scope: &mut Scope) -> bool;
+}
+
+#[derive(Debug, Clone)]
+pub struct Engine {
+    module_paths: Vec<String>,
+    dependencies: HashMap<String, String>,
+    loaded_modules: Vec<String>,
+    policy: Policy,
+    file_system: Rc<dyn FileSystem>,
+    clock: Rc<dyn Clock>,
+    strict_conditions: bool,
+    deterministic: bool,
+    random: Random,
+    random_seed_locked: bool,
+    script_args: Vec<String>,
+    custom_loaders: HashMap<usize, CustomLoader>,
+    debug_hook: Option<Rc<dyn DebugHook>>,
+    call_stack: Vec<StackFrame>,
+    #[cfg(feature = "runner")]
+    native_calls: Vec<String>,
+}
+
+impl Engine {
+    pub fn new() -> Engine {
+        Engine {
+            module_paths: Vec::new(),
+            dependencies: HashMap::new(),
+            loaded_modules: Vec::new(),
+            policy: Policy::default(),
+            file_system: Rc::new(OsFileSystem::new()),
+            clock: Rc::new(SystemClock::new()),
+            strict_conditions: false,
+            deterministic: false,
+            random: Random::default(),
+            random_seed_locked: false,
+            script_args: Vec::new(),
+            custom_loaders: HashMap::new(),
+            debug_hook: None,
+            call_stack: Vec::new(),
+            #[cfg(feature = "runner")]
+            native_calls: Vec::new(),
+        }
+    }
+
+    pub fn get_file_system(&self) -> Rc<dyn FileSystem> {
+        self.file_system.clone()
+    }
+
+    pub fn set_file_system(&mut self, file_system: Rc<dyn FileSystem>) {
+        self.file_system = file_system;
+    }
+
+    pub fn get_clock(&self) -> Rc<dyn Clock> {
+        self.clock.clone()
+    }
+
+    pub fn set_clock(&mut self, clock: Rc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    pub fn get_policy(&self) -> &Policy {
+        &self.policy
+    }
+
+    pub fn get_policy_mut(&mut self) -> &mut Policy {
+        &mut self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: Policy) {
+        self.policy = policy;
+    }
+
+    /// Checks `path` against policy using file system of engine.
+    pub fn is_path_allowed(&self, path: &str) -> bool {
+        self.policy.is_path_allowed(self.file_system.as_ref(), path)
+    }
+
+    /// In strict mode conditions of `if`, `if_else`, `and`, `or` and `not` should be `Bool`,
+    /// otherwise `Int` is accepted too and everything except `0` is true.
+    pub fn is_strict_conditions(&self) -> bool {
+        self.strict_conditions
+    }
+
+    pub fn set_strict_conditions(&mut self, strict: bool) {
+        self.strict_conditions = strict;
+    }
+
+    /// In deterministic mode functions, that depend on host (`input`, `env_get`, `env_vars`, `run_process`),
+    /// throw exception, and time functions work only with deterministic clock, such as `ManualClock`.
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.deterministic = deterministic;
+    }
+
+    pub fn get_random(&self) -> &Random {
+        &self.random
+    }
+
+    pub fn get_random_mut(&mut self) -> &mut Random {
+        &mut self.random
+    }
+
+    /// Restarts random sequence from `seed`. Works even if seed is locked.
+    pub fn set_random_seed(&mut self, seed: u64) {
+        self.random.set_seed(seed);
+    }
+
+    /// Forbids scripts to change seed with `rand_seed`, so host keeps replays in sync.
+    pub fn lock_random_seed(&mut self, locked: bool) {
+        self.random_seed_locked = locked;
+    }
+
+    pub fn is_random_seed_locked(&self) -> bool {
+        self.random_seed_locked
+    }
+
+    /// Sets arguments, that scripts get with `argc` and `argv`.
+    pub fn set_script_args(&mut self, args: Vec<String>) {
+        self.script_args = args;
+    }
+
+    pub fn get_script_args(&self) -> &Vec<String> {
+        &self.script_args
+    }
+
+    pub fn get_debug_hook(&self) -> Option<Rc<dyn DebugHook>> {
+        self.debug_hook.clone()
+    }
+
+    /// Attaches debugger. Call stack is tracked only while debug hook is attached.
+    pub fn set_debug_hook(&mut self, debug_hook: Option<Rc<dyn DebugHook>>) {
+        self.debug_hook = debug_hook;
+    }
+
+    pub fn is_debugging(&self) -> bool {
+        self.debug_hook.is_some()
+    }
+
+    /// Returns frames from the outermost to the innermost one.
+    pub fn get_call_stack(&self) -> &Vec<StackFrame> {
+        &self.call_stack
+    }
+
+    #[cfg(feature = "runner")]
+    pub(crate) fn get_call_stack_mut(&mut self) -> &mut Vec<StackFrame> {
+        &mut self.call_stack
+    }
+
+    /// Names of native functions being called. Blocks, that they execute, get their names in call stack.
+    #[cfg(feature = "runner")]
+    pub(crate) fn get_native_calls_mut(&mut self) -> &mut Vec<String> {
+        &mut self.native_calls
+    }
+
+    /// Registers loader of custom type with `id` for scope snapshots.
+    pub fn add_custom_loader(&mut self, id: usize, loader: CustomLoader) -> Option<CustomLoader> {
+        self.custom_loaders.insert(id, loader)
+    }
+
+    pub fn get_custom_loader(&self, id: usize) -> Option<CustomLoader> {
+        self.custom_loaders.get(&id).copied()
+    }
+
+    pub fn add_module_path(&mut self, path: &str) {
+        if !self
+            .module_paths
+            .iter()
+            .any(|module_path| module_path == path)
+        {
+            self.module_paths.push(path.to_string());
+        }
+    }
+
+    pub fn get_module_paths(&self) -> &Vec<String> {
+        &self.module_paths
+    }
+
+    pub fn clear_module_paths(&mut self) {
+        self.module_paths.clear();
+    }
+
+    pub fn add_dependency(&mut self, name: &str, path: &str) -> Option<String> {
+        self.dependencies.insert(name.to_string(), path.to_string())
+    }
+
+    pub fn get_dependency(&self, name: &str) -> Option<&String> {
+        self.dependencies.get(name)
+    }
+
+    pub fn is_module_loaded(&self, path: &str) -> bool {
+        self.loaded_modules.iter().any(|module| module == path)
+    }
+
+    pub fn set_module_loaded(&mut self, path: &str) {
+        if !self.is_module_loaded(path) {
+            self.loaded_modules.push(path.to_string());
+        }
+    }
+
+    pub fn unset_module_loaded(&mut self, path: &str) {
+        self.loaded_modules.retain(|module| module != path);
+    }
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
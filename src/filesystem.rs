@@ -0,0 +1,820 @@
+use crate::engine::normalize_path;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    fs,
+    io::{self, Cursor, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+    rc::Rc,
+    time::UNIX_EPOCH,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct OpenMode {
+    pub read: bool,
+    pub write: bool,
+    pub append: bool,
+    pub truncate: bool,
+    pub create: bool,
+}
+
+impl OpenMode {
+    /// Parses C-like mode string (`r`, `w`, `a`, `r+`, `w+`, `a+`, optionally with `b`).
+    pub fn parse(mode: &str) -> Option<OpenMode> {
+        let mut chars = mode.chars().filter(|chr| *chr != 'b');
+        let first = chars.next()?;
+        let plus = match chars.next() {
+            None => false,
+            Some('+') => true,
+            Some(_) => return None,
+        };
+
+        if chars.next().is_some() {
+            return None;
+        }
+
+        let mut open_mode = match first {
+            'r' => OpenMode {
+                read: true,
+                ..OpenMode::default()
+            },
+            'w' => OpenMode {
+                write: true,
+                truncate: true,
+                create: true,
+                ..OpenMode::default()
+            },
+            'a' => OpenMode {
+                write: true,
+                append: true,
+                create: true,
+                ..OpenMode::default()
+            },
+            _ => return None,
+        };
+
+        if plus {
+            open_mode.read = true;
+            open_mode.write = true;
+        }
+
+        Some(open_mode)
+    }
+}
+
+pub trait FileHandle: Read + Write + Seek {}
+
+impl<T: Read + Write + Seek> FileHandle for T {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FileInfo {
+    pub is_dir: bool,
+    pub size: u64,
+    /// Milliseconds since Unix epoch, if file system tracks modification time.
+    pub modified: Option<i64>,
+}
+
+/// Storage, that file natives work with.
+/// Paths passed to file system are paths, as script sees them.
+pub trait FileSystem: Debug {
+    fn open(&self, path: &str, mode: OpenMode) -> io::Result<Box<dyn FileHandle>>;
+    fn metadata(&self, path: &str) -> io::Result<FileInfo>;
+    /// Returns sorted names of entries in directory.
+    fn list_dir(&self, path: &str) -> io::Result<Vec<String>>;
+    /// Creates directory and all its missing parents.
+    fn create_dir(&self, path: &str) -> io::Result<()>;
+    /// Removes file or empty directory.
+    fn remove(&self, path: &str) -> io::Result<()>;
+    fn rename(&self, from: &str, to: &str) -> io::Result<()>;
+    /// Returns unique name of existing file, that is the same for every path pointing to it.
+    fn canonicalize(&self, path: &str) -> io::Result<String>;
+
+    /// Makes path absolute and resolves `.`, `..` and symbolic links, so policy can compare it with allowed prefixes.
+    /// Unlike `canonicalize`, works for missing paths. By default path is resolved as text relative to `/`.
+    fn normalize_path(&self, path: &str) -> PathBuf {
+        PathBuf::from(normalize_memory_path(path))
+    }
+
+    /// Maps path to path on host disk, that processes started by script should get.
+    /// Returns `None`, if file system doesn't keep files on host disk or path can't be accessed.
+    fn to_host_path(&self, _path: &str) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns `true`, if files depend only on calls of this file system and its host,
+    /// so file system can be used in deterministic mode.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    fn read_to_string(&self, path: &str) -> io::Result<String> {
+        let mut content = String::new();
+        self.open(path, OpenMode::parse("r").unwrap_or_default())?
+            .read_to_string(&mut content)?;
+        Ok(content)
+    }
+}
+
+fn canonicalize_host_path(path: &Path) -> io::Result<String> {
+    Ok(fs::canonicalize(path)?.to_string_lossy().to_string())
+}
+
+fn get_host_metadata(path: &Path) -> io::Result<FileInfo> {
+    let metadata = fs::metadata(path)?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as i64);
+
+    Ok(FileInfo {
+        is_dir: metadata.is_dir(),
+        size: metadata.len(),
+        modified,
+    })
+}
+
+fn list_host_dir(path: &Path) -> io::Result<Vec<String>> {
+    let mut names: Vec<String> = Vec::new();
+
+    for entry in fs::read_dir(path)? {
+        names.push(entry?.file_name().to_string_lossy().to_string());
+    }
+
+    names.sort();
+    Ok(names)
+}
+
+fn remove_host_path(path: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        return fs::remove_dir(path);
+    }
+
+    fs::remove_file(path)
+}
+
+fn get_open_options(mode: OpenMode) -> fs::OpenOptions {
+    let mut options = fs::OpenOptions::new();
+    options
+        .read(mode.read)
+        .write(mode.write)
+        .append(mode.append)
+        .truncate(mode.truncate)
+        .create(mode.create);
+    options
+}
+
+/// Real file system of the host.
+#[derive(Debug, Clone, Default)]
+pub struct OsFileSystem {}
+
+impl OsFileSystem {
+    pub fn new() -> OsFileSystem {
+        OsFileSystem {}
+    }
+}
+
+impl FileSystem for OsFileSystem {
+    fn open(&self, path: &str, mode: OpenMode) -> io::Result<Box<dyn FileHandle>> {
+        let file = get_open_options(mode).open(path)?;
+        Ok(Box::new(file))
+    }
+
+    fn metadata(&self, path: &str) -> io::Result<FileInfo> {
+        get_host_metadata(Path::new(path))
+    }
+
+    fn list_dir(&self, path: &str) -> io::Result<Vec<String>> {
+        list_host_dir(Path::new(path))
+    }
+
+    fn create_dir(&self, path: &str) -> io::Result<()> {
+        fs::create_dir_all(path)
+    }
+
+    fn remove(&self, path: &str) -> io::Result<()> {
+        remove_host_path(Path::new(path))
+    }
+
+    fn rename(&self, from: &str, to: &str) -> io::Result<()> {
+        fs::rename(from, to)
+    }
+
+    fn canonicalize(&self, path: &str) -> io::Result<String> {
+        canonicalize_host_path(Path::new(path))
+    }
+
+    fn normalize_path(&self, path: &str) -> PathBuf {
+        normalize_path(path)
+    }
+
+    fn to_host_path(&self, path: &str) -> Option<PathBuf> {
+        Some(PathBuf::from(path))
+    }
+}
+
+/// Real file system, where script can see only files inside of `root` directory.
+/// Both `/file` and `file` refer to `<root>/file`, `..` never leaves `root`.
+#[derive(Debug, Clone)]
+pub struct JailFileSystem {
+    root: PathBuf,
+}
+
+impl JailFileSystem {
+    pub fn new(root: &str) -> JailFileSystem {
+        JailFileSystem {
+            root: fs::canonicalize(root).unwrap_or(PathBuf::from(root)),
+        }
+    }
+
+    pub fn get_root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn to_host_path(&self, path: &str) -> io::Result<PathBuf> {
+        let mut host_path = self.root.clone();
+        let mut depth = 0;
+
+        for component in Path::new(path).components() {
+            match component {
+                Component::Normal(part) => {
+                    host_path.push(part);
+                    depth += 1;
+                }
+                Component::ParentDir => {
+                    if depth == 0 {
+                        return Err(io::Error::new(
+                            io::ErrorKind::PermissionDenied,
+                            "Path leaves file system root",
+                        ));
+                    }
+
+                    host_path.pop();
+                    depth -= 1;
+                }
+                _ => {}
+            }
+        }
+
+        // Path may not exist yet (new file, directory or rename target), so check the nearest
+        // existing ancestor. Dangling link is an existing entry, that can't be resolved.
+        let mut existing = host_path.as_path();
+
+        while fs::symlink_metadata(existing).is_err() {
+            match existing.parent() {
+                Some(parent) => existing = parent,
+                None => break,
+            }
+        }
+
+        let canonical = fs::canonicalize(existing);
+
+        if canonical.is_err() || !unsafe { canonical.unwrap_unchecked() }.starts_with(&self.root) {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "Path leaves file system root",
+            ));
+        }
+
+        Ok(host_path)
+    }
+}
+
+impl FileSystem for JailFileSystem {
+    fn open(&self, path: &str, mode: OpenMode) -> io::Result<Box<dyn FileHandle>> {
+        let host_path = self.to_host_path(path)?;
+        let file = get_open_options(mode).open(host_path)?;
+        Ok(Box::new(file))
+    }
+
+    fn metadata(&self, path: &str) -> io::Result<FileInfo> {
+        get_host_metadata(&self.to_host_path(path)?)
+    }
+
+    fn list_dir(&self, path: &str) -> io::Result<Vec<String>> {
+        list_host_dir(&self.to_host_path(path)?)
+    }
+
+    fn create_dir(&self, path: &str) -> io::Result<()> {
+        fs::create_dir_all(self.to_host_path(path)?)
+    }
+
+    fn remove(&self, path: &str) -> io::Result<()> {
+        let host_path = self.to_host_path(path)?;
+
+        if host_path == self.root {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "Can't remove file system root",
+            ));
+        }
+
+        remove_host_path(&host_path)
+    }
+
+    fn rename(&self, from: &str, to: &str) -> io::Result<()> {
+        fs::rename(self.to_host_path(from)?, self.to_host_path(to)?)
+    }
+
+    fn canonicalize(&self, path: &str) -> io::Result<String> {
+        canonicalize_host_path(&self.to_host_path(path)?)
+    }
+
+    fn to_host_path(&self, path: &str) -> Option<PathBuf> {
+        JailFileSystem::to_host_path(self, path).ok()
+    }
+
+    fn normalize_path(&self, path: &str) -> PathBuf {
+        let mut host_path = self.root.clone();
+
+        for component in Path::new(path).components() {
+            match component {
+                Component::Normal(part) => {
+                    host_path.push(part);
+
+                    if let Ok(canonical) = fs::canonicalize(&host_path) {
+                        host_path = canonical;
+                    }
+                }
+                Component::ParentDir if host_path != self.root => {
+                    host_path.pop();
+                }
+                _ => {}
+            }
+        }
+
+        // Links, that leave root, can't be accessed, so their host path is kept as is.
+        match host_path.strip_prefix(&self.root) {
+            Ok(relative) => Path::new("/").join(relative),
+            Err(_) => host_path,
+        }
+    }
+}
+
+fn normalize_memory_path(path: &str) -> String {
+    let mut parts: Vec<String> = Vec::new();
+
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().to_string()),
+            Component::ParentDir => {
+                parts.pop();
+            }
+            _ => {}
+        }
+    }
+
+    "/".to_string() + &parts.join("/")
+}
+
+fn get_memory_parent(path: &str) -> &str {
+    match path.rfind('/') {
+        Some(0) | None => "/",
+        Some(index) => &path[..index],
+    }
+}
+
+fn not_found_error() -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, "No such file or directory")
+}
+
+type MemoryFiles = Rc<RefCell<HashMap<String, Vec<u8>>>>;
+
+/// File system, that exists only in memory. Clones share the same files.
+/// Directories exist if they were created or contain files.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFileSystem {
+    files: MemoryFiles,
+    dirs: Rc<RefCell<HashSet<String>>>,
+}
+
+impl MemoryFileSystem {
+    pub fn new() -> MemoryFileSystem {
+        MemoryFileSystem::default()
+    }
+
+    pub fn add_file(&self, path: &str, content: &[u8]) {
+        self.files
+            .borrow_mut()
+            .insert(normalize_memory_path(path), content.to_vec());
+    }
+
+    pub fn get_file(&self, path: &str) -> Option<Vec<u8>> {
+        self.files
+            .borrow()
+            .get(&normalize_memory_path(path))
+            .cloned()
+    }
+
+    pub fn get_paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = self.files.borrow().keys().cloned().collect();
+        paths.sort();
+        paths
+    }
+
+    fn is_dir(&self, normalized: &str) -> bool {
+        if normalized == "/" || self.dirs.borrow().contains(normalized) {
+            return true;
+        }
+
+        let prefix = normalized.to_string() + "/";
+        self.files
+            .borrow()
+            .keys()
+            .any(|file| file.starts_with(&prefix))
+            || self
+                .dirs
+                .borrow()
+                .iter()
+                .any(|dir| dir.starts_with(&prefix))
+    }
+}
+
+struct MemoryFile {
+    files: MemoryFiles,
+    path: String,
+    cursor: Cursor<Vec<u8>>,
+    mode: OpenMode,
+}
+
+impl Read for MemoryFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if !self.mode.read {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "File isn't opened for reading",
+            ));
+        }
+
+        self.cursor.read(buf)
+    }
+}
+
+impl Write for MemoryFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.mode.write {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "File isn't opened for writing",
+            ));
+        }
+
+        if self.mode.append {
+            self.cursor.seek(SeekFrom::End(0))?;
+        }
+
+        let written = self.cursor.write(buf)?;
+        self.flush()?;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.files
+            .borrow_mut()
+            .insert(self.path.clone(), self.cursor.get_ref().clone());
+        Ok(())
+    }
+}
+
+impl Seek for MemoryFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.cursor.seek(pos)
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn open(&self, path: &str, mode: OpenMode) -> io::Result<Box<dyn FileHandle>> {
+        let normalized = normalize_memory_path(path);
+
+        if self.is_dir(&normalized) {
+            return Err(io::Error::other("Is a directory"));
+        }
+
+        let existing = self.files.borrow().get(&normalized).cloned();
+
+        let content = match existing {
+            Some(content) => {
+                if mode.truncate {
+                    Vec::new()
+                } else {
+                    content
+                }
+            }
+            None => {
+                if !mode.create {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        "No such file or directory",
+                    ));
+                }
+
+                Vec::new()
+            }
+        };
+
+        let mut file = MemoryFile {
+            files: self.files.clone(),
+            path: normalized,
+            cursor: Cursor::new(content),
+            mode,
+        };
+
+        if mode.write {
+            file.flush()?;
+        }
+
+        Ok(Box::new(file))
+    }
+
+    fn metadata(&self, path: &str) -> io::Result<FileInfo> {
+        let normalized = normalize_memory_path(path);
+        let size = self
+            .files
+            .borrow()
+            .get(&normalized)
+            .map(|content| content.len() as u64);
+
+        if size.is_some() {
+            return Ok(FileInfo {
+                is_dir: false,
+                size: unsafe { size.unwrap_unchecked() },
+                modified: None,
+            });
+        }
+
+        if self.is_dir(&normalized) {
+            return Ok(FileInfo {
+                is_dir: true,
+                size: 0,
+                modified: None,
+            });
+        }
+
+        Err(not_found_error())
+    }
+
+    fn list_dir(&self, path: &str) -> io::Result<Vec<String>> {
+        let normalized = normalize_memory_path(path);
+
+        if !self.is_dir(&normalized) {
+            return Err(not_found_error());
+        }
+
+        let prefix = if normalized == "/" {
+            "/".to_string()
+        } else {
+            normalized + "/"
+        };
+        let mut names: Vec<String> = Vec::new();
+        let files = self.files.borrow();
+        let dirs = self.dirs.borrow();
+
+        for entry in files.keys().chain(dirs.iter()) {
+            if !entry.starts_with(&prefix) {
+                continue;
+            }
+
+            let name = entry[prefix.len()..].split('/').next().unwrap_or("");
+
+            if !name.is_empty() && !names.iter().any(|existing| existing == name) {
+                names.push(name.to_string());
+            }
+        }
+
+        names.sort();
+        Ok(names)
+    }
+
+    fn create_dir(&self, path: &str) -> io::Result<()> {
+        let mut normalized = normalize_memory_path(path);
+
+        if self.files.borrow().contains_key(&normalized) {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, "File exists"));
+        }
+
+        while normalized != "/" {
+            let parent = get_memory_parent(&normalized).to_string();
+            self.dirs.borrow_mut().insert(normalized);
+            normalized = parent;
+        }
+
+        Ok(())
+    }
+
+    fn remove(&self, path: &str) -> io::Result<()> {
+        let normalized = normalize_memory_path(path);
+
+        if self.files.borrow_mut().remove(&normalized).is_some() {
+            return Ok(());
+        }
+
+        if normalized == "/" || !self.is_dir(&normalized) {
+            return Err(not_found_error());
+        }
+
+        if !self.list_dir(&normalized)?.is_empty() {
+            return Err(io::Error::other("Directory not empty"));
+        }
+
+        self.dirs.borrow_mut().remove(&normalized);
+        Ok(())
+    }
+
+    fn rename(&self, from: &str, to: &str) -> io::Result<()> {
+        let from_normalized = normalize_memory_path(from);
+        let to_normalized = normalize_memory_path(to);
+        let content = self.files.borrow_mut().remove(&from_normalized);
+
+        if content.is_some() {
+            self.files
+                .borrow_mut()
+                .insert(to_normalized, unsafe { content.unwrap_unchecked() });
+            return Ok(());
+        }
+
+        if from_normalized == "/" || !self.is_dir(&from_normalized) {
+            return Err(not_found_error());
+        }
+
+        let prefix = from_normalized.clone() + "/";
+        let rename_entry = |entry: &String| -> String {
+            if *entry == from_normalized {
+                return to_normalized.clone();
+            }
+
+            if entry.starts_with(&prefix) {
+                return to_normalized.clone() + "/" + &entry[prefix.len()..];
+            }
+
+            entry.clone()
+        };
+        let files: HashMap<String, Vec<u8>> = self
+            .files
+            .borrow_mut()
+            .drain()
+            .map(|(path, content)| (rename_entry(&path), content))
+            .collect();
+        let dirs: HashSet<String> = self
+            .dirs
+            .borrow_mut()
+            .drain()
+            .map(|dir| rename_entry(&dir))
+            .collect();
+        *self.files.borrow_mut() = files;
+        *self.dirs.borrow_mut() = dirs;
+        self.dirs.borrow_mut().insert(to_normalized);
+        Ok(())
+    }
+
+    fn canonicalize(&self, path: &str) -> io::Result<String> {
+        self.metadata(path)?;
+        Ok(normalize_memory_path(path))
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+static READ_BUFFER_SIZE: usize = 8192;
+
+/// Opened file with read buffer, shared by all file natives.
+pub struct OpenFile {
+    handle: Box<dyn FileHandle>,
+    buffer: Vec<u8>,
+    buffer_position: usize,
+}
+
+impl OpenFile {
+    pub fn new(handle: Box<dyn FileHandle>) -> OpenFile {
+        OpenFile {
+            handle,
+            buffer: Vec::new(),
+            buffer_position: 0,
+        }
+    }
+
+    fn buffered(&self) -> usize {
+        self.buffer.len() - self.buffer_position
+    }
+
+    fn fill_buffer(&mut self) -> io::Result<usize> {
+        if self.buffered() != 0 {
+            return Ok(self.buffered());
+        }
+
+        self.buffer.resize(READ_BUFFER_SIZE, 0);
+        self.buffer_position = 0;
+        let result = self.handle.read(&mut self.buffer);
+
+        if result.is_err() {
+            self.buffer.clear();
+            return result;
+        }
+
+        let read = unsafe { result.unwrap_unchecked() };
+        self.buffer.truncate(read);
+        Ok(read)
+    }
+
+    /// Moves real file position back to logical position and drops read buffer.
+    fn discard_buffer(&mut self) -> io::Result<()> {
+        let buffered = self.buffered() as i64;
+        self.buffer.clear();
+        self.buffer_position = 0;
+
+        if buffered != 0 {
+            self.handle.seek(SeekFrom::Current(-buffered))?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads line without line ending. Returns `None` if end of file is reached.
+    pub fn read_line(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut line: Vec<u8> = Vec::new();
+        let mut found_any = false;
+
+        loop {
+            if self.fill_buffer()? == 0 {
+                break;
+            }
+
+            found_any = true;
+            let available = &self.buffer[self.buffer_position..];
+            let newline = available.iter().position(|byte| *byte == b'\n');
+
+            if newline.is_some() {
+                let end = unsafe { newline.unwrap_unchecked() };
+                line.extend_from_slice(&available[..end]);
+                self.buffer_position += end + 1;
+
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+
+                return Ok(Some(line));
+            }
+
+            line.extend_from_slice(available);
+            self.buffer_position = self.buffer.len();
+        }
+
+        if !found_any {
+            return Ok(None);
+        }
+
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+
+        Ok(Some(line))
+    }
+
+    /// Reads at most `count` bytes. Returns less bytes only if end of file is reached.
+    pub fn read_bytes(&mut self, count: usize) -> io::Result<Vec<u8>> {
+        let mut bytes: Vec<u8> = Vec::new();
+
+        while bytes.len() < count {
+            if self.fill_buffer()? == 0 {
+                break;
+            }
+
+            let take = (count - bytes.len()).min(self.buffered());
+            bytes
+                .extend_from_slice(&self.buffer[self.buffer_position..self.buffer_position + take]);
+            self.buffer_position += take;
+        }
+
+        Ok(bytes)
+    }
+
+    pub fn read_to_end(&mut self) -> io::Result<Vec<u8>> {
+        let mut bytes: Vec<u8> = self.buffer[self.buffer_position..].to_vec();
+        self.buffer.clear();
+        self.buffer_position = 0;
+        self.handle.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    pub fn write_all(&mut self, data: &[u8]) -> io::Result<()> {
+        self.discard_buffer()?;
+        self.handle.write_all(data)?;
+        self.handle.flush()
+    }
+
+    pub fn seek(&mut self, position: SeekFrom) -> io::Result<u64> {
+        self.discard_buffer()?;
+        self.handle.seek(position)
+    }
+
+    pub fn tell(&mut self) -> io::Result<u64> {
+        let position = self.handle.stream_position()?;
+        Ok(position - self.buffered() as u64)
+    }
+
+    pub fn is_eof(&mut self) -> io::Result<bool> {
+        Ok(self.fill_buffer()? == 0)
+    }
+}
@@ -1,4 +1,5 @@
 use crate::types::{Token, TokenType};
+use std::str::Chars;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct LexerError {
@@ -7,15 +8,16 @@ pub struct LexerError {
     pub description: String,
 }
 
-const DIGITS_FIRST: &str = "-0123456789";
-const DIGITS: &str = "0123456789";
+const DIGITS_FIRST: &str = "+-0123456789";
+const DIGITS: &str = "0123456789_abcdefABCDEFxXoO.";
 const IDENTIFIER_CHARS_FIRST: &str =
     "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_+-*/%$^!&~`/?:<>";
 const IDENTIFIER_CHARS: &str =
     "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_+-*/%$^!&~`/?:<>0123456789";
 const WHITESPACE_CHARS: &str = " \t\n";
-// const USED_CHARS: &str = "(),{}#";
-// const RESERVED_CHARS: &str = "@;[]\\";
+pub const UNTERMINATED_MULTILINE_STRING: &str = "Unterminated multi-line string literal";
+// const USED_CHARS: &str = "(),{}[]=#";
+// const RESERVED_CHARS: &str = "@;\\";
 
 pub fn is_number_char(chr: char, is_first: bool) -> bool {
     if is_first {
@@ -25,6 +27,22 @@ pub fn is_number_char(chr: char, is_first: bool) -> bool {
     DIGITS.contains(chr)
 }
 
+/// Checks if number token is only a sign, that should be followed by digit to start number.
+/// Otherwise sign starts identifier, like `+`, `-` or `+x`.
+pub fn is_number_sign(content: &str) -> bool {
+    content == "+" || content == "-"
+}
+
+/// Checks if `chr` is a sign of exponent in float literal, like `-` in `1.5e-3`.
+pub fn is_exponent_sign(number: &str, chr: char) -> bool {
+    let unsigned = number.trim_start_matches(['+', '-']);
+
+    (chr == '+' || chr == '-')
+        && (number.ends_with('e') || number.ends_with('E'))
+        && !unsigned.starts_with("0x")
+        && !unsigned.starts_with("0X")
+}
+
 pub fn is_identifier_char(chr: char, is_first: bool) -> bool {
     if is_first {
         return IDENTIFIER_CHARS_FIRST.contains(chr);
@@ -41,6 +59,102 @@ pub fn is_string_end(chr: char) -> bool {
     chr == '"'
 }
 
+pub fn is_raw_string_prefix(content: &str) -> bool {
+    content == "r"
+}
+
+pub fn is_format_string_prefix(content: &str) -> bool {
+    content == "f"
+}
+
+pub fn is_escape_start(chr: char) -> bool {
+    chr == '\\'
+}
+
+/// Decodes escape sequence without leading backslash.
+/// Returns `Ok(None)` if sequence isn't complete yet.
+pub fn parse_escape(sequence: &str) -> Result<Option<char>, String> {
+    let mut chars = sequence.chars();
+    let kind = match chars.next() {
+        Some(kind) => kind,
+        None => return Ok(None),
+    };
+
+    match kind {
+        'n' => Ok(Some('\n')),
+        't' => Ok(Some('\t')),
+        'r' => Ok(Some('\r')),
+        '\\' => Ok(Some('\\')),
+        '"' => Ok(Some('"')),
+        '0' => Ok(Some('\0')),
+        'x' => {
+            let digits = chars.as_str();
+
+            if !digits.chars().all(|chr| chr.is_ascii_hexdigit()) {
+                return Err(format!(
+                    "Invalid escape sequence '\\{}', expected 2 hex digits",
+                    sequence
+                ));
+            }
+
+            if digits.len() < 2 {
+                return Ok(None);
+            }
+
+            Ok(Some(char::from(unsafe {
+                u8::from_str_radix(digits, 16).unwrap_unchecked()
+            })))
+        }
+        'u' => {
+            let rest = chars.as_str();
+
+            if rest.is_empty() {
+                return Ok(None);
+            }
+
+            if !rest.starts_with('{') {
+                return Err(format!(
+                    "Invalid escape sequence '\\{}', expected '{{' after '\\u'",
+                    sequence
+                ));
+            }
+
+            let digits = rest[1..].strip_suffix('}');
+            let is_closed = digits.is_some();
+            let digits = digits.unwrap_or(&rest[1..]);
+
+            if !digits.chars().all(|chr| chr.is_ascii_hexdigit()) || digits.len() > 6 {
+                return Err(format!(
+                    "Invalid escape sequence '\\{}', expected 1 to 6 hex digits",
+                    sequence
+                ));
+            }
+
+            if !is_closed {
+                return Ok(None);
+            }
+
+            if digits.is_empty() {
+                return Err(format!(
+                    "Invalid escape sequence '\\{}', expected 1 to 6 hex digits",
+                    sequence
+                ));
+            }
+
+            let code = unsafe { u32::from_str_radix(digits, 16).unwrap_unchecked() };
+
+            match char::from_u32(code) {
+                Some(chr) => Ok(Some(chr)),
+                None => Err(format!(
+                    "Invalid escape sequence '\\{}', {:X} isn't a Unicode scalar value",
+                    sequence, code
+                )),
+            }
+        }
+        _ => Err(format!("Unknown escape sequence '\\{}'", sequence)),
+    }
+}
+
 pub fn is_lparen(chr: char) -> bool {
     chr == '('
 }
@@ -57,6 +171,18 @@ pub fn is_rbrace(chr: char) -> bool {
     chr == '}'
 }
 
+pub fn is_lbracket(chr: char) -> bool {
+    chr == '['
+}
+
+pub fn is_rbracket(chr: char) -> bool {
+    chr == ']'
+}
+
+pub fn is_equals(chr: char) -> bool {
+    chr == '='
+}
+
 pub fn is_comma(chr: char) -> bool {
     chr == ','
 }
@@ -98,6 +224,18 @@ pub fn get_token_type(chr: char) -> TokenType {
         return TokenType::Rbrace;
     }
 
+    if is_lbracket(chr) {
+        return TokenType::Lbracket;
+    }
+
+    if is_rbracket(chr) {
+        return TokenType::Rbracket;
+    }
+
+    if is_equals(chr) {
+        return TokenType::Equals;
+    }
+
     if is_comma(chr) {
         return TokenType::Comma;
     }
@@ -132,6 +270,9 @@ pub fn is_still_type(chr: char, token_type: &TokenType) -> bool {
         | TokenType::Rparen
         | TokenType::Lbrace
         | TokenType::Rbrace
+        | TokenType::Lbracket
+        | TokenType::Rbracket
+        | TokenType::Equals
         | TokenType::Comma => false,
         _ => true,
     }
@@ -142,6 +283,8 @@ pub fn is_not_continuer(token: Token) -> bool {
         && token.token_type != TokenType::Lparen
         && token.token_type != TokenType::Rbrace
         && token.token_type != TokenType::Rparen
+        && token.token_type != TokenType::Rbracket
+        && token.token_type != TokenType::Equals
         && token.token_type != TokenType::Comma
 }
 
@@ -152,6 +295,8 @@ pub fn is_not_after_continuer(token: Token) -> bool {
         && token.token_type != TokenType::Number
         && token.token_type != TokenType::String
         && token.token_type != TokenType::Lbrace
+        && token.token_type != TokenType::Lbracket
+        && token.token_type != TokenType::Rbracket
 }
 
 // FIXME: Almost duplicate function
@@ -179,6 +324,7 @@ pub fn are_line_tokens_correct(tokens: &Vec<Token>) -> Result<(), LexerError> {
         match token.token_type {
             TokenType::Lparen => brackets.push(token),
             TokenType::Lbrace => brackets.push(token),
+            TokenType::Lbracket => brackets.push(token),
             TokenType::Rparen => {
                 let open_token_result = brackets.pop();
 
@@ -227,6 +373,30 @@ pub fn are_line_tokens_correct(tokens: &Vec<Token>) -> Result<(), LexerError> {
                     });
                 }
             }
+            TokenType::Rbracket => {
+                let open_token_result = brackets.pop();
+
+                if open_token_result.is_none() {
+                    return Err(LexerError {
+                        line: token.line,
+                        column: token.column,
+                        description: "Unexpected closing bracket".to_string(),
+                    });
+                }
+
+                let open_token = unsafe { open_token_result.unwrap_unchecked() };
+
+                if open_token.token_type != TokenType::Lbracket {
+                    return Err(LexerError {
+                        line: token.line,
+                        column: token.column,
+                        description: format!(
+                            "Unclosed bracket in line {} column {}",
+                            open_token.line, open_token.column
+                        ),
+                    });
+                }
+            }
             _ => continue,
         }
     }
@@ -317,6 +487,7 @@ pub fn are_line_tokens_correct(tokens: &Vec<Token>) -> Result<(), LexerError> {
                 if tokens[i].token_type != TokenType::Comma
                     && tokens[i].token_type != TokenType::Rparen
                     && tokens[i].token_type != TokenType::Rbrace
+                    && tokens[i].token_type != TokenType::Rbracket
                 {
                     return Err(LexerError {
                         line: token.line,
@@ -346,6 +517,7 @@ pub fn are_line_tokens_correct(tokens: &Vec<Token>) -> Result<(), LexerError> {
                 if tokens[i].token_type != TokenType::Comma
                     && tokens[i].token_type != TokenType::Rparen
                     && tokens[i].token_type != TokenType::Rbrace
+                    && tokens[i].token_type != TokenType::Rbracket
                 {
                     return Err(LexerError {
                         line: token.line,
@@ -354,6 +526,51 @@ pub fn are_line_tokens_correct(tokens: &Vec<Token>) -> Result<(), LexerError> {
                     });
                 }
             }
+            TokenType::Lbracket => {
+                if i >= tokens_length {
+                    break;
+                }
+
+                if tokens[i].token_type != TokenType::Comma
+                    && tokens[i].token_type != TokenType::Equals
+                    && is_not_after_continuer(tokens[i].clone())
+                {
+                    return Err(LexerError {
+                        line: token.line,
+                        column: token.column,
+                        description: "Unexpected type after left bracket ('[')".to_string(),
+                    });
+                }
+            }
+            TokenType::Equals => {
+                check_paren!(i, tokens_length);
+
+                if tokens[i].token_type == TokenType::Rparen
+                    || tokens[i].token_type == TokenType::Rbrace
+                    || is_not_after_continuer(tokens[i].clone())
+                {
+                    return Err(LexerError {
+                        line: token.line,
+                        column: token.column,
+                        description: "Unexpected type after equals sign ('=')".to_string(),
+                    });
+                }
+            }
+            TokenType::Rbracket => {
+                check_paren!(i, tokens_length);
+
+                if tokens[i].token_type != TokenType::Comma
+                    && tokens[i].token_type != TokenType::Rparen
+                    && tokens[i].token_type != TokenType::Rbrace
+                    && tokens[i].token_type != TokenType::Rbracket
+                {
+                    return Err(LexerError {
+                        line: token.line,
+                        column: token.column,
+                        description: "Unexpected type after right bracket (']')".to_string(),
+                    });
+                }
+            }
             _ => {
                 return Err(LexerError {
                     line: token.line,
@@ -370,11 +587,13 @@ pub fn are_line_tokens_correct(tokens: &Vec<Token>) -> Result<(), LexerError> {
 pub fn are_tokens_correct(tokens: &Vec<Token>) -> Result<(), LexerError> {
     // START:(Ident)
     // Ident:(Lparen...Ident=AIdent,Number=ANumber,String=AString...Rparen|Lbrace...Ident=AIdent,Number=ANumber,String=AString...Rbrace)
-    // AIdent,ANumber,AString:(Rparen|Comma|Lparen|Lbrace|Rbrace)
-    // Comma:(Rparen|Rbrace|AIdent|ANumber|AString|Lbrace|END)
+    // AIdent,ANumber,AString:(Rparen|Comma|Lparen|Lbrace|Rbrace|Rbracket|Equals)
+    // Comma:(Rparen|Rbrace|Rbracket|AIdent|ANumber|AString|Lbrace|Lbracket|END)
     // Number:SHOULDN'T MET
-    // Lparen,Lbrace:(AIdent|Comma|ANumber|Rparen|Rbrace|AString|Lbrace)
-    // Rparen,Rbrace:(Comma|Rparen|Rbrace|END)
+    // Lparen,Lbrace,Lbracket:(AIdent|Comma|ANumber|Rparen|Rbrace|Rbracket|AString|Lbrace|Lbracket)
+    // Lbracket:(Equals)
+    // Equals:(AIdent|ANumber|AString|Lbrace|Lbracket|Rbracket)
+    // Rparen,Rbrace,Rbracket:(Comma|Rparen|Rbrace|Rbracket|END)
     // String:SHOULDN'T MET
     // Unknown:SHOULDN'T MET
 
@@ -402,6 +621,7 @@ pub fn are_tokens_correct(tokens: &Vec<Token>) -> Result<(), LexerError> {
         match token.token_type {
             TokenType::Lparen => brackets.push(token),
             TokenType::Lbrace => brackets.push(token),
+            TokenType::Lbracket => brackets.push(token),
             TokenType::Rparen => {
                 let open_token_result = brackets.pop();
 
@@ -450,6 +670,30 @@ pub fn are_tokens_correct(tokens: &Vec<Token>) -> Result<(), LexerError> {
                     });
                 }
             }
+            TokenType::Rbracket => {
+                let open_token_result = brackets.pop();
+
+                if open_token_result.is_none() {
+                    return Err(LexerError {
+                        line: token.line,
+                        column: token.column,
+                        description: "Unexpected closing bracket".to_string(),
+                    });
+                }
+
+                let open_token = unsafe { open_token_result.unwrap_unchecked() };
+
+                if open_token.token_type != TokenType::Lbracket {
+                    return Err(LexerError {
+                        line: token.line,
+                        column: token.column,
+                        description: format!(
+                            "Unclosed bracket in line {} column {}",
+                            open_token.line, open_token.column
+                        ),
+                    });
+                }
+            }
             _ => continue,
         }
     }
@@ -568,6 +812,7 @@ pub fn are_tokens_correct(tokens: &Vec<Token>) -> Result<(), LexerError> {
                 if tokens[i].token_type != TokenType::Comma
                     && tokens[i].token_type != TokenType::Rparen
                     && tokens[i].token_type != TokenType::Rbrace
+                    && tokens[i].token_type != TokenType::Rbracket
                 {
                     return Err(LexerError {
                         line: token.line,
@@ -605,6 +850,7 @@ pub fn are_tokens_correct(tokens: &Vec<Token>) -> Result<(), LexerError> {
                 if tokens[i].token_type != TokenType::Comma
                     && tokens[i].token_type != TokenType::Rparen
                     && tokens[i].token_type != TokenType::Rbrace
+                    && tokens[i].token_type != TokenType::Rbracket
                 {
                     return Err(LexerError {
                         line: token.line,
@@ -613,6 +859,59 @@ pub fn are_tokens_correct(tokens: &Vec<Token>) -> Result<(), LexerError> {
                     });
                 }
             }
+            TokenType::Lbracket => {
+                scope_level += 1;
+
+                if i >= tokens_length {
+                    return Err(LexerError {
+                        line: token.line,
+                        column: token.column,
+                        description: "Unterminated left bracket (']')".to_string(),
+                    });
+                }
+
+                if tokens[i].token_type != TokenType::Comma
+                    && tokens[i].token_type != TokenType::Equals
+                    && is_not_after_continuer(tokens[i].clone())
+                {
+                    return Err(LexerError {
+                        line: token.line,
+                        column: token.column,
+                        description: "Unexpected type after left bracket ('[')".to_string(),
+                    });
+                }
+            }
+            TokenType::Equals => {
+                check_paren!(i, tokens_length, scope_level, token);
+
+                if tokens[i].token_type == TokenType::Rparen
+                    || tokens[i].token_type == TokenType::Rbrace
+                    || is_not_after_continuer(tokens[i].clone())
+                {
+                    return Err(LexerError {
+                        line: token.line,
+                        column: token.column,
+                        description: "Unexpected type after equals sign ('=')".to_string(),
+                    });
+                }
+            }
+            TokenType::Rbracket => {
+                scope_level -= 1;
+
+                check_paren!(i, tokens_length, scope_level, token);
+
+                if tokens[i].token_type != TokenType::Comma
+                    && tokens[i].token_type != TokenType::Rparen
+                    && tokens[i].token_type != TokenType::Rbrace
+                    && tokens[i].token_type != TokenType::Rbracket
+                {
+                    return Err(LexerError {
+                        line: token.line,
+                        column: token.column,
+                        description: "Unexpected type after right bracket (']')".to_string(),
+                    });
+                }
+            }
             _ => {
                 return Err(LexerError {
                     line: token.line,
@@ -626,6 +925,398 @@ pub fn are_tokens_correct(tokens: &Vec<Token>) -> Result<(), LexerError> {
     Ok(())
 }
 
+struct StringLine {
+    text: String,
+    line: u32,
+    column: u32,
+}
+
+fn is_indent_char(chr: char) -> bool {
+    chr == ' ' || chr == '\t'
+}
+
+/// Strips indentation, common for all non-blank lines, and whitespace before closing quotes.
+fn strip_common_indent(lines: &mut [StringLine]) {
+    let last = lines.len() - 1;
+
+    if lines[last].text.chars().all(is_indent_char) {
+        lines[last].column += lines[last].text.chars().count() as u32;
+        lines[last].text.clear();
+    }
+
+    let indent = lines
+        .iter()
+        .filter(|string_line| !string_line.text.chars().all(is_indent_char))
+        .map(|string_line| {
+            string_line
+                .text
+                .chars()
+                .take_while(|chr| is_indent_char(*chr))
+                .count()
+        })
+        .min()
+        .unwrap_or(0);
+
+    for string_line in lines.iter_mut() {
+        let stripped = indent.min(string_line.text.chars().count());
+        string_line.text = string_line.text.chars().skip(stripped).collect();
+        string_line.column += stripped as u32;
+    }
+}
+
+/// Decodes escapes of `string_line` to `result` and adds position of each decoded char to `positions`.
+fn decode_escapes(
+    string_line: &StringLine,
+    result: &mut String,
+    positions: &mut Vec<(u32, u32)>,
+) -> Result<(), LexerError> {
+    let mut escape: Option<String> = None;
+    let mut escape_column: u32 = 0;
+
+    for (column, chr) in (string_line.column..).zip(string_line.text.chars()) {
+        if escape.is_some() {
+            let sequence = unsafe { escape.as_mut().unwrap_unchecked() };
+            sequence.push(chr);
+
+            match parse_escape(sequence) {
+                Ok(Some(decoded)) => {
+                    result.push(decoded);
+                    positions.push((string_line.line, escape_column));
+                    escape = None;
+                }
+                Ok(None) => {}
+                Err(description) => {
+                    return Err(LexerError {
+                        line: string_line.line,
+                        column: escape_column,
+                        description,
+                    });
+                }
+            }
+        } else if is_escape_start(chr) {
+            escape = Some(String::new());
+            escape_column = column;
+        } else {
+            result.push(chr);
+            positions.push((string_line.line, column));
+        }
+    }
+
+    if escape.is_some() {
+        return Err(LexerError {
+            line: string_line.line,
+            column: escape_column,
+            description: format!("Unterminated escape sequence '\\{}'", unsafe {
+                escape.unwrap_unchecked()
+            }),
+        });
+    }
+
+    Ok(())
+}
+
+/// Reads string literal after its opening quote.
+///
+/// Literal, that starts with `"""`, ends with `"""` and may contain newlines.
+/// If opening `"""` is followed by a newline, that newline is skipped, indentation,
+/// common for all lines, is stripped and last line is removed if it's blank.
+/// Raw strings don't process escape sequences.
+/// Line and column in code of each char of result are added to `positions`.
+fn read_string(
+    chars: &mut Chars,
+    line: &mut u32,
+    column: &mut u32,
+    token: &Token,
+    raw: bool,
+    positions: &mut Vec<(u32, u32)>,
+) -> Result<String, LexerError> {
+    let mut lookahead = chars.clone();
+    let is_triple = lookahead.next() == Some('"') && lookahead.next() == Some('"');
+    let mut is_block = false;
+
+    if is_triple {
+        chars.nth(1);
+        *column += 2;
+
+        if chars.clone().next() == Some('\n') {
+            chars.next();
+            *line += 1;
+            *column = 0;
+            is_block = true;
+        }
+    }
+
+    let mut lines: Vec<StringLine> = vec![StringLine {
+        text: String::new(),
+        line: *line,
+        column: *column + 1,
+    }];
+    let mut is_escaped = false;
+
+    loop {
+        let chr_result = chars.next();
+
+        if chr_result.is_none() || (!is_triple && chr_result == Some('\n')) {
+            return Err(LexerError {
+                line: token.line,
+                column: token.column,
+                description: if is_triple {
+                    UNTERMINATED_MULTILINE_STRING.to_string()
+                } else {
+                    "Unterminated string literal".to_string()
+                },
+            });
+        }
+
+        let chr = unsafe { chr_result.unwrap_unchecked() };
+
+        if chr == '\n' {
+            *line += 1;
+            *column = 0;
+            let last = unsafe { lines.last_mut().unwrap_unchecked() };
+
+            if last.text.ends_with('\r') {
+                last.text.pop();
+            }
+
+            lines.push(StringLine {
+                text: String::new(),
+                line: *line,
+                column: 1,
+            });
+            is_escaped = false;
+            continue;
+        }
+
+        *column += 1;
+
+        if is_string_end(chr) && !is_escaped {
+            if !is_triple {
+                break;
+            }
+
+            let mut lookahead = chars.clone();
+
+            if lookahead.next() == Some('"') && lookahead.next() == Some('"') {
+                chars.nth(1);
+                *column += 2;
+                break;
+            }
+        }
+
+        is_escaped = !raw && !is_escaped && is_escape_start(chr);
+        unsafe { lines.last_mut().unwrap_unchecked() }
+            .text
+            .push(chr);
+    }
+
+    if is_block {
+        strip_common_indent(&mut lines);
+    }
+
+    let mut result = String::new();
+
+    for (i, string_line) in lines.iter().enumerate() {
+        if i > 0 {
+            let previous = &lines[i - 1];
+            result.push('\n');
+            positions.push((
+                previous.line,
+                previous.column + previous.text.chars().count() as u32,
+            ));
+        }
+
+        if raw {
+            result.push_str(&string_line.text);
+            positions.extend(
+                (string_line.column..)
+                    .take(string_line.text.chars().count())
+                    .map(|column| (string_line.line, column)),
+            );
+        } else {
+            decode_escapes(string_line, &mut result, positions)?;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Splits content of `f"..."` literal to `format` template and expressions of placeholders
+/// with index of their first char in content.
+/// Expression ends at `:` or `}`, that isn't inside of brackets or string.
+fn split_format_string(
+    token: &Token,
+    content: &str,
+    positions: &[(u32, u32)],
+) -> Result<(String, Vec<(String, usize)>), LexerError> {
+    let error = |index: usize, description: &str| {
+        let (line, column) = positions
+            .get(index)
+            .copied()
+            .unwrap_or((token.line, token.column));
+        LexerError {
+            line,
+            column,
+            description: description.to_string(),
+        }
+    };
+    let mut template = String::new();
+    let mut expressions: Vec<(String, usize)> = Vec::new();
+    let mut chars = content.chars().enumerate().peekable();
+
+    while let Some((index, chr)) = chars.next() {
+        if chr == '}' {
+            if chars.next_if(|(_, next)| *next == '}').is_none() {
+                return Err(error(index, "Unmatched `}` in format string literal"));
+            }
+
+            template.push_str("}}");
+            continue;
+        }
+
+        if chr != '{' {
+            template.push(chr);
+            continue;
+        }
+
+        if chars.next_if(|(_, next)| *next == '{').is_some() {
+            template.push_str("{{");
+            continue;
+        }
+
+        let mut expression = String::new();
+        let mut depth = 0;
+        let mut in_string = false;
+        let mut is_escaped = false;
+        let mut end = None;
+
+        for (_, chr) in chars.by_ref() {
+            if in_string {
+                in_string = is_escaped || !is_string_end(chr);
+                is_escaped = !is_escaped && is_escape_start(chr);
+            } else if is_string_start(chr) {
+                in_string = true;
+            } else if is_lparen(chr) || is_lbrace(chr) || is_lbracket(chr) {
+                depth += 1;
+            } else if depth > 0 && (is_rparen(chr) || is_rbrace(chr) || is_rbracket(chr)) {
+                depth -= 1;
+            } else if depth == 0 && (chr == ':' || chr == '}') {
+                end = Some(chr);
+                break;
+            }
+
+            expression.push(chr);
+        }
+
+        if end.is_none() {
+            return Err(error(index, "Unmatched `{` in format string literal"));
+        }
+
+        if expression.trim().is_empty() {
+            return Err(error(index, "Empty expression in format string literal"));
+        }
+
+        template.push('{');
+
+        if end == Some(':') {
+            template.push(':');
+
+            for (_, chr) in chars.by_ref() {
+                if chr == '}' {
+                    end = None;
+                    break;
+                }
+
+                template.push(chr);
+            }
+
+            if end.is_some() {
+                return Err(error(index, "Unmatched `{` in format string literal"));
+            }
+        }
+
+        template.push('}');
+        expressions.push((expression, index + 1));
+    }
+
+    Ok((template, expressions))
+}
+
+/// Converts line and column in `expression`, that starts at `start` char of literal content,
+/// to line and column in code.
+fn get_expression_position(
+    positions: &[(u32, u32)],
+    expression: &str,
+    start: usize,
+    line: u32,
+    column: u32,
+) -> (u32, u32) {
+    let offset: usize = expression
+        .split('\n')
+        .take(line.saturating_sub(1) as usize)
+        .map(|expression_line| expression_line.chars().count() + 1)
+        .sum::<usize>()
+        + column.saturating_sub(1) as usize;
+    let length = expression.chars().count();
+
+    if offset < length {
+        return positions[start + offset];
+    }
+
+    // Error at the end of expression is reported after its last char.
+    let (last_line, last_column) = positions[start + length - 1];
+    (last_line, last_column + 1)
+}
+
+/// Converts `f"..."` literal to tokens of `format` call with expressions of placeholders as arguments.
+/// Tokens of expressions get their positions in code from `positions` of literal content.
+fn desugar_format_string(
+    token: &Token,
+    content: &str,
+    positions: &[(u32, u32)],
+) -> Result<Vec<Token>, LexerError> {
+    let (template, expressions) = split_format_string(token, content, positions)?;
+    let new_token = |token_type: TokenType, content: &str| {
+        Token::new(token_type, token.line, token.column, content)
+    };
+    let mut tokens = vec![
+        new_token(TokenType::Identifier, "format"),
+        new_token(TokenType::Lparen, "("),
+        new_token(TokenType::String, &template),
+    ];
+
+    for (expression, start) in expressions {
+        let expression_tokens = __to_tokens(&format!("{}\n", expression));
+
+        if expression_tokens.is_err() {
+            let error = unsafe { expression_tokens.unwrap_err_unchecked() };
+            let (line, column) =
+                get_expression_position(positions, &expression, start, error.line, error.column);
+            return Err(LexerError {
+                line,
+                column,
+                description: error.description,
+            });
+        }
+
+        tokens.push(new_token(TokenType::Comma, ","));
+
+        for mut expression_token in unsafe { expression_tokens.unwrap_unchecked() } {
+            (expression_token.line, expression_token.column) = get_expression_position(
+                positions,
+                &expression,
+                start,
+                expression_token.line,
+                expression_token.column,
+            );
+            tokens.push(expression_token);
+        }
+    }
+
+    tokens.push(new_token(TokenType::Rparen, ")"));
+    Ok(tokens)
+}
+
 fn __to_tokens(code: &str) -> Result<Vec<Token>, LexerError> {
     let mut chars = code.chars();
     let mut line: u32 = 1;
@@ -710,12 +1401,11 @@ fn __to_tokens(code: &str) -> Result<Vec<Token>, LexerError> {
             continue;
         }
 
-        if next_token.token_type == TokenType::String && chr == '\n' {
-            return Err(LexerError {
-                line: next_token.line,
-                column: next_token.column,
-                description: "Unterminated string literal".to_string(),
-            });
+        if next_token.token_type == TokenType::Number
+            && is_number_sign(&next_token.content)
+            && !chr.is_ascii_digit()
+        {
+            next_token.token_type = TokenType::Identifier;
         }
 
         if next_token.token_type == TokenType::Unknown {
@@ -732,12 +1422,73 @@ fn __to_tokens(code: &str) -> Result<Vec<Token>, LexerError> {
             next_token.line = line;
             next_token.column = column;
             match get_type_start(chr) {
+                Some(TokenType::String) => {
+                    next_token.content = read_string(
+                        &mut chars,
+                        &mut line,
+                        &mut column,
+                        &next_token,
+                        false,
+                        &mut Vec::new(),
+                    )?;
+                    next_token.token_type = TokenType::String;
+                    tokens.push(next_token);
+                    next_token = Token {
+                        token_type: TokenType::Unknown,
+                        content: String::new(),
+                        line: 0,
+                        column: 0,
+                    };
+                    continue;
+                }
                 Some(token_type) => {
                     next_token.token_type = token_type;
                     continue;
                 }
                 None => next_token.token_type = get_token_type(chr),
             };
+        } else if next_token.token_type == TokenType::Identifier
+            && is_raw_string_prefix(&next_token.content)
+            && is_string_start(chr)
+        {
+            next_token.content = read_string(
+                &mut chars,
+                &mut line,
+                &mut column,
+                &next_token,
+                true,
+                &mut Vec::new(),
+            )?;
+            next_token.token_type = TokenType::String;
+            tokens.push(next_token);
+            next_token = Token {
+                token_type: TokenType::Unknown,
+                content: String::new(),
+                line: 0,
+                column: 0,
+            };
+            continue;
+        } else if next_token.token_type == TokenType::Identifier
+            && is_format_string_prefix(&next_token.content)
+            && is_string_start(chr)
+        {
+            let mut positions: Vec<(u32, u32)> = Vec::new();
+            let content = read_string(
+                &mut chars,
+                &mut line,
+                &mut column,
+                &next_token,
+                false,
+                &mut positions,
+            )?;
+            tokens.extend(desugar_format_string(&next_token, &content, &positions)?);
+            next_token = Token {
+                token_type: TokenType::Unknown,
+                content: String::new(),
+                line: 0,
+                column: 0,
+            };
+            continue;
         } else if is_type_end(chr, &next_token.token_type) {
             tokens.push(next_token);
             next_token = Token {
@@ -747,7 +1498,10 @@ fn __to_tokens(code: &str) -> Result<Vec<Token>, LexerError> {
                 column: 0,
             };
             continue;
-        } else if !is_still_type(chr, &next_token.token_type) {
+        } else if !(is_still_type(chr, &next_token.token_type)
+            || next_token.token_type == TokenType::Number
+                && is_exponent_sign(&next_token.content, chr))
+        {
             tokens.push(next_token);
             next_token = Token {
                 token_type: TokenType::Unknown,
@@ -769,14 +1523,6 @@ fn __to_tokens(code: &str) -> Result<Vec<Token>, LexerError> {
         next_token.content.push(chr);
     }
 
-    if next_token.token_type == TokenType::String {
-        return Err(LexerError {
-            line: next_token.line,
-            column: next_token.column,
-            description: "Unterminated string literal".to_string(),
-        });
-    }
-
     Ok(tokens)
 }
 
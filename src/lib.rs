@@ -1,11 +1,23 @@
+#[cfg(feature = "bigint")]
+pub mod bigint;
+pub mod clock;
+#[cfg(feature = "runner")]
+pub mod debugger;
+pub mod engine;
+pub mod filesystem;
 #[cfg(feature = "lexer")]
 pub mod lexer;
 #[cfg(feature = "parser")]
 pub mod parser;
+#[cfg(feature = "runner")]
+pub mod project;
+pub mod random;
 #[cfg(feature = "repl")]
 pub mod repl;
 #[cfg(feature = "runner")]
 pub mod runner;
 #[cfg(feature = "stdlib")]
+pub mod snapshot;
+#[cfg(feature = "stdlib")]
 pub mod stdlib;
 pub mod types;
@@ -1,25 +1,72 @@
+#[cfg(feature = "runner")]
+use easy_prog::debugger::debug_file;
+#[cfg(feature = "runner")]
+use easy_prog::project::run_project_scope;
 #[cfg(feature = "repl")]
 use easy_prog::repl::start_repl;
 #[cfg(feature = "runner")]
-use easy_prog::runner::run_file;
+use easy_prog::runner::run_file_scope;
+#[cfg(feature = "runner")]
+use easy_prog::types::Scope;
 use std::env;
 
+#[cfg(feature = "runner")]
+fn create_scope(script_args: &[String]) -> Scope {
+    let mut scope = Scope::with_stdlib();
+    scope.get_engine_mut().set_script_args(script_args.to_vec());
+    scope
+}
+
+#[cfg(feature = "runner")]
+fn run_file(path: &str, script_args: &[String]) {
+    run_file_scope(path, &mut create_scope(script_args));
+}
+
+#[cfg(feature = "runner")]
+fn run_project(dir: &str, script_args: &[String]) {
+    run_project_scope(dir, &mut create_scope(script_args));
+}
+
+#[cfg(feature = "runner")]
+fn debug(path: &str, script_args: &[String]) {
+    debug_file(path, &mut create_scope(script_args));
+}
+
 #[cfg(not(feature = "runner"))]
-fn run_file(_i: &String) {
+fn run_file(_i: &str, _script_args: &[String]) {
     println!("Feature 'runner' required to run specified file");
 }
 
+#[cfg(not(feature = "runner"))]
+fn run_project(_i: &str, _script_args: &[String]) {
+    println!("Feature 'runner' required to run specified project");
+}
+
+#[cfg(not(feature = "runner"))]
+fn debug(_i: &str, _script_args: &[String]) {
+    println!("Feature 'runner' required to debug specified file");
+}
+
 #[cfg(not(feature = "repl"))]
 fn start_repl() {
-    println!("Usage: easy_prog <path_to_file.ep>");
+    println!("Usage: easy_prog <path_to_file.ep> [-- <args>...]");
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect::<Vec<String>>();
+    let all_args: Vec<String> = env::args().collect::<Vec<String>>();
+    // Arguments after `--` are passed to script.
+    let (args, script_args) = match all_args.iter().position(|arg| arg == "--") {
+        Some(index) => (&all_args[..index], &all_args[index + 1..]),
+        None => (&all_args[..], &[] as &[String]),
+    };
 
-    if args.len() > 1 {
+    if args.len() == 3 && args[1] == "run" {
+        run_project(&args[2], script_args);
+    } else if args.len() == 3 && args[1] == "debug" {
+        debug(&args[2], script_args);
+    } else if args.len() > 1 {
         for i in args[1..].iter() {
-            run_file(i);
+            run_file(i, script_args);
         }
     } else {
         start_repl();
@@ -1,7 +1,8 @@
 #[cfg(feature = "lexer")]
 use crate::lexer::to_tokens;
 use crate::types::{
-    AstNode, CallFuncNode, ConstIntNode, ConstStrNode, Int, SequenceNode, Str, VariableNode,
+    AstNode, CallFuncNode, ConstFloatNode, ConstIntNode, ConstStrNode, Float, Int, ListNode,
+    MapNode, NodeType, SequenceNode, Str, VariableNode,
 };
 use crate::types::{Token, TokenType};
 use std::collections::VecDeque;
@@ -79,15 +80,40 @@ lparen = "("
 rparen = ")"
 lbrace = "{"
 rbrace = "}"
+lbracket = "["
+rbracket = "]"
+equals = "="
 str_quote = '"'
-
-string = str_quote { character - str_quote } str_quote
+backslash = "\\"
+hex_digit = digit | "a" | "b" | "c" | "d" | "e" | "f" | "A" | "B" | "C" | "D" | "E" | "F"
+
+escape = backslash ( "n" | "t" | "r" | backslash | str_quote | "0" | "x" hex_digit hex_digit | "u{" hex_digit { hex_digit } "}" )
+triple_quote = str_quote str_quote str_quote
+string = str_quote { character - str_quote - backslash - "\n" | escape } str_quote
+       | triple_quote { character - backslash | escape } triple_quote
+       | "r" str_quote { character - str_quote - "\n" } str_quote
+       | "r" triple_quote { character } triple_quote
+       | "f" string (* lexer converts it to format call *)
 identifier = letter_lowercase | letter_uppercase | identifier_characters { letter_lowercase | letter_uppercase | identifier_characters | digit }
-number = digit | { digit }
+sign = "+" | "-"
+digit_separator = "_"
+binary_digit = "0" | "1"
+octal_digit = binary_digit | "2" | "3" | "4" | "5" | "6" | "7"
+number = [ sign ] ( digit { digit | digit_separator }
+       | "0x" hex_digit { hex_digit | digit_separator }
+       | "0b" binary_digit { binary_digit | digit_separator }
+       | "0o" octal_digit { octal_digit | digit_separator } )
+digits = digit { digit | digit_separator }
+float = [ sign ] digits ( "." digits [ exponent ] | exponent )
+exponent = ( "e" | "E" ) [ sign ] digits
 expression_list = ( expression { comma expression } )
 func_call = identifier lparen expression_list ? rparen
 func_body = lbrace expression_list ? rbrace
-expression = string | identifier | number | func_call | func_body
+list = lbracket expression_list ? rbracket
+map_key = string | identifier | number
+map_entry = map_key equals expression
+map = lbracket ( map_entry { comma map_entry } | equals ) rbracket
+expression = string | identifier | number | float | func_call | func_body | list | map
 program = START expression_list END
  */
 
@@ -299,6 +325,316 @@ pub fn parse_func_body(tokens: &mut Vector<Token>) -> Result<SequenceNode, Parse
     ))
 }
 
+pub fn parse_list(tokens: &mut Vector<Token>) -> Result<ListNode, ParserError> {
+    if tokens.len() < 2 {
+        return Err(ParserError::new(
+            0,
+            0,
+            "Expected list, but found end of file",
+            ParserErrorCode::EmptyTokenList,
+        ));
+    }
+
+    let first_token: Token = unsafe { tokens.get(0).unwrap_unchecked() };
+
+    if first_token.token_type != TokenType::Lbracket {
+        return Err(ParserError::new(
+            first_token.line,
+            first_token.column,
+            "Unexpected token type, expected left bracket ('[')",
+            ParserErrorCode::UnexpectedType,
+        ));
+    }
+
+    tokens.pop_front();
+
+    if unsafe { tokens.get(0).unwrap_unchecked() }.token_type == TokenType::Rbracket {
+        tokens.pop_front();
+        return Ok(ListNode::new(
+            first_token.line,
+            first_token.column,
+            Vec::new(),
+        ));
+    }
+
+    let result = parse_expression_list(tokens, TokenType::Rbracket);
+
+    if result.is_err() {
+        return Err(unsafe { result.unwrap_err_unchecked() });
+    }
+
+    if tokens.is_empty() {
+        return Err(ParserError::new(
+            0,
+            0,
+            "Expected right bracket (']'), but found end of file",
+            ParserErrorCode::ShortTokenList,
+        ));
+    }
+
+    let last_token: Token = unsafe { tokens.pop_front().unwrap_unchecked() };
+
+    if last_token.token_type != TokenType::Rbracket {
+        return Err(ParserError::new(
+            last_token.line,
+            last_token.column,
+            "Unexpected token type, expected right bracket (']')",
+            ParserErrorCode::UnexpectedType,
+        ));
+    }
+
+    Ok(ListNode::new(
+        first_token.line,
+        first_token.column,
+        unsafe { result.unwrap_unchecked() },
+    ))
+}
+
+/// Checks if tokens start with map literal: `[=]` or `[key = ...`.
+fn is_map_start(tokens: &Vector<Token>) -> bool {
+    let is_equals = |index: usize| {
+        tokens
+            .get(index)
+            .is_some_and(|token| token.token_type == TokenType::Equals)
+    };
+
+    let is_key = tokens.get(1).is_some_and(|token| {
+        matches!(
+            token.token_type,
+            TokenType::String | TokenType::Identifier | TokenType::Number
+        )
+    });
+
+    is_equals(1) || (is_key && is_equals(2))
+}
+
+pub fn parse_map(tokens: &mut Vector<Token>) -> Result<MapNode, ParserError> {
+    if tokens.len() < 3 {
+        return Err(ParserError::new(
+            0,
+            0,
+            "Expected map, but found end of file",
+            ParserErrorCode::EmptyTokenList,
+        ));
+    }
+
+    let first_token: Token = unsafe { tokens.get(0).unwrap_unchecked() };
+
+    if first_token.token_type != TokenType::Lbracket {
+        return Err(ParserError::new(
+            first_token.line,
+            first_token.column,
+            "Unexpected token type, expected left bracket ('[')",
+            ParserErrorCode::UnexpectedType,
+        ));
+    }
+
+    tokens.pop_front();
+    let mut items: Vec<(Rc<dyn AstNode>, Rc<dyn AstNode>)> = Vec::new();
+
+    if unsafe { tokens.get(0).unwrap_unchecked() }.token_type == TokenType::Equals {
+        tokens.pop_front();
+    } else {
+        loop {
+            let key_token: Token = unsafe { tokens.get(0).unwrap_unchecked() };
+            let key = parse_expression(tokens);
+
+            if key.is_err() {
+                return Err(unsafe { key.unwrap_err_unchecked() });
+            }
+
+            let key = unsafe { key.unwrap_unchecked() };
+
+            if !matches!(
+                key.get_type(),
+                NodeType::ConstStr | NodeType::ConstInt | NodeType::Identifier
+            ) {
+                return Err(ParserError::new(
+                    key_token.line,
+                    key_token.column,
+                    "Map key should be string, integer or identifier",
+                    ParserErrorCode::UnexpectedType,
+                ));
+            }
+
+            let equals_token = tokens.pop_front();
+
+            if equals_token.is_none() {
+                return Err(ParserError::new(
+                    0,
+                    0,
+                    "Expected equals sign ('='), but found end of file",
+                    ParserErrorCode::ShortTokenList,
+                ));
+            }
+
+            let equals_token = unsafe { equals_token.unwrap_unchecked() };
+
+            if equals_token.token_type != TokenType::Equals {
+                return Err(ParserError::new(
+                    equals_token.line,
+                    equals_token.column,
+                    "Unexpected token type, expected equals sign ('=')",
+                    ParserErrorCode::UnexpectedType,
+                ));
+            }
+
+            let value = parse_expression(tokens);
+
+            if value.is_err() {
+                return Err(unsafe { value.unwrap_err_unchecked() });
+            }
+
+            items.push((key, unsafe { value.unwrap_unchecked() }));
+
+            if tokens.is_empty()
+                || unsafe { tokens.get(0).unwrap_unchecked() }.token_type != TokenType::Comma
+            {
+                break;
+            }
+
+            tokens.pop_front();
+        }
+    }
+
+    if tokens.is_empty() {
+        return Err(ParserError::new(
+            0,
+            0,
+            "Expected right bracket (']'), but found end of file",
+            ParserErrorCode::ShortTokenList,
+        ));
+    }
+
+    let last_token: Token = unsafe { tokens.pop_front().unwrap_unchecked() };
+
+    if last_token.token_type != TokenType::Rbracket {
+        return Err(ParserError::new(
+            last_token.line,
+            last_token.column,
+            "Unexpected token type, expected right bracket (']')",
+            ParserErrorCode::UnexpectedType,
+        ));
+    }
+
+    Ok(MapNode::new(first_token.line, first_token.column, items))
+}
+
+fn get_radix_name(radix: u32) -> &'static str {
+    match radix {
+        2 => "binary",
+        8 => "octal",
+        16 => "hexadecimal",
+        _ => "decimal",
+    }
+}
+
+/// Parses integer literal with optional sign, `0x`, `0b` or `0o` prefix and `_` separators.
+pub fn parse_int_literal(literal: &str) -> Result<i64, String> {
+    let (sign, unsigned) = match literal.chars().next() {
+        Some('-') => ("-", &literal[1..]),
+        Some('+') => ("", &literal[1..]),
+        _ => ("", literal),
+    };
+    let (radix, digits) = match unsigned.get(..2) {
+        Some("0x") | Some("0X") => (16, &unsigned[2..]),
+        Some("0b") | Some("0B") => (2, &unsigned[2..]),
+        Some("0o") | Some("0O") => (8, &unsigned[2..]),
+        _ => (10, unsigned),
+    };
+
+    if digits.is_empty() {
+        return Err(format!("Expected digits in integer literal '{}'", literal));
+    }
+
+    if digits.starts_with('_') || digits.ends_with('_') || digits.contains("__") {
+        return Err(format!(
+            "Digit separator '_' should be between digits in integer literal '{}'",
+            literal
+        ));
+    }
+
+    let invalid_digit = digits
+        .chars()
+        .find(|chr| *chr != '_' && !chr.is_digit(radix));
+
+    if invalid_digit.is_some() {
+        return Err(format!(
+            "Invalid digit '{}' in {} integer literal '{}'",
+            unsafe { invalid_digit.unwrap_unchecked() },
+            get_radix_name(radix),
+            literal
+        ));
+    }
+
+    let result = i64::from_str_radix(&(sign.to_string() + &digits.replace('_', "")), radix);
+
+    if result.is_err() {
+        return Err("Integer value overflowed".to_string());
+    }
+
+    Ok(unsafe { result.unwrap_unchecked() })
+}
+
+/// Checks if number literal is a float: it has `.` or exponent and no radix prefix.
+pub fn is_float_literal(literal: &str) -> bool {
+    let unsigned = literal.strip_prefix(['+', '-']).unwrap_or(literal);
+
+    if matches!(
+        unsigned.get(..2),
+        Some("0x") | Some("0X") | Some("0b") | Some("0B") | Some("0o") | Some("0O")
+    ) {
+        return false;
+    }
+
+    unsigned.contains(['.', 'e', 'E'])
+}
+
+fn is_digit_sequence(digits: &str) -> bool {
+    !digits.is_empty()
+        && !digits.starts_with('_')
+        && !digits.ends_with('_')
+        && !digits.contains("__")
+        && digits.chars().all(|chr| chr == '_' || chr.is_ascii_digit())
+}
+
+/// Parses float literal in form `[sign] digits [. digits] [(e|E) [sign] digits]`,
+/// where digits may be separated by `_`.
+pub fn parse_float_literal(literal: &str) -> Result<f64, String> {
+    let unsigned = literal.strip_prefix(['+', '-']).unwrap_or(literal);
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(index) => (&unsigned[..index], Some(&unsigned[index + 1..])),
+        None => (unsigned, None),
+    };
+    let (integer, fraction) = match mantissa.find('.') {
+        Some(index) => (&mantissa[..index], Some(&mantissa[index + 1..])),
+        None => (mantissa, None),
+    };
+
+    let is_fraction_valid = fraction.is_none_or(is_digit_sequence);
+    let is_exponent_valid = exponent.is_none_or(|exponent| {
+        is_digit_sequence(exponent.strip_prefix(['+', '-']).unwrap_or(exponent))
+    });
+
+    if !is_digit_sequence(integer) || !is_fraction_valid || !is_exponent_valid {
+        return Err(format!("Invalid float literal '{}'", literal));
+    }
+
+    let result = literal.replace('_', "").parse::<f64>();
+
+    if result.is_err() {
+        return Err(format!("Invalid float literal '{}'", literal));
+    }
+
+    let number = unsafe { result.unwrap_unchecked() };
+
+    if number.is_infinite() {
+        return Err("Float value overflowed".to_string());
+    }
+
+    Ok(number)
+}
+
 pub fn parse_expression(tokens: &mut Vector<Token>) -> Result<Rc<dyn AstNode>, ParserError> {
     if tokens.is_empty() {
         return Err(ParserError::new(
@@ -321,15 +657,36 @@ pub fn parse_expression(tokens: &mut Vector<Token>) -> Result<Rc<dyn AstNode>, P
         return Ok(ast_box);
     }
 
+    if token.token_type == TokenType::Number && is_float_literal(&token.content) {
+        tokens.pop_front();
+        let result = parse_float_literal(&token.content);
+
+        if result.is_err() {
+            return Err(ParserError::new(
+                token.line,
+                token.column,
+                &unsafe { result.unwrap_err_unchecked() },
+                ParserErrorCode::InvalidValue,
+            ));
+        }
+
+        let ast_box: Rc<dyn AstNode> = Rc::new(ConstFloatNode::new(
+            token.line,
+            token.column,
+            Float::new(unsafe { result.unwrap_unchecked() }),
+        ));
+        return Ok(ast_box);
+    }
+
     if token.token_type == TokenType::Number {
         tokens.pop_front();
-        let result = token.content.parse();
+        let result = parse_int_literal(&token.content);
 
         if result.is_err() {
             return Err(ParserError::new(
                 token.line,
                 token.column,
-                "Integer value overflowed",
+                &unsafe { result.unwrap_err_unchecked() },
                 ParserErrorCode::InvalidValue,
             ));
         }
@@ -374,6 +731,28 @@ pub fn parse_expression(tokens: &mut Vector<Token>) -> Result<Rc<dyn AstNode>, P
         return Ok(ast_box);
     }
 
+    if token.token_type == TokenType::Lbracket && is_map_start(tokens) {
+        let node: Result<MapNode, ParserError> = parse_map(tokens);
+
+        if node.is_err() {
+            return Err(unsafe { node.unwrap_err_unchecked() });
+        }
+
+        let ast_box: Rc<dyn AstNode> = Rc::new(unsafe { node.unwrap_unchecked() });
+        return Ok(ast_box);
+    }
+
+    if token.token_type == TokenType::Lbracket {
+        let node: Result<ListNode, ParserError> = parse_list(tokens);
+
+        if node.is_err() {
+            return Err(unsafe { node.unwrap_err_unchecked() });
+        }
+
+        let ast_box: Rc<dyn AstNode> = Rc::new(unsafe { node.unwrap_unchecked() });
+        return Ok(ast_box);
+    }
+
     Err(ParserError::new(
         token.line,
         token.column,
@@ -0,0 +1,431 @@
+#[cfg(feature = "parser")]
+use crate::engine::Permission;
+#[cfg(feature = "parser")]
+use crate::runner::run_file_code_scope;
+#[cfg(feature = "parser")]
+use crate::types::Scope;
+
+use crate::engine::Engine;
+use crate::filesystem::FileSystem;
+use std::path::{Component, Path, PathBuf};
+
+pub static MANIFEST_FILE: &str = "EasyProg.toml";
+pub static DEFAULT_ENTRY: &str = "main.ep";
+pub static VENDOR_DIR: &str = "vendor";
+pub static MODULE_EXTENSION: &str = ".ep";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ProjectError {
+    pub line: u32,
+    pub column: u32,
+    pub description: String,
+}
+
+impl ProjectError {
+    pub fn new(line: u32, column: u32, description: &str) -> ProjectError {
+        ProjectError {
+            line,
+            column,
+            description: description.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Manifest {
+    pub name: String,
+    pub version: String,
+    pub entry: String,
+    pub dependencies: Vec<(String, String)>,
+}
+
+impl Manifest {
+    pub fn new(name: &str, version: &str, entry: &str) -> Manifest {
+        Manifest {
+            name: name.to_string(),
+            version: version.to_string(),
+            entry: entry.to_string(),
+            dependencies: Vec::new(),
+        }
+    }
+}
+
+fn is_manifest_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|chr| chr.is_ascii_alphanumeric() || chr == '_' || chr == '-')
+}
+
+fn parse_manifest_value(value: &str, line: u32, column: u32) -> Result<String, ProjectError> {
+    if !value.starts_with('"') {
+        return Err(ProjectError::new(
+            line,
+            column,
+            "Expected string value in double quotes",
+        ));
+    }
+
+    let end_result = value[1..].find('"');
+
+    if end_result.is_none() {
+        return Err(ProjectError::new(line, column, "Unterminated string value"));
+    }
+
+    let end = unsafe { end_result.unwrap_unchecked() } + 1;
+    let rest = value[end + 1..].trim_start();
+
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return Err(ProjectError::new(
+            line,
+            column + end as u32 + 1,
+            "Unexpected characters after value",
+        ));
+    }
+
+    Ok(value[1..end].to_string())
+}
+
+fn is_project_path(path: &str) -> bool {
+    Path::new(path)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// Parses project manifest.
+///
+/// Manifest consists of `key = "value"` lines, `# comments` and an optional
+/// `[dependencies]` section, where each key is a module name and each value is
+/// a path to the module directory, relative to the manifest.
+pub fn parse_manifest(code: &str) -> Result<Manifest, ProjectError> {
+    let mut name: Option<String> = None;
+    let mut version: Option<String> = None;
+    let mut entry: Option<String> = None;
+    let mut dependencies: Vec<(String, String)> = Vec::new();
+    let mut in_dependencies = false;
+    let mut line: u32 = 0;
+
+    for raw_line in code.lines() {
+        line += 1;
+        let trimmed = raw_line.trim_start();
+        let column = (raw_line.len() - trimmed.len()) as u32 + 1;
+        let text = trimmed.trim_end();
+
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+
+        if text.starts_with('[') {
+            if text == "[dependencies]" {
+                in_dependencies = true;
+                continue;
+            }
+
+            return Err(ProjectError::new(
+                line,
+                column,
+                &format!("Unknown section '{}'", text),
+            ));
+        }
+
+        let separator_result = text.find('=');
+
+        if separator_result.is_none() {
+            return Err(ProjectError::new(
+                line,
+                column,
+                "Expected `key = \"value\"` pair",
+            ));
+        }
+
+        let separator = unsafe { separator_result.unwrap_unchecked() };
+        let key = text[..separator].trim_end();
+
+        if !is_manifest_key(key) {
+            return Err(ProjectError::new(
+                line,
+                column,
+                &format!("Invalid key '{}'", key),
+            ));
+        }
+
+        let value_part = &text[separator + 1..];
+        let value_trimmed = value_part.trim_start();
+        let value_column =
+            column + separator as u32 + 1 + (value_part.len() - value_trimmed.len()) as u32;
+        let value = parse_manifest_value(value_trimmed, line, value_column)?;
+
+        if in_dependencies {
+            if dependencies.iter().any(|dependency| dependency.0 == key) {
+                return Err(ProjectError::new(
+                    line,
+                    column,
+                    &format!("Duplicate dependency '{}'", key),
+                ));
+            }
+
+            dependencies.push((key.to_string(), value));
+            continue;
+        }
+
+        if key == "entry" && !is_project_path(&value) {
+            return Err(ProjectError::new(
+                line,
+                value_column,
+                "Entry should be a relative path inside of project directory",
+            ));
+        }
+
+        let field = match key {
+            "name" => &mut name,
+            "version" => &mut version,
+            "entry" => &mut entry,
+            _ => {
+                return Err(ProjectError::new(
+                    line,
+                    column,
+                    &format!("Unknown key '{}'", key),
+                ));
+            }
+        };
+
+        if field.is_some() {
+            return Err(ProjectError::new(
+                line,
+                column,
+                &format!("Duplicate key '{}'", key),
+            ));
+        }
+
+        *field = Some(value);
+    }
+
+    if name.is_none() {
+        return Err(ProjectError::new(0, 0, "Missing required key 'name'"));
+    }
+
+    if version.is_none() {
+        return Err(ProjectError::new(0, 0, "Missing required key 'version'"));
+    }
+
+    let mut manifest = Manifest::new(
+        &unsafe { name.unwrap_unchecked() },
+        &unsafe { version.unwrap_unchecked() },
+        &entry.unwrap_or(DEFAULT_ENTRY.to_string()),
+    );
+    manifest.dependencies = dependencies;
+    Ok(manifest)
+}
+
+pub fn load_manifest(file_system: &dyn FileSystem, dir: &str) -> Result<Manifest, ProjectError> {
+    let path = Path::new(dir).join(MANIFEST_FILE);
+    let code = file_system.read_to_string(&path_to_string(&path));
+
+    if code.is_err() {
+        return Err(ProjectError::new(
+            0,
+            0,
+            &format!("{}: File error: {}", path.display(), unsafe {
+                code.unwrap_err_unchecked()
+            }),
+        ));
+    }
+
+    let manifest = parse_manifest(&unsafe { code.unwrap_unchecked() });
+
+    if manifest.is_err() {
+        let mut error = unsafe { manifest.unwrap_err_unchecked() };
+        error.description = format!("{}: {}", path.display(), error.description);
+        return Err(error);
+    }
+
+    manifest
+}
+
+fn path_to_string(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+/// Paths, that policy doesn't allow, are treated as missing, so scripts can't probe them.
+fn is_file(engine: &Engine, path: &Path) -> bool {
+    let path = path_to_string(path);
+
+    if !engine.is_path_allowed(&path) {
+        return false;
+    }
+
+    let metadata = engine.get_file_system().metadata(&path);
+    metadata.is_ok() && !unsafe { metadata.unwrap_unchecked() }.is_dir
+}
+
+fn is_dir(engine: &Engine, path: &Path) -> bool {
+    let path = path_to_string(path);
+
+    if !engine.is_path_allowed(&path) {
+        return false;
+    }
+
+    let metadata = engine.get_file_system().metadata(&path);
+    metadata.is_ok() && unsafe { metadata.unwrap_unchecked() }.is_dir
+}
+
+/// Registers dependencies and `vendor` directory of project in `dir` as module sources.
+pub fn add_project_modules(engine: &mut Engine, dir: &str, manifest: &Manifest) {
+    let root = Path::new(dir);
+
+    for dependency in manifest.dependencies.iter() {
+        if engine.get_dependency(&dependency.0).is_none() {
+            engine.add_dependency(&dependency.0, &path_to_string(&root.join(&dependency.1)));
+        }
+    }
+
+    engine.add_module_path(&path_to_string(&root.join(VENDOR_DIR)));
+}
+
+fn get_module_entry(engine: &mut Engine, dir: &Path) -> Result<PathBuf, ProjectError> {
+    if is_file(engine, &dir.join(MANIFEST_FILE)) {
+        let dir_string = path_to_string(dir);
+        let manifest = load_manifest(engine.get_file_system().as_ref(), &dir_string)?;
+        add_project_modules(engine, &dir_string, &manifest);
+        return Ok(dir.join(manifest.entry));
+    }
+
+    Ok(dir.join(DEFAULT_ENTRY))
+}
+
+/// Resolves module name, passed to `import`, to path of the file, that should be executed.
+///
+/// File names (ending with `.ep`) are looked up relative to the current directory and then
+/// relative to every module path. Other names are looked up in project dependencies and then
+/// as `<name>.ep` or `<name>/` in every module path.
+/// Files and manifests outside of directories, allowed by policy of engine, are skipped.
+pub fn resolve_module(engine: &mut Engine, name: &str) -> Result<String, ProjectError> {
+    let mut candidates: Vec<PathBuf> = Vec::new();
+
+    if name.ends_with(MODULE_EXTENSION) {
+        candidates.push(PathBuf::from(name));
+
+        for module_path in engine.get_module_paths() {
+            candidates.push(Path::new(module_path).join(name));
+        }
+
+        for candidate in candidates {
+            if is_file(engine, &candidate) {
+                return Ok(path_to_string(&candidate));
+            }
+        }
+
+        return Err(ProjectError::new(
+            0,
+            0,
+            &format!("Module file '{}' not found", name),
+        ));
+    }
+
+    let dependency = engine.get_dependency(name).cloned();
+
+    if dependency.is_some() {
+        let dir = PathBuf::from(unsafe { dependency.unwrap_unchecked() });
+
+        if !is_dir(engine, &dir) {
+            return Err(ProjectError::new(
+                0,
+                0,
+                &format!(
+                    "Dependency '{}' points to missing directory '{}'",
+                    name,
+                    dir.display()
+                ),
+            ));
+        }
+
+        let entry = get_module_entry(engine, &dir)?;
+        return Ok(path_to_string(&entry));
+    }
+
+    for module_path in engine.get_module_paths().clone() {
+        let file = Path::new(&module_path).join(name.to_string() + MODULE_EXTENSION);
+
+        if is_file(engine, &file) {
+            return Ok(path_to_string(&file));
+        }
+
+        let dir = Path::new(&module_path).join(name);
+
+        if is_dir(engine, &dir) {
+            let entry = get_module_entry(engine, &dir)?;
+            return Ok(path_to_string(&entry));
+        }
+    }
+
+    Err(ProjectError::new(
+        0,
+        0,
+        &format!("Module '{}' not found", name),
+    ))
+}
+
+#[cfg(feature = "parser")]
+pub fn run_project_scope(dir: &str, scope: &mut Scope) -> bool {
+    let manifest_result = load_manifest(scope.get_engine().get_file_system().as_ref(), dir);
+
+    if manifest_result.is_err() {
+        let error = unsafe { manifest_result.unwrap_err_unchecked() };
+
+        if error.line == 0 {
+            println!("Manifest error: {}", error.description);
+        } else {
+            println!(
+                "Manifest error on line {} column {}: {}",
+                error.line, error.column, error.description
+            );
+        }
+
+        return false;
+    }
+
+    let manifest = unsafe { manifest_result.unwrap_unchecked() };
+    let entry = path_to_string(&Path::new(dir).join(&manifest.entry));
+
+    if !scope
+        .get_engine()
+        .get_policy()
+        .is_allowed(Permission::FileRead)
+    {
+        println!(
+            "{}: Permission denied: {} is not allowed",
+            entry,
+            Permission::FileRead.get_name()
+        );
+        return false;
+    }
+
+    if !scope.get_engine().is_path_allowed(&entry) {
+        println!(
+            "{}: Permission denied: access to '{}' is not allowed",
+            entry, entry
+        );
+        return false;
+    }
+
+    let code = scope.get_engine().get_file_system().read_to_string(&entry);
+
+    if code.is_err() {
+        println!("{}: File error: {}", entry, unsafe {
+            code.unwrap_err_unchecked()
+        });
+        return false;
+    }
+
+    let mut engine = scope.get_engine_mut();
+    engine.add_module_path(dir);
+    add_project_modules(&mut engine, dir, &manifest);
+    drop(engine);
+    run_file_code_scope(&entry, &unsafe { code.unwrap_unchecked() }, scope)
+}
+
+#[cfg(feature = "parser")]
+pub fn run_project(dir: &str) -> bool {
+    let mut scope: Scope = Scope::with_stdlib();
+    run_project_scope(dir, &mut scope)
+}
@@ -0,0 +1,63 @@
+/// Seedable pseudorandom number generator (SplitMix64).
+/// Same seed always gives the same sequence on every platform.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Random {
+    seed: u64,
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Random {
+        Random { seed, state: seed }
+    }
+
+    /// Gets seed, that was used for the last reset of the generator.
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Restarts sequence from `seed`.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.state = seed;
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut result = self.state;
+        result = (result ^ (result >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        result = (result ^ (result >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        result ^ (result >> 31)
+    }
+
+    /// Gets uniformly distributed number from `0` to `bound - 1`. `bound` should not be zero.
+    pub fn next_below(&mut self, bound: u64) -> u64 {
+        // Values from the incomplete last block are rejected to avoid modulo bias.
+        let limit = u64::MAX - u64::MAX % bound;
+
+        loop {
+            let value = self.next_u64();
+
+            if value < limit {
+                return value % bound;
+            }
+        }
+    }
+
+    /// Gets uniformly distributed number from `min` to `max` inclusive. `min` should not be greater than `max`.
+    pub fn next_range(&mut self, min: i64, max: i64) -> i64 {
+        let span = max.wrapping_sub(min) as u64;
+
+        if span == u64::MAX {
+            return self.next_u64() as i64;
+        }
+
+        min.wrapping_add(self.next_below(span + 1) as i64)
+    }
+}
+
+impl Default for Random {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
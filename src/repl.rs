@@ -1,5 +1,5 @@
 #[cfg(feature = "repl-rustyline")]
-use crate::lexer::{get_checked_tokens, is_identifier_char};
+use crate::lexer::{get_checked_tokens, is_identifier_char, UNTERMINATED_MULTILINE_STRING};
 #[cfg(feature = "repl-rustyline")]
 use crate::types::TokenType;
 #[cfg(feature = "repl-rustyline")]
@@ -235,7 +235,12 @@ impl rustyline::validate::Validator for VarFuncHelper {
         let tokens_result = get_checked_tokens(ctx.input());
 
         if tokens_result.is_err() {
-            // let error = unsafe { tokens_result.unwrap_err_unchecked() };
+            if unsafe { tokens_result.unwrap_err_unchecked() }.description
+                == UNTERMINATED_MULTILINE_STRING
+            {
+                return Ok(rustyline::validate::ValidationResult::Incomplete);
+            }
+
             // Let user see error from lexer, not from rustyline
             return Ok(rustyline::validate::ValidationResult::Valid(None));
         }
@@ -360,9 +365,22 @@ pub fn start_repl_ex<T: Write>(scope: &mut Scope, out: &mut T) -> ReplError {
                 Type::Int => {
                     let _ = write!(out, "{}", final_result.as_int().number);
                 }
+                Type::Float => {
+                    let _ = write!(out, "{:?}", final_result.as_float().number);
+                }
+                Type::Bool => {
+                    let _ = write!(out, "{}", final_result.as_bool().value);
+                }
+                #[cfg(feature = "bigint")]
+                Type::BigInt => {
+                    let _ = write!(out, "{}", final_result.as_bigint());
+                }
                 Type::Str => {
                     let _ = write!(out, "\"{}\"", final_result.as_str().text);
                 }
+                Type::List | Type::Map => {
+                    let _ = write!(out, "{:?}", final_result);
+                }
                 Type::Func => {
                     let func = final_result.as_func();
 
@@ -452,9 +470,22 @@ pub fn start_default_repl<W: Write>(scope: &mut Scope, out: &mut W, in_: Stdin)
                 Type::Int => {
                     let _ = write!(out, "{}", final_result.as_int().number);
                 }
+                Type::Float => {
+                    let _ = write!(out, "{:?}", final_result.as_float().number);
+                }
+                Type::Bool => {
+                    let _ = write!(out, "{}", final_result.as_bool().value);
+                }
+                #[cfg(feature = "bigint")]
+                Type::BigInt => {
+                    let _ = write!(out, "{}", final_result.as_bigint());
+                }
                 Type::Str => {
                     let _ = write!(out, "\"{}\"", final_result.as_str().text);
                 }
+                Type::List | Type::Map => {
+                    let _ = write!(out, "{:?}", final_result);
+                }
                 Type::Func => {
                     let func = final_result.as_func();
 
@@ -3,9 +3,10 @@ use crate::parser::parse;
 
 use std::{fs, io::Error, rc::Rc, sync::Mutex};
 
+use crate::engine::StackFrame;
 use crate::types::{
-    CallFuncNode, Function, NativeException, NodeType, Scope, SequenceNode, VariableNode, Variant,
-    Void,
+    AstNode, CallFuncNode, Function, List, Map, MapItems, MapKey, NativeException, NodeType, Scope,
+    SequenceNode, VariableNode, Variant, Void,
 };
 
 #[derive(Debug)]
@@ -26,6 +27,9 @@ impl RunnerError {
 }
 
 static DESTRUCTORS: Mutex<Vec<fn(&mut Scope)>> = Mutex::new(Vec::new());
+pub static MAIN_FRAME: &str = "<main>";
+pub static MODULE_FRAME: &str = "<module>";
+static BLOCK_FRAME: &str = "<block>";
 
 pub fn get_variable(scope: &Scope, node: VariableNode) -> Result<&Rc<dyn Variant>, RunnerError> {
     let result = scope.get_variable(&node.name);
@@ -41,6 +45,70 @@ pub fn get_variable(scope: &Scope, node: VariableNode) -> Result<&Rc<dyn Variant
     Ok(unsafe { result.unwrap_unchecked() })
 }
 
+/// Evaluates argument node: calls functions, reads variables and builds constants and lists.
+pub fn evaluate_node(
+    scope: &mut Scope,
+    node: Rc<dyn AstNode>,
+) -> Result<Result<Rc<dyn Variant>, NativeException>, RunnerError> {
+    match node.get_type() {
+        NodeType::Sequence => Ok(Ok(Rc::new(Function::new(node.as_sequence())))),
+        NodeType::CallFunc => execute_func(scope, node.as_call_func()),
+        NodeType::ConstInt => Ok(Ok(Rc::new(node.as_int_const().value))),
+        NodeType::ConstFloat => Ok(Ok(Rc::new(node.as_float_const().value))),
+        NodeType::ConstStr => Ok(Ok(Rc::new(node.as_str_const().value))),
+        NodeType::Identifier => Ok(Ok(get_variable(scope, node.as_variable())?.clone())),
+        NodeType::List => {
+            let mut items: Vec<Rc<dyn Variant>> = Vec::new();
+
+            for item in node.as_list().items {
+                let result = evaluate_node(&mut *scope, item)?;
+
+                if result.is_err() {
+                    return Ok(Err(unsafe { result.unwrap_err_unchecked() }));
+                }
+
+                items.push(unsafe { result.unwrap_unchecked() });
+            }
+
+            Ok(Ok(Rc::new(List::new(items))))
+        }
+        NodeType::Map => {
+            let map_node = node.as_map();
+            let mut items = MapItems::new();
+
+            for (key_node, value_node) in map_node.items {
+                let key = evaluate_node(&mut *scope, key_node)?;
+
+                if key.is_err() {
+                    return Ok(Err(unsafe { key.unwrap_err_unchecked() }));
+                }
+
+                let key = MapKey::from_variant(unsafe { key.unwrap_unchecked() }.as_ref());
+
+                if key.is_none() {
+                    return Ok(Err(NativeException::new(
+                        map_node.line,
+                        map_node.column,
+                        "Map key should be `Int` or `Str`",
+                    )));
+                }
+
+                let value = evaluate_node(&mut *scope, value_node)?;
+
+                if value.is_err() {
+                    return Ok(Err(unsafe { value.unwrap_err_unchecked() }));
+                }
+
+                items.insert(unsafe { key.unwrap_unchecked() }, unsafe {
+                    value.unwrap_unchecked()
+                });
+            }
+
+            Ok(Ok(Rc::new(Map::new(items))))
+        }
+    }
+}
+
 pub fn execute_func(
     scope: &mut Scope,
     node: CallFuncNode,
@@ -61,51 +129,37 @@ pub fn execute_func(
     let mut value_args: Vec<Rc<dyn Variant>> = Vec::new();
 
     for arg in node.args {
-        match arg.get_type() {
-            NodeType::Sequence => {
-                value_args.push(Rc::new(Function::new(arg.as_sequence())));
-            }
-            NodeType::CallFunc => {
-                let result = execute_func(&mut *scope, arg.as_call_func());
-
-                if result.is_err() {
-                    return unsafe { Err(result.unwrap_err_unchecked()) };
-                }
-
-                let native_result = unsafe { result.unwrap_unchecked() };
+        let result = evaluate_node(&mut *scope, arg)?;
 
-                if native_result.is_err() {
-                    return unsafe { Ok(Err(native_result.unwrap_err_unchecked())) };
-                }
-
-                value_args.push(unsafe { native_result.unwrap_unchecked() });
-            }
-            NodeType::ConstInt => {
-                value_args.push(Rc::new(arg.as_int_const().value));
-            }
-            NodeType::ConstStr => {
-                value_args.push(Rc::new(arg.as_str_const().value));
-            }
-            NodeType::Identifier => {
-                let result = get_variable(scope, arg.as_variable());
-
-                if result.is_err() {
-                    return Err(unsafe { result.unwrap_err_unchecked() });
-                }
-
-                value_args.push(unsafe { result.unwrap_unchecked() }.clone());
-            }
+        if result.is_err() {
+            return Ok(Err(unsafe { result.unwrap_err_unchecked() }));
         }
+
+        value_args.push(unsafe { result.unwrap_unchecked() });
     }
 
     if func.body.is_some() {
-        execute_sequence(&mut *scope, unsafe {
+        execute_frame(&mut *scope, Some(&node.name), None, unsafe {
             func.body.as_ref().unwrap_unchecked()
         });
         return Ok(Ok(Rc::new(Void::new())));
     } else if func.native.is_some() {
+        let debugging = scope.get_engine().is_debugging();
+
+        if debugging {
+            scope
+                .get_engine_mut()
+                .get_native_calls_mut()
+                .push(node.name.clone());
+        }
+
         let var =
             unsafe { func.native.unwrap_unchecked() }(node.line, node.column, scope, value_args);
+
+        if debugging {
+            scope.get_engine_mut().get_native_calls_mut().pop();
+        }
+
         return Ok(var);
     }
 
@@ -122,13 +176,75 @@ pub fn execute_func(
 pub fn execute_sequence(
     scope: &mut Scope,
     node: &SequenceNode,
+) -> Option<Result<RunnerError, NativeException>> {
+    execute_frame(scope, None, None, node)
+}
+
+/// Executes sequence in new frame of call stack, if debugger is attached.
+/// By default frame is named after native function, that executes the sequence, and has file of parent frame.
+pub fn execute_frame(
+    scope: &mut Scope,
+    name: Option<&str>,
+    file: Option<&str>,
+    node: &SequenceNode,
+) -> Option<Result<RunnerError, NativeException>> {
+    if !scope.get_engine().is_debugging() {
+        return execute_statements(scope, node);
+    }
+
+    let mut engine = scope.get_engine_mut();
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => match engine.get_native_calls_mut().last() {
+            Some(name) => name.clone(),
+            None => BLOCK_FRAME.to_string(),
+        },
+    };
+    let call_stack = engine.get_call_stack_mut();
+    let file = match file {
+        Some(file) => file.to_string(),
+        None => match call_stack.last() {
+            Some(frame) => frame.file.clone(),
+            None => String::new(),
+        },
+    };
+    let depth = call_stack.len();
+    call_stack.push(StackFrame::new(&name, &file, node.line, node.column));
+    drop(engine);
+    let result = execute_statements(scope, node);
+    scope.get_engine_mut().get_call_stack_mut().truncate(depth);
+    result
+}
+
+fn execute_statements(
+    scope: &mut Scope,
+    node: &SequenceNode,
 ) -> Option<Result<RunnerError, NativeException>> {
     for child in node.body.iter() {
         if child.get_type() != NodeType::CallFunc {
             panic!();
         }
 
-        let result = execute_func(&mut *scope, child.as_call_func());
+        let call = child.as_call_func();
+
+        let debug_hook = scope.get_engine().get_debug_hook();
+
+        if let Some(debug_hook) = debug_hook {
+            if let Some(frame) = scope.get_engine_mut().get_call_stack_mut().last_mut() {
+                frame.line = call.line;
+                frame.column = call.column;
+            }
+
+            if !debug_hook.on_statement(scope) {
+                return Some(Ok(RunnerError::new(
+                    call.line,
+                    call.column,
+                    "Execution stopped by debugger",
+                )));
+            }
+        }
+
+        let result = execute_func(&mut *scope, call);
 
         if result.is_err() {
             return Some(Ok(unsafe { result.unwrap_err_unchecked() }));
@@ -170,7 +286,7 @@ pub fn cleanup(scope: &mut Scope) -> bool {
 }
 
 pub fn execute(scope: &mut Scope, ast: &SequenceNode, path: &str) -> bool {
-    let exec_result = execute_sequence(&mut *scope, ast);
+    let exec_result = execute_frame(&mut *scope, Some(MAIN_FRAME), Some(path), ast);
 
     if exec_result.is_some() {
         let error = unsafe { exec_result.unwrap_unchecked() };
@@ -228,7 +344,13 @@ pub fn run_file_scope(path: &str, scope: &mut Scope) -> bool {
         return false;
     }
 
-    let parse_result = parse(&unsafe { code.unwrap_unchecked() });
+    run_file_code_scope(path, &unsafe { code.unwrap_unchecked() }, scope)
+}
+
+/// Runs `code`, that was read from file at `path`.
+#[cfg(feature = "parser")]
+pub fn run_file_code_scope(path: &str, code: &str, scope: &mut Scope) -> bool {
+    let parse_result = parse(code);
 
     if parse_result.is_err() {
         let error = unsafe { parse_result.unwrap_err_unchecked() };
@@ -0,0 +1,767 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    rc::Rc,
+};
+
+#[cfg(feature = "bigint")]
+use crate::bigint::BigInt;
+use crate::{
+    engine::Engine,
+    stdlib::{json_to_value, value_to_json, MAX_JSON_DEPTH},
+    types::{
+        AstNode, CallFuncNode, ConstFloatNode, ConstIntNode, ConstStrNode, Float, Function, Int,
+        List, ListNode, Map, MapItems, MapKey, MapNode, NodeType, Scope, SequenceNode, Str, Type,
+        VariableNode, Variant, Void,
+    },
+};
+
+pub static SNAPSHOT_FORMAT: &str = "easy_prog_snapshot";
+/// Version of snapshot format. Snapshots of newer versions can't be loaded.
+pub const SNAPSHOT_VERSION: i64 = 1;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SnapshotError {
+    pub description: String,
+}
+
+impl SnapshotError {
+    pub fn new(description: &str) -> SnapshotError {
+        SnapshotError {
+            description: description.to_string(),
+        }
+    }
+}
+
+fn invalid(description: &str) -> SnapshotError {
+    SnapshotError::new(&format!("Invalid snapshot: {}", description))
+}
+
+fn int_value(number: i64) -> Rc<dyn Variant> {
+    Rc::new(Int::new(number))
+}
+
+fn str_value(text: &str) -> Rc<dyn Variant> {
+    Rc::new(Str::new(text))
+}
+
+fn list_value(items: Vec<Rc<dyn Variant>>) -> Rc<dyn Variant> {
+    Rc::new(List::new(items))
+}
+
+/// Values and nodes are saved as lists with name of type first: `["Int", 5]`.
+fn tagged(tag: &str, mut fields: Vec<Rc<dyn Variant>>) -> Rc<dyn Variant> {
+    fields.insert(0, str_value(tag));
+    list_value(fields)
+}
+
+/// Fails, if list at JSON `depth` can't be loaded back because of JSON nesting limit.
+fn check_depth(depth: usize) -> Result<(), SnapshotError> {
+    if depth > MAX_JSON_DEPTH {
+        return Err(SnapshotError::new("Nesting is too deep"));
+    }
+
+    Ok(())
+}
+
+/// Saves nodes to list at JSON `depth`.
+fn save_nodes(nodes: &[Rc<dyn AstNode>], depth: usize) -> Result<Rc<dyn Variant>, SnapshotError> {
+    check_depth(depth)?;
+    let mut items: Vec<Rc<dyn Variant>> = Vec::new();
+
+    for node in nodes {
+        items.push(save_node(node, depth + 1)?);
+    }
+
+    Ok(list_value(items))
+}
+
+fn save_sequence(sequence: &SequenceNode, depth: usize) -> Result<Rc<dyn Variant>, SnapshotError> {
+    check_depth(depth)?;
+    Ok(tagged(
+        "Seq",
+        vec![
+            int_value(sequence.line as i64),
+            int_value(sequence.column as i64),
+            save_nodes(&sequence.body, depth + 1)?,
+        ],
+    ))
+}
+
+fn save_node(node: &Rc<dyn AstNode>, depth: usize) -> Result<Rc<dyn Variant>, SnapshotError> {
+    check_depth(depth)?;
+
+    Ok(match node.get_type() {
+        NodeType::Sequence => save_sequence(&node.as_sequence(), depth)?,
+        NodeType::CallFunc => {
+            let call = node.as_call_func();
+            tagged(
+                "Call",
+                vec![
+                    int_value(call.line as i64),
+                    int_value(call.column as i64),
+                    str_value(&call.name),
+                    save_nodes(&call.args, depth + 1)?,
+                ],
+            )
+        }
+        NodeType::ConstInt => {
+            let constant = node.as_int_const();
+            tagged(
+                "Int",
+                vec![
+                    int_value(constant.line as i64),
+                    int_value(constant.column as i64),
+                    int_value(constant.value.number),
+                ],
+            )
+        }
+        NodeType::ConstFloat => {
+            let constant = node.as_float_const();
+            tagged(
+                "Float",
+                vec![
+                    int_value(constant.line as i64),
+                    int_value(constant.column as i64),
+                    str_value(&format!("{:?}", constant.value.number)),
+                ],
+            )
+        }
+        NodeType::ConstStr => {
+            let constant = node.as_str_const();
+            tagged(
+                "Str",
+                vec![
+                    int_value(constant.line as i64),
+                    int_value(constant.column as i64),
+                    str_value(&constant.value.text),
+                ],
+            )
+        }
+        NodeType::Identifier => {
+            let variable = node.as_variable();
+            tagged(
+                "Id",
+                vec![
+                    int_value(variable.line as i64),
+                    int_value(variable.column as i64),
+                    str_value(&variable.name),
+                ],
+            )
+        }
+        NodeType::List => {
+            let list = node.as_list();
+            tagged(
+                "List",
+                vec![
+                    int_value(list.line as i64),
+                    int_value(list.column as i64),
+                    save_nodes(&list.items, depth + 1)?,
+                ],
+            )
+        }
+        NodeType::Map => {
+            let map = node.as_map();
+            let mut items: Vec<Rc<dyn Variant>> = Vec::new();
+            check_depth(depth + 1)?;
+
+            for (key, value) in map.items.iter() {
+                check_depth(depth + 2)?;
+                items.push(list_value(vec![
+                    save_node(key, depth + 3)?,
+                    save_node(value, depth + 3)?,
+                ]));
+            }
+
+            tagged(
+                "Map",
+                vec![
+                    int_value(map.line as i64),
+                    int_value(map.column as i64),
+                    list_value(items),
+                ],
+            )
+        }
+    })
+}
+
+/// Saves values. Lists and maps, that are referenced several times, are saved once and then referenced by id.
+#[derive(Default)]
+struct Saver {
+    ids: HashMap<usize, i64>,
+}
+
+impl Saver {
+    fn save_collection(
+        &mut self,
+        tag: &str,
+        ptr: usize,
+        save_items: impl FnOnce(&mut Saver) -> Result<Rc<dyn Variant>, SnapshotError>,
+    ) -> Result<Rc<dyn Variant>, SnapshotError> {
+        if let Some(id) = self.ids.get(&ptr) {
+            return Ok(tagged("Ref", vec![int_value(*id)]));
+        }
+
+        let id = self.ids.len() as i64;
+        self.ids.insert(ptr, id);
+        Ok(tagged(tag, vec![int_value(id), save_items(self)?]))
+    }
+
+    /// Saves value to tagged list at JSON `depth`.
+    fn save_value(
+        &mut self,
+        value: &Rc<dyn Variant>,
+        depth: usize,
+    ) -> Result<Rc<dyn Variant>, SnapshotError> {
+        check_depth(depth)?;
+
+        Ok(match value.get_type() {
+            Type::Void => tagged("Void", Vec::new()),
+            Type::Bool => tagged("Bool", vec![Rc::new(value.as_bool())]),
+            Type::Int => tagged("Int", vec![int_value(value.as_int().number)]),
+            Type::Float => tagged(
+                "Float",
+                vec![str_value(&format!("{:?}", value.as_float().number))],
+            ),
+            #[cfg(feature = "bigint")]
+            Type::BigInt => tagged("BigInt", vec![str_value(&value.as_bigint().to_string())]),
+            Type::Str => tagged("Str", vec![str_value(&value.as_str().text)]),
+            Type::List => {
+                let list = value.as_list();
+                let ptr = Rc::as_ptr(&list.items) as *const () as usize;
+                self.save_collection("List", ptr, |saver| {
+                    let mut items: Vec<Rc<dyn Variant>> = Vec::new();
+                    check_depth(depth + 1)?;
+
+                    for item in list.items.borrow().iter() {
+                        items.push(saver.save_value(item, depth + 2)?);
+                    }
+
+                    Ok(list_value(items))
+                })?
+            }
+            Type::Map => {
+                let map = value.as_map();
+                let ptr = Rc::as_ptr(&map.items) as *const () as usize;
+                self.save_collection("Map", ptr, |saver| {
+                    let mut entries: Vec<Rc<dyn Variant>> = Vec::new();
+                    check_depth(depth + 1)?;
+
+                    for (key, value) in map.items.borrow().entries() {
+                        check_depth(depth + 2)?;
+                        entries.push(list_value(vec![
+                            key.to_variant(),
+                            saver.save_value(value, depth + 3)?,
+                        ]));
+                    }
+
+                    Ok(list_value(entries))
+                })?
+            }
+            Type::Func => {
+                let func = value.as_func();
+
+                if func.body.is_none() {
+                    return Err(SnapshotError::new("Native function can't be saved"));
+                }
+
+                tagged(
+                    "Func",
+                    vec![save_sequence(
+                        unsafe { func.body.as_ref().unwrap_unchecked() },
+                        depth + 1,
+                    )?],
+                )
+            }
+            Type::Custom => {
+                let custom = value.as_custom();
+                let data = custom.snapshot();
+
+                if data.is_none() {
+                    return Err(SnapshotError::new(&format!(
+                        "Custom type {} can't be saved",
+                        custom.get_id()
+                    )));
+                }
+
+                tagged(
+                    "Custom",
+                    vec![
+                        int_value(custom.get_id() as i64),
+                        str_value(&unsafe { data.unwrap_unchecked() }),
+                    ],
+                )
+            }
+        })
+    }
+}
+
+/// Serializes variables and user functions of scope and its parents to versioned JSON text.
+/// Native functions aren't saved, they should be added to scope, that loads snapshot.
+pub fn save_scope(scope: &Scope) -> Result<String, SnapshotError> {
+    let mut saver = Saver::default();
+    let mut variables = MapItems::new();
+
+    for (name, value) in scope.get_variables() {
+        // Value is nested into root object and "variables" object.
+        let saved = saver.save_value(&value, 3).map_err(|error| {
+            SnapshotError::new(&format!(
+                "Can't save variable '{}': {}",
+                name, error.description
+            ))
+        })?;
+        variables.insert(MapKey::Str(name), saved);
+    }
+
+    let mut functions = MapItems::new();
+
+    for (name, func) in scope.get_functions() {
+        if let Some(body) = &func.body {
+            let saved = save_sequence(body, 3).map_err(|error| {
+                SnapshotError::new(&format!(
+                    "Can't save function '{}': {}",
+                    name, error.description
+                ))
+            })?;
+            functions.insert(MapKey::Str(name), saved);
+        }
+    }
+
+    let mut root = MapItems::new();
+    root.insert(
+        MapKey::Str("format".to_string()),
+        str_value(SNAPSHOT_FORMAT),
+    );
+    root.insert(
+        MapKey::Str("version".to_string()),
+        int_value(SNAPSHOT_VERSION),
+    );
+    root.insert(
+        MapKey::Str("variables".to_string()),
+        Rc::new(Map::new(variables)),
+    );
+    root.insert(
+        MapKey::Str("functions".to_string()),
+        Rc::new(Map::new(functions)),
+    );
+    let root: Rc<dyn Variant> = Rc::new(Map::new(root));
+    value_to_json(0, 0, &root, 0).map_err(|error| SnapshotError::new(&error.description))
+}
+
+fn get_tagged(value: &Rc<dyn Variant>) -> Result<(String, Vec<Rc<dyn Variant>>), SnapshotError> {
+    if value.get_type() != Type::List {
+        return Err(invalid("expected tagged list"));
+    }
+
+    let mut items = value.as_list().items.borrow().clone();
+
+    if items.is_empty() || items[0].get_type() != Type::Str {
+        return Err(invalid("expected tagged list"));
+    }
+
+    let tag = items.remove(0).as_str().text;
+    Ok((tag, items))
+}
+
+fn check_fields(tag: &str, fields: &[Rc<dyn Variant>], count: usize) -> Result<(), SnapshotError> {
+    if fields.len() != count {
+        return Err(invalid(&format!("wrong number of fields in '{}'", tag)));
+    }
+
+    Ok(())
+}
+
+fn get_int(value: &Rc<dyn Variant>) -> Result<i64, SnapshotError> {
+    if value.get_type() != Type::Int {
+        return Err(invalid("expected integer"));
+    }
+
+    Ok(value.as_int().number)
+}
+
+fn get_position(fields: &[Rc<dyn Variant>]) -> Result<(u32, u32), SnapshotError> {
+    let line = get_int(&fields[0])?;
+    let column = get_int(&fields[1])?;
+
+    if line < 0 || line > u32::MAX as i64 || column < 0 || column > u32::MAX as i64 {
+        return Err(invalid("position is out of range"));
+    }
+
+    Ok((line as u32, column as u32))
+}
+
+fn get_text(value: &Rc<dyn Variant>) -> Result<String, SnapshotError> {
+    if value.get_type() != Type::Str {
+        return Err(invalid("expected string"));
+    }
+
+    Ok(value.as_str().text)
+}
+
+fn get_items(value: &Rc<dyn Variant>) -> Result<Vec<Rc<dyn Variant>>, SnapshotError> {
+    if value.get_type() != Type::List {
+        return Err(invalid("expected list"));
+    }
+
+    Ok(value.as_list().items.borrow().clone())
+}
+
+fn get_float(value: &Rc<dyn Variant>) -> Result<f64, SnapshotError> {
+    get_text(value)?
+        .parse::<f64>()
+        .map_err(|_| invalid("invalid float"))
+}
+
+fn load_sequence(value: &Rc<dyn Variant>) -> Result<SequenceNode, SnapshotError> {
+    let (tag, fields) = get_tagged(value)?;
+
+    if tag != "Seq" {
+        return Err(invalid("expected function body"));
+    }
+
+    check_fields(&tag, &fields, 3)?;
+    let (line, column) = get_position(&fields)?;
+    let mut body: Vec<Rc<dyn AstNode>> = Vec::new();
+
+    for item in get_items(&fields[2])? {
+        body.push(load_node(&item)?);
+    }
+
+    Ok(SequenceNode::new(line, column, body))
+}
+
+fn load_node(value: &Rc<dyn Variant>) -> Result<Rc<dyn AstNode>, SnapshotError> {
+    let (tag, fields) = get_tagged(value)?;
+
+    if tag == "Seq" {
+        return Ok(Rc::new(load_sequence(value)?));
+    }
+
+    check_fields(&tag, &fields, if tag == "Call" { 4 } else { 3 })?;
+    let (line, column) = get_position(&fields)?;
+
+    Ok(match tag.as_str() {
+        "Call" => {
+            let mut args: Vec<Rc<dyn AstNode>> = Vec::new();
+
+            for arg in get_items(&fields[3])? {
+                args.push(load_node(&arg)?);
+            }
+
+            Rc::new(CallFuncNode::new(line, column, get_text(&fields[2])?, args))
+        }
+        "Int" => Rc::new(ConstIntNode::new(
+            line,
+            column,
+            Int::new(get_int(&fields[2])?),
+        )),
+        "Float" => Rc::new(ConstFloatNode::new(
+            line,
+            column,
+            Float::new(get_float(&fields[2])?),
+        )),
+        "Str" => Rc::new(ConstStrNode::new(
+            line,
+            column,
+            Str::new(&get_text(&fields[2])?),
+        )),
+        "Id" => Rc::new(VariableNode::new(line, column, &get_text(&fields[2])?)),
+        "List" => {
+            let mut items: Vec<Rc<dyn AstNode>> = Vec::new();
+
+            for item in get_items(&fields[2])? {
+                items.push(load_node(&item)?);
+            }
+
+            Rc::new(ListNode::new(line, column, items))
+        }
+        "Map" => {
+            let mut items: Vec<(Rc<dyn AstNode>, Rc<dyn AstNode>)> = Vec::new();
+
+            for entry in get_items(&fields[2])? {
+                let entry = get_items(&entry)?;
+
+                if entry.len() != 2 {
+                    return Err(invalid("map entry should have key and value"));
+                }
+
+                items.push((load_node(&entry[0])?, load_node(&entry[1])?));
+            }
+
+            Rc::new(MapNode::new(line, column, items))
+        }
+        _ => return Err(invalid(&format!("unknown node '{}'", tag))),
+    })
+}
+
+struct Loader<'a> {
+    engine: &'a Engine,
+    collections: HashMap<i64, Rc<dyn Variant>>,
+    /// Ids of collections, whose items are being loaded. Reference to them would make collection contain itself.
+    loading: HashSet<i64>,
+}
+
+impl Loader<'_> {
+    fn add_collection(
+        &mut self,
+        id: &Rc<dyn Variant>,
+        value: Rc<dyn Variant>,
+    ) -> Result<i64, SnapshotError> {
+        let id = get_int(id)?;
+
+        if self.collections.insert(id, value).is_some() {
+            return Err(invalid(&format!("duplicate collection id {}", id)));
+        }
+
+        self.loading.insert(id);
+        Ok(id)
+    }
+
+    fn load_value(&mut self, value: &Rc<dyn Variant>) -> Result<Rc<dyn Variant>, SnapshotError> {
+        let (tag, fields) = get_tagged(value)?;
+
+        Ok(match tag.as_str() {
+            "Void" => {
+                check_fields(&tag, &fields, 0)?;
+                Rc::new(Void::new())
+            }
+            "Bool" => {
+                check_fields(&tag, &fields, 1)?;
+
+                if fields[0].get_type() != Type::Bool {
+                    return Err(invalid("expected boolean"));
+                }
+
+                Rc::new(fields[0].as_bool())
+            }
+            "Int" => {
+                check_fields(&tag, &fields, 1)?;
+                int_value(get_int(&fields[0])?)
+            }
+            "Float" => {
+                check_fields(&tag, &fields, 1)?;
+                Rc::new(Float::new(get_float(&fields[0])?))
+            }
+            #[cfg(feature = "bigint")]
+            "BigInt" => {
+                check_fields(&tag, &fields, 1)?;
+                let number = BigInt::parse(&get_text(&fields[0])?);
+
+                if number.is_none() {
+                    return Err(invalid("invalid big integer"));
+                }
+
+                Rc::new(unsafe { number.unwrap_unchecked() })
+            }
+            #[cfg(not(feature = "bigint"))]
+            "BigInt" => {
+                return Err(SnapshotError::new(
+                    "Snapshot contains `BigInt`, but `bigint` feature is disabled",
+                ));
+            }
+            "Str" => {
+                check_fields(&tag, &fields, 1)?;
+                str_value(&get_text(&fields[0])?)
+            }
+            "List" => {
+                check_fields(&tag, &fields, 2)?;
+                let list = List::new(Vec::new());
+                let id = self.add_collection(&fields[0], Rc::new(list.clone()))?;
+
+                for item in get_items(&fields[1])? {
+                    let item = self.load_value(&item)?;
+                    list.items.borrow_mut().push(item);
+                }
+
+                self.loading.remove(&id);
+                Rc::new(list)
+            }
+            "Map" => {
+                check_fields(&tag, &fields, 2)?;
+                let map = Map::new(MapItems::new());
+                let id = self.add_collection(&fields[0], Rc::new(map.clone()))?;
+
+                for entry in get_items(&fields[1])? {
+                    let entry = get_items(&entry)?;
+
+                    if entry.len() != 2 {
+                        return Err(invalid("map entry should have key and value"));
+                    }
+
+                    let key = MapKey::from_variant(entry[0].as_ref());
+
+                    if key.is_none() {
+                        return Err(invalid("map key should be integer or string"));
+                    }
+
+                    let value = self.load_value(&entry[1])?;
+                    map.items
+                        .borrow_mut()
+                        .insert(unsafe { key.unwrap_unchecked() }, value);
+                }
+
+                self.loading.remove(&id);
+                Rc::new(map)
+            }
+            "Ref" => {
+                check_fields(&tag, &fields, 1)?;
+                let id = get_int(&fields[0])?;
+
+                if self.loading.contains(&id) {
+                    return Err(invalid(&format!("collection {} contains itself", id)));
+                }
+
+                let collection = self.collections.get(&id);
+
+                if collection.is_none() {
+                    return Err(invalid(&format!("unknown collection id {}", id)));
+                }
+
+                unsafe { collection.unwrap_unchecked() }.clone()
+            }
+            "Func" => {
+                check_fields(&tag, &fields, 1)?;
+                Rc::new(Function::new(load_sequence(&fields[0])?))
+            }
+            "Custom" => {
+                check_fields(&tag, &fields, 2)?;
+                let id = get_int(&fields[0])? as usize;
+                let loader = self.engine.get_custom_loader(id);
+
+                if loader.is_none() {
+                    return Err(SnapshotError::new(&format!(
+                        "No loader for custom type {}",
+                        id
+                    )));
+                }
+
+                let custom = unsafe { loader.unwrap_unchecked() }(&get_text(&fields[1])?);
+
+                if custom.is_none() {
+                    return Err(SnapshotError::new(&format!(
+                        "Custom type {} can't be loaded",
+                        id
+                    )));
+                }
+
+                Rc::new(unsafe { custom.unwrap_unchecked() })
+            }
+            _ => return Err(invalid(&format!("unknown type '{}'", tag))),
+        })
+    }
+}
+
+type NamedValues = Vec<(String, Rc<dyn Variant>)>;
+
+fn get_root_field(root: &Map, name: &str) -> Result<Rc<dyn Variant>, SnapshotError> {
+    let value = root
+        .items
+        .borrow()
+        .get(&MapKey::Str(name.to_string()))
+        .cloned();
+
+    if value.is_none() {
+        return Err(invalid(&format!("missing '{}'", name)));
+    }
+
+    Ok(unsafe { value.unwrap_unchecked() })
+}
+
+fn get_named_entries(value: &Rc<dyn Variant>) -> Result<NamedValues, SnapshotError> {
+    if value.get_type() != Type::Map {
+        return Err(invalid("expected object"));
+    }
+
+    Ok(value
+        .as_map()
+        .items
+        .borrow()
+        .entries()
+        .iter()
+        .filter_map(|(key, value)| match key {
+            MapKey::Str(name) => Some((name.clone(), value.clone())),
+            MapKey::Int(_) => None,
+        })
+        .collect())
+}
+
+/// Loads variables and user functions from snapshot to scope, replacing ones with the same names.
+/// Scope isn't changed, if snapshot can't be loaded.
+pub fn load_scope(scope: &mut Scope, snapshot: &str) -> Result<(), SnapshotError> {
+    let root =
+        json_to_value(0, 0, snapshot).map_err(|error| SnapshotError::new(&error.description))?;
+
+    if root.get_type() != Type::Map {
+        return Err(invalid("expected object"));
+    }
+
+    let root = root.as_map();
+    let format = get_root_field(&root, "format")?;
+
+    if format.get_type() != Type::Str || format.as_str().text != SNAPSHOT_FORMAT {
+        return Err(invalid("unknown format"));
+    }
+
+    let version = get_int(&get_root_field(&root, "version")?)?;
+
+    if !(1..=SNAPSHOT_VERSION).contains(&version) {
+        return Err(SnapshotError::new(&format!(
+            "Unsupported snapshot version {}",
+            version
+        )));
+    }
+
+    let engine = scope.get_engine();
+    let mut loader = Loader {
+        engine: &engine,
+        collections: HashMap::new(),
+        loading: HashSet::new(),
+    };
+    let mut variables: NamedValues = Vec::new();
+
+    for (name, value) in get_named_entries(&get_root_field(&root, "variables")?)? {
+        let value = loader.load_value(&value).map_err(|error| {
+            SnapshotError::new(&format!(
+                "Can't load variable '{}': {}",
+                name, error.description
+            ))
+        })?;
+        variables.push((name, value));
+    }
+
+    let mut functions: Vec<(String, Function)> = Vec::new();
+
+    for (name, body) in get_named_entries(&get_root_field(&root, "functions")?)? {
+        let body = load_sequence(&body).map_err(|error| {
+            SnapshotError::new(&format!(
+                "Can't load function '{}': {}",
+                name, error.description
+            ))
+        })?;
+        functions.push((name, Function::new(body)));
+    }
+
+    drop(engine);
+
+    for (name, value) in variables {
+        scope.set_variable(&name, value);
+    }
+
+    for (name, func) in functions {
+        scope.set_function(&name, func);
+    }
+
+    Ok(())
+}
+
+pub fn save_scope_file(scope: &Scope, path: &str) -> Result<(), SnapshotError> {
+    let snapshot = save_scope(scope)?;
+    fs::write(path, snapshot)
+        .map_err(|error| SnapshotError::new(&format!("{}: File error: {}", path, error)))
+}
+
+pub fn load_scope_file(scope: &mut Scope, path: &str) -> Result<(), SnapshotError> {
+    let snapshot = fs::read_to_string(path)
+        .map_err(|error| SnapshotError::new(&format!("{}: File error: {}", path, error)))?;
+    load_scope(scope, &snapshot)
+}
@@ -1,16 +1,26 @@
 use std::rc::Rc;
 use std::{
-    ffi::{c_void, CStr, CString},
-    io::{self, Write},
-    process,
+    cell::RefCell,
+    io::{self, SeekFrom, Write},
 };
 
+#[cfg(feature = "bigint")]
+use crate::bigint::BigInt;
 use crate::{
-    runner::{add_cleanup_destructor, execute_sequence},
+    engine::Permission,
+    filesystem::{FileHandle, OpenFile, OpenMode},
+    runner::execute_sequence,
     types::{
-        Custom, Function, Int, NativeException, Scope, SequenceNode, Str, Type, Variant, Void,
+        contains_collection, Bool, Custom, Float, Function, Int, List, Map, MapItems,
+        NativeException, Scope, SequenceNode, Str, Type, Variant, Void,
     },
 };
+#[cfg(feature = "parser")]
+use crate::{
+    parser::parse,
+    project::resolve_module,
+    runner::{execute_frame, MODULE_FRAME},
+};
 
 macro_rules! native_function {
     ($name: ident, $line: ident, $column: ident, $scope: ident, $args: ident, $body: block) => {
@@ -25,36 +35,324 @@ macro_rules! native_function {
     };
 }
 
+macro_rules! require_permission {
+    ($line: ident, $column: ident, $scope: ident, $permission: expr) => {
+        if !$scope.get_engine().get_policy().is_allowed($permission) {
+            return Err(NativeException::permission_denied(
+                $line,
+                $column,
+                &format!("{} is not allowed", $permission.get_name()),
+            ));
+        }
+    };
+}
+
+macro_rules! require_deterministic {
+    ($line: ident, $column: ident, $scope: ident, $name: expr) => {
+        if $scope.get_engine().is_deterministic() {
+            return Err(NativeException::nondeterministic(
+                $line,
+                $column,
+                &format!("`{}` is not allowed in deterministic mode", $name),
+            ));
+        }
+    };
+}
+
+macro_rules! require_deterministic_file_system {
+    ($line: ident, $column: ident, $scope: ident, $name: expr) => {
+        if $scope.get_engine().is_deterministic()
+            && !$scope.get_engine().get_file_system().is_deterministic()
+        {
+            return Err(NativeException::nondeterministic(
+                $line,
+                $column,
+                &format!(
+                    "`{}` requires deterministic file system in deterministic mode",
+                    $name
+                ),
+            ));
+        }
+    };
+}
+
+macro_rules! require_path_permission {
+    ($line: ident, $column: ident, $scope: ident, $path: expr) => {
+        if !$scope.get_engine().is_path_allowed($path) {
+            return Err(NativeException::permission_denied(
+                $line,
+                $column,
+                &format!("access to '{}' is not allowed", $path),
+            ));
+        }
+    };
+}
+
+mod convert;
+mod env;
+mod format;
+mod fs;
+mod json;
+mod list;
+mod map;
+mod math;
+mod process;
+mod random;
+mod string;
+mod time;
+
+pub use self::convert::*;
+pub use self::env::*;
+pub use self::format::*;
+pub use self::fs::*;
+pub use self::json::*;
+pub use self::list::*;
+pub use self::map::*;
+pub use self::math::*;
+pub use self::process::*;
+pub use self::random::*;
+pub use self::string::*;
+pub use self::time::*;
+
 static CUSTOM_TYPE_FILE: usize = 1;
 
+fn get_ordinal(index: usize) -> &'static str {
+    match index {
+        0 => "First",
+        1 => "Second",
+        2 => "Third",
+        3 => "Fourth",
+        4 => "Fifth",
+        _ => "Next",
+    }
+}
+
+fn check_args_count(
+    line: u32,
+    column: u32,
+    args: &[Rc<dyn Variant>],
+    count: usize,
+) -> Result<(), NativeException> {
+    if args.len() != count {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!(
+                "This function takes {} argument{}, {} given",
+                count,
+                if count == 1 { "" } else { "s" },
+                args.len()
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+fn check_min_args_count(
+    line: u32,
+    column: u32,
+    args: &[Rc<dyn Variant>],
+    count: usize,
+) -> Result<(), NativeException> {
+    if args.len() < count {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!(
+                "This function takes at least {} argument{}, {} given",
+                count,
+                if count == 1 { "" } else { "s" },
+                args.len()
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+fn get_str_arg(
+    line: u32,
+    column: u32,
+    args: &[Rc<dyn Variant>],
+    index: usize,
+    name: &str,
+) -> Result<String, NativeException> {
+    if args[index].get_type() != Type::Str {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!(
+                "{} argument of this function should be `Str({})`",
+                get_ordinal(index),
+                name
+            ),
+        ));
+    }
+
+    Ok(args[index].as_str().text)
+}
+
+fn get_int_arg(
+    line: u32,
+    column: u32,
+    args: &[Rc<dyn Variant>],
+    index: usize,
+    name: &str,
+) -> Result<i64, NativeException> {
+    if args[index].get_type() != Type::Int {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!(
+                "{} argument of this function should be `Int({})`",
+                get_ordinal(index),
+                name
+            ),
+        ));
+    }
+
+    Ok(args[index].as_int().number)
+}
+
+fn get_list_arg(
+    line: u32,
+    column: u32,
+    args: &[Rc<dyn Variant>],
+    index: usize,
+    name: &str,
+) -> Result<List, NativeException> {
+    if args[index].get_type() != Type::List {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!(
+                "{} argument of this function should be `List({})`",
+                get_ordinal(index),
+                name
+            ),
+        ));
+    }
+
+    Ok(args[index].as_list())
+}
+
+fn get_map_arg(
+    line: u32,
+    column: u32,
+    args: &[Rc<dyn Variant>],
+    index: usize,
+    name: &str,
+) -> Result<Map, NativeException> {
+    if args[index].get_type() != Type::Map {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!(
+                "{} argument of this function should be `Map({})`",
+                get_ordinal(index),
+                name
+            ),
+        ));
+    }
+
+    Ok(args[index].as_map())
+}
+
+/// Checks that `value` can be added to `collection` (`List` or `Map`) without making it contain itself.
+fn check_not_cycle(
+    line: u32,
+    column: u32,
+    collection: &dyn Variant,
+    value: &Rc<dyn Variant>,
+) -> Result<(), NativeException> {
+    if contains_collection(value.as_ref(), collection) {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("{:?} can't contain itself", collection.get_type()),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Gets condition argument, which should be `Bool` or, if strict conditions are disabled, `Int`.
+fn get_condition_arg(
+    line: u32,
+    column: u32,
+    scope: &Scope,
+    args: &[Rc<dyn Variant>],
+    index: usize,
+    name: &str,
+) -> Result<bool, NativeException> {
+    let strict = scope.get_engine().is_strict_conditions();
+
+    match args[index].get_type() {
+        Type::Bool => return Ok(args[index].as_bool().value),
+        Type::Int if !strict => return Ok(args[index].as_int().number != 0),
+        _ => {}
+    }
+
+    let expected = if strict {
+        format!("`Bool({})`", name)
+    } else {
+        format!("`Bool({})` or `Int({})`", name, name)
+    };
+
+    Err(NativeException::new(
+        line,
+        column,
+        &format!(
+            "{} argument of this function should be {}",
+            get_ordinal(index),
+            expected
+        ),
+    ))
+}
+
+/// Converts value to condition: `Void`, zero numbers, empty strings, lists and maps are false.
+pub fn is_truthy(value: &dyn Variant) -> bool {
+    match value.get_type() {
+        Type::Void => false,
+        Type::Bool => value.as_bool().value,
+        Type::Int => value.as_int().number != 0,
+        Type::Float => value.as_float().number != 0.0 && !value.as_float().number.is_nan(),
+        #[cfg(feature = "bigint")]
+        Type::BigInt => !value.as_bigint().is_zero(),
+        Type::Str => !value.as_str().text.is_empty(),
+        Type::List => !value.as_list().items.borrow().is_empty(),
+        Type::Map => !value.as_map().items.borrow().is_empty(),
+        Type::Func | Type::Custom => true,
+    }
+}
+
+/// Checks if the first argument is `List`, so function should use its `List` form.
+fn is_list_call(args: &[Rc<dyn Variant>]) -> bool {
+    args.first().is_some_and(|arg| arg.get_type() == Type::List)
+}
+
 struct CustomFile {
-    file: *mut libc::FILE,
-    closed: *mut bool,
+    file: RefCell<Option<OpenFile>>,
 }
 
 impl CustomFile {
-    pub fn new_rc(file: *mut libc::FILE) -> Rc<dyn Custom> {
+    pub fn new_rc(file: Box<dyn FileHandle>) -> Rc<dyn Custom> {
         Rc::new(CustomFile {
-            file,
-            closed: &mut false,
+            file: RefCell::new(Some(OpenFile::new(file))),
         }) as Rc<dyn Custom>
     }
 
-    pub fn from_custom(custom: &dyn Custom) -> CustomFile {
-        unsafe { (custom.get_ptr() as *mut CustomFile).read() }
-    }
-
-    pub fn get_file(&self) -> *mut libc::FILE {
-        self.file
+    pub fn from_custom(custom: &dyn Custom) -> &CustomFile {
+        unsafe { &*(custom.get_ptr() as *const CustomFile) }
     }
 
-    pub fn close(&mut self) {
-        unsafe { self.closed.write(true) };
-        //unsafe { self.ptr.write(*state) };
+    pub fn close(&self) -> bool {
+        self.file.borrow_mut().take().is_some()
     }
 
     pub fn is_closed(&self) -> bool {
-        unsafe { self.closed.read() }
+        self.file.borrow().is_none()
     }
 }
 
@@ -64,47 +362,151 @@ impl Custom for CustomFile {
     }
 
     fn custom_equals(&self, custom: &mut Rc<dyn Custom>) -> bool {
-        self.file == CustomFile::from_custom(custom.as_ref()).file
+        self.get_ptr() == custom.get_ptr()
+    }
+
+    fn repr(&self) -> Option<String> {
+        if self.is_closed() {
+            return Some("closed file".to_string());
+        }
+
+        Some("file".to_string())
     }
 }
 
-native_function!(print, _line, _column, _scope, args, {
-    for arg in args {
-        match arg.get_type() {
-            Type::Int => {
-                print!("{}", arg.as_int().number);
-            }
-            Type::Str => {
-                print!("{}", arg.as_str().text);
-            }
-            Type::Void => {
-                print!("<null>");
-            }
-            Type::Func => {
-                print!(
-                    "<function at address {:#}>",
-                    &arg.as_func() as *const Function as u64
-                )
-            }
-            Type::Custom => {
-                let node = arg.as_custom();
-                let repr = node.repr();
-
-                if repr.is_some() {
-                    print!("<custom type {}: {:?}>", node.get_id(), unsafe {
-                        repr.unwrap_unchecked()
-                    });
-                } else {
-                    print!("<custom type {}>", node.get_id());
-                }
+fn get_file_arg(
+    line: u32,
+    column: u32,
+    arg: &Rc<dyn Variant>,
+    position: &str,
+) -> Result<Rc<dyn Custom>, NativeException> {
+    if arg.get_type() != Type::Custom || arg.as_custom().get_id() != CUSTOM_TYPE_FILE {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!(
+                "{} argument of this function should be `File(file)`",
+                position
+            ),
+        ));
+    }
+
+    Ok(arg.as_custom())
+}
+
+fn with_open_file<T>(
+    line: u32,
+    column: u32,
+    custom: &Rc<dyn Custom>,
+    action: impl FnOnce(&mut OpenFile) -> io::Result<T>,
+) -> Result<T, NativeException> {
+    let file = CustomFile::from_custom(custom.as_ref());
+    let mut file_ref = file.file.borrow_mut();
+
+    if file_ref.is_none() {
+        return Err(NativeException::new(line, column, "File is closed"));
+    }
+
+    let result = action(unsafe { file_ref.as_mut().unwrap_unchecked() });
+
+    if result.is_err() {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("I/O error: {}", unsafe { result.unwrap_err_unchecked() }),
+        ));
+    }
+
+    Ok(unsafe { result.unwrap_unchecked() })
+}
+
+fn bytes_to_str(bytes: &[u8]) -> Str {
+    Str::new(&bytes.iter().map(|byte| *byte as char).collect::<String>())
+}
+
+fn str_to_bytes(text: &str) -> Option<Vec<u8>> {
+    let mut bytes: Vec<u8> = Vec::new();
+
+    for chr in text.chars() {
+        if chr as u32 > 0xFF {
+            return None;
+        }
+
+        bytes.push(chr as u8);
+    }
+
+    Some(bytes)
+}
+
+fn utf8_to_str(line: u32, column: u32, bytes: Vec<u8>) -> Result<Str, NativeException> {
+    let text = String::from_utf8(bytes);
+
+    if text.is_err() {
+        return Err(NativeException::new(
+            line,
+            column,
+            "File content isn't valid UTF-8",
+        ));
+    }
+
+    Ok(Str {
+        text: unsafe { text.unwrap_unchecked() },
+    })
+}
+
+/// Formats value the way `print` outputs it: strings without quotes, lists and maps like literals.
+pub fn to_display_string(value: &Rc<dyn Variant>) -> String {
+    match value.get_type() {
+        Type::Int => value.as_int().number.to_string(),
+        Type::Float => format!("{:?}", value.as_float().number),
+        Type::Bool => value.as_bool().value.to_string(),
+        #[cfg(feature = "bigint")]
+        Type::BigInt => value.as_bigint().to_string(),
+        Type::Str => value.as_str().text,
+        Type::List | Type::Map => format!("{:?}", value),
+        Type::Void => "<null>".to_string(),
+        Type::Func => format!(
+            "<function at address {:#}>",
+            &value.as_func() as *const Function as u64
+        ),
+        Type::Custom => {
+            let node = value.as_custom();
+            let repr = node.repr();
+
+            if repr.is_some() {
+                format!("<custom type {}: {:?}>", node.get_id(), unsafe {
+                    repr.unwrap_unchecked()
+                })
+            } else {
+                format!("<custom type {}>", node.get_id())
             }
         }
     }
+}
+
+/// Same as `to_display_string`, but in deterministic mode doesn't print address of function,
+/// because it differs between runs.
+fn to_scope_display_string(scope: &Scope, value: &Rc<dyn Variant>) -> String {
+    if value.get_type() == Type::Func && scope.get_engine().is_deterministic() {
+        return "<function>".to_string();
+    }
+
+    to_display_string(value)
+}
+
+native_function!(print, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::Stdio);
+
+    for arg in args {
+        print!("{}", to_scope_display_string(scope, &arg));
+    }
 
     Ok(Rc::new(Void::new()))
 });
 
-native_function!(flush_stdout, line, column, _scope, args, {
+native_function!(flush_stdout, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::Stdio);
+
     if !args.is_empty() {
         return Err(NativeException::new(
             line,
@@ -122,43 +524,20 @@ native_function!(flush_stdout, line, column, _scope, args, {
     Ok(Rc::new(Void::new()))
 });
 
-native_function!(printerr, _line, _column, _scope, args, {
+native_function!(printerr, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::Stdio);
+
     for arg in args {
-        match arg.get_type() {
-            Type::Int => {
-                eprint!("{}", arg.as_int().number);
-            }
-            Type::Str => {
-                eprint!("{}", arg.as_str().text);
-            }
-            Type::Void => {
-                eprint!("<null>");
-            }
-            Type::Func => {
-                eprint!(
-                    "<function at address {:#}>",
-                    &arg.as_func() as *const Function as u64
-                )
-            }
-            Type::Custom => {
-                let node = arg.as_custom();
-                let repr = node.repr();
-
-                if repr.is_some() {
-                    print!("<custom type {}: {:?}>", node.get_id(), unsafe {
-                        repr.unwrap_unchecked()
-                    });
-                } else {
-                    print!("<custom type {}>", node.get_id());
-                }
-            }
-        }
+        eprint!("{}", to_scope_display_string(scope, &arg));
     }
 
     Ok(Rc::new(Void::new()))
 });
 
-native_function!(input, line, column, _scope, args, {
+native_function!(input, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::Stdio);
+    require_deterministic!(line, column, scope, "input");
+
     if !args.is_empty() {
         return Err(NativeException::new(
             line,
@@ -181,7 +560,7 @@ native_function!(input, line, column, _scope, args, {
     Err(NativeException::new(line, column, "I/O error"))
 });
 
-native_function!(fopen, line, column, _scope, args, {
+native_function!(fopen, line, column, scope, args, {
     if args.len() != 2 {
         return Err(NativeException::new(
             line,
@@ -207,37 +586,47 @@ native_function!(fopen, line, column, _scope, args, {
     }
 
     let path = args[0].as_str().text;
-    let cstr_res = CString::new(path);
+    let mode = args[1].as_str().text;
+
+    if mode.contains('r') || mode.contains('+') {
+        require_permission!(line, column, scope, Permission::FileRead);
+    }
 
-    if cstr_res.is_err() {
+    if mode.contains('w') || mode.contains('a') || mode.contains('+') {
+        require_permission!(line, column, scope, Permission::FileWrite);
+    }
+
+    require_path_permission!(line, column, scope, &path);
+    require_deterministic_file_system!(line, column, scope, "fopen");
+    let open_mode = OpenMode::parse(&mode);
+
+    if open_mode.is_none() {
         return Err(NativeException::new(
             line,
             column,
-            "Error when creating path string",
+            &format!("Invalid file mode '{}'", mode),
         ));
     }
 
-    let cstr = unsafe { cstr_res.unwrap_unchecked() };
-    let str_ptr = cstr.into_raw();
-    let cstr2_res = CString::new(args[1].as_str().text);
+    let file_system = scope.get_engine().get_file_system();
+    let file = file_system.open(&path, unsafe { open_mode.unwrap_unchecked() });
 
-    if cstr2_res.is_err() {
+    if file.is_err() {
         return Err(NativeException::new(
             line,
             column,
-            "Error when creating path string",
+            &format!("I/O error: {}", unsafe { file.unwrap_err_unchecked() }),
         ));
     }
 
-    let cstr2 = unsafe { cstr2_res.unwrap_unchecked() };
-    let str_ptr2 = cstr2.into_raw();
-    let file: *mut libc::FILE = unsafe { libc::fopen(str_ptr, str_ptr2) };
-    let _ = unsafe { CString::from_raw(str_ptr) };
-    let _ = unsafe { CString::from_raw(str_ptr2) };
-    Ok(Rc::new(CustomFile::new_rc(file)))
+    Ok(Rc::new(CustomFile::new_rc(unsafe {
+        file.unwrap_unchecked()
+    })))
 });
 
-native_function!(fread, line, column, _scope, args, {
+native_function!(fread, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::FileRead);
+
     if args.len() != 1 {
         return Err(NativeException::new(
             line,
@@ -246,47 +635,121 @@ native_function!(fread, line, column, _scope, args, {
         ));
     }
 
-    if args[0].get_type() != Type::Custom {
+    let file_result = get_file_arg(line, column, &args[0], "First");
+
+    if file_result.is_err() {
+        return Err(unsafe { file_result.unwrap_err_unchecked() });
+    }
+
+    let bytes = with_open_file(
+        line,
+        column,
+        &unsafe { file_result.unwrap_unchecked() },
+        |file| {
+            file.seek(SeekFrom::Start(0))?;
+            file.read_to_end()
+        },
+    );
+
+    if bytes.is_err() {
+        return Err(unsafe { bytes.unwrap_err_unchecked() });
+    }
+
+    let text = utf8_to_str(line, column, unsafe { bytes.unwrap_unchecked() });
+
+    if text.is_err() {
+        return Err(unsafe { text.unwrap_err_unchecked() });
+    }
+
+    Ok(Rc::new(unsafe { text.unwrap_unchecked() }))
+});
+
+native_function!(fread_line, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::FileRead);
+
+    if args.len() != 1 {
         return Err(NativeException::new(
             line,
             column,
-            "First argument of this function should be `File(file)`",
+            &format!("This function takes 1 argument, {} given", args.len()),
         ));
     }
 
-    let custom = args[0].as_custom();
+    let file_result = get_file_arg(line, column, &args[0], "First");
+
+    if file_result.is_err() {
+        return Err(unsafe { file_result.unwrap_err_unchecked() });
+    }
+
+    let line_result = with_open_file(
+        line,
+        column,
+        &unsafe { file_result.unwrap_unchecked() },
+        |file| file.read_line(),
+    );
+
+    if line_result.is_err() {
+        return Err(unsafe { line_result.unwrap_err_unchecked() });
+    }
+
+    let bytes = unsafe { line_result.unwrap_unchecked() };
+
+    if bytes.is_none() {
+        return Ok(Rc::new(Void::new()));
+    }
+
+    let text = utf8_to_str(line, column, unsafe { bytes.unwrap_unchecked() });
+
+    if text.is_err() {
+        return Err(unsafe { text.unwrap_err_unchecked() });
+    }
+
+    Ok(Rc::new(unsafe { text.unwrap_unchecked() }))
+});
+
+native_function!(fread_bytes, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::FileRead);
 
-    if custom.get_id() != CUSTOM_TYPE_FILE {
+    if args.len() != 2 {
         return Err(NativeException::new(
             line,
             column,
-            "First argument should be `File(file)`",
+            &format!("This function takes 2 arguments, {} given", args.len()),
         ));
     }
 
-    let file: CustomFile = CustomFile::from_custom(custom.as_ref());
-    unsafe { libc::fseek(file.get_file(), 0, libc::SEEK_END) };
-    let file_len = unsafe { libc::ftell(file.get_file()) } as usize;
-    unsafe { libc::rewind(file.get_file()) };
-    let buffer = unsafe { libc::malloc(file_len + 1) };
-    unsafe { libc::fread(buffer, file_len, 1, file.get_file()) };
-    unsafe { (buffer as *mut i8).add(file_len).write(0) };
-    let file_result = unsafe { CStr::from_ptr(buffer as *const i8).to_str() };
+    let file_result = get_file_arg(line, column, &args[0], "First");
 
     if file_result.is_err() {
-        let error = NativeException::new(line, column, &unsafe {
-            file_result.unwrap_err_unchecked().to_string()
-        });
-        unsafe { libc::free(buffer) };
-        return Err(error);
+        return Err(unsafe { file_result.unwrap_err_unchecked() });
+    }
+
+    if args[1].get_type() != Type::Int || args[1].as_int().number < 0 {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Second argument of this function should be non-negative `Int(count)`",
+        ));
     }
 
-    let result = Rc::new(Str::new(unsafe { file_result.unwrap_unchecked() }));
-    unsafe { libc::free(buffer) };
-    Ok(result)
+    let count = args[1].as_int().number as usize;
+    let bytes = with_open_file(
+        line,
+        column,
+        &unsafe { file_result.unwrap_unchecked() },
+        |file| file.read_bytes(count),
+    );
+
+    if bytes.is_err() {
+        return Err(unsafe { bytes.unwrap_err_unchecked() });
+    }
+
+    Ok(Rc::new(bytes_to_str(&unsafe { bytes.unwrap_unchecked() })))
 });
 
-native_function!(fwrite, line, column, _scope, args, {
+native_function!(fwrite, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::FileWrite);
+
     if args.len() != 2 {
         return Err(NativeException::new(
             line,
@@ -295,61 +758,223 @@ native_function!(fwrite, line, column, _scope, args, {
         ));
     }
 
-    if args[0].get_type() != Type::Custom {
+    let file_result = get_file_arg(line, column, &args[0], "First");
+
+    if file_result.is_err() {
+        return Err(unsafe { file_result.unwrap_err_unchecked() });
+    }
+
+    if args[1].get_type() != Type::Str {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Second argument of this function should be `Str(data)`",
+        ));
+    }
+
+    let data = args[1].as_str().text;
+    let result = with_open_file(
+        line,
+        column,
+        &unsafe { file_result.unwrap_unchecked() },
+        |file| file.write_all(data.as_bytes()),
+    );
+
+    if result.is_err() {
+        return Err(unsafe { result.unwrap_err_unchecked() });
+    }
+
+    Ok(Rc::new(Void::new()))
+});
+
+native_function!(fwrite_bytes, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::FileWrite);
+
+    if args.len() != 2 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 2 arguments, {} given", args.len()),
+        ));
+    }
+
+    let file_result = get_file_arg(line, column, &args[0], "First");
+
+    if file_result.is_err() {
+        return Err(unsafe { file_result.unwrap_err_unchecked() });
+    }
+
+    if args[1].get_type() != Type::Str {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Second argument of this function should be `Str(bytes)`",
+        ));
+    }
+
+    let bytes = str_to_bytes(&args[1].as_str().text);
+
+    if bytes.is_none() {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Byte string contains character with code greater than 255",
+        ));
+    }
+
+    let data = unsafe { bytes.unwrap_unchecked() };
+    let result = with_open_file(
+        line,
+        column,
+        &unsafe { file_result.unwrap_unchecked() },
+        |file| file.write_all(&data),
+    );
+
+    if result.is_err() {
+        return Err(unsafe { result.unwrap_err_unchecked() });
+    }
+
+    Ok(Rc::new(Void::new()))
+});
+
+native_function!(fseek, line, column, _scope, args, {
+    if args.len() != 2 && args.len() != 3 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 2 or 3 arguments, {} given", args.len()),
+        ));
+    }
+
+    let file_result = get_file_arg(line, column, &args[0], "First");
+
+    if file_result.is_err() {
+        return Err(unsafe { file_result.unwrap_err_unchecked() });
+    }
+
+    if args[1].get_type() != Type::Int {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Second argument of this function should be `Int(offset)`",
+        ));
+    }
+
+    let offset = args[1].as_int().number;
+    let mut origin = "start".to_string();
+
+    if args.len() == 3 {
+        if args[2].get_type() != Type::Str {
+            return Err(NativeException::new(
+                line,
+                column,
+                "Third argument of this function should be `Str(origin)`",
+            ));
+        }
+
+        origin = args[2].as_str().text;
+    }
+
+    let position = match origin.as_str() {
+        "start" => {
+            if offset < 0 {
+                return Err(NativeException::new(
+                    line,
+                    column,
+                    "Offset from start of file can't be negative",
+                ));
+            }
+
+            SeekFrom::Start(offset as u64)
+        }
+        "current" => SeekFrom::Current(offset),
+        "end" => SeekFrom::End(offset),
+        _ => {
+            return Err(NativeException::new(
+                line,
+                column,
+                &format!(
+                    "Invalid origin '{}', expected one of 'start', 'current', 'end'",
+                    origin
+                ),
+            ));
+        }
+    };
+
+    let result = with_open_file(
+        line,
+        column,
+        &unsafe { file_result.unwrap_unchecked() },
+        |file| file.seek(position),
+    );
+
+    if result.is_err() {
+        return Err(unsafe { result.unwrap_err_unchecked() });
+    }
+
+    Ok(Rc::new(Int::new(
+        unsafe { result.unwrap_unchecked() } as i64
+    )))
+});
+
+native_function!(ftell, line, column, _scope, args, {
+    if args.len() != 1 {
         return Err(NativeException::new(
             line,
             column,
-            "First argument of this function should be `File(file)`",
+            &format!("This function takes 1 argument, {} given", args.len()),
         ));
     }
 
-    if args[1].get_type() != Type::Str {
-        return Err(NativeException::new(
-            line,
-            column,
-            "Second argument of this function should be `Str(data)`",
-        ));
+    let file_result = get_file_arg(line, column, &args[0], "First");
+
+    if file_result.is_err() {
+        return Err(unsafe { file_result.unwrap_err_unchecked() });
     }
 
-    let custom = args[0].as_custom();
+    let result = with_open_file(
+        line,
+        column,
+        &unsafe { file_result.unwrap_unchecked() },
+        |file| file.tell(),
+    );
 
-    if custom.get_id() != CUSTOM_TYPE_FILE {
-        return Err(NativeException::new(
-            line,
-            column,
-            "First argument should be `File(path)`",
-        ));
+    if result.is_err() {
+        return Err(unsafe { result.unwrap_err_unchecked() });
     }
 
-    let file: CustomFile = CustomFile::from_custom(custom.as_ref());
-    let cstr_res = CString::new(args[1].as_str().text);
+    Ok(Rc::new(Int::new(
+        unsafe { result.unwrap_unchecked() } as i64
+    )))
+});
 
-    if cstr_res.is_err() {
+native_function!(feof, line, column, _scope, args, {
+    if args.len() != 1 {
         return Err(NativeException::new(
             line,
             column,
-            "Error when creating path string",
+            &format!("This function takes 1 argument, {} given", args.len()),
         ));
     }
 
-    let cstr = unsafe { cstr_res.unwrap_unchecked() };
-    let str_ptr = cstr.into_raw();
-    unsafe {
-        libc::fwrite(
-            str_ptr as *const c_void,
-            libc::strlen(str_ptr),
-            1,
-            file.get_file(),
-        );
-    }
-    let _ = unsafe { CString::from_raw(str_ptr) };
-    /*let file_result = file.file.as_ref().write_all(args[1].as_str().text.as_bytes());
+    let file_result = get_file_arg(line, column, &args[0], "First");
 
     if file_result.is_err() {
-        return Err(NativeException::new(line, column, "I/O error"));
-    }*/
+        return Err(unsafe { file_result.unwrap_err_unchecked() });
+    }
 
-    Ok(Rc::new(Void::new()))
+    let result = with_open_file(
+        line,
+        column,
+        &unsafe { file_result.unwrap_unchecked() },
+        |file| file.is_eof(),
+    );
+
+    if result.is_err() {
+        return Err(unsafe { result.unwrap_err_unchecked() });
+    }
+
+    Ok(Rc::new(Bool::new(unsafe { result.unwrap_unchecked() })))
 });
 
 native_function!(fclose, line, column, _scope, args, {
@@ -361,60 +986,71 @@ native_function!(fclose, line, column, _scope, args, {
         ));
     }
 
-    if args[0].get_type() != Type::Custom {
-        return Err(NativeException::new(
-            line,
-            column,
-            "First argument of this function should be `File(path)`",
-        ));
+    let file_result = get_file_arg(line, column, &args[0], "First");
+
+    if file_result.is_err() {
+        return Err(unsafe { file_result.unwrap_err_unchecked() });
     }
 
-    let custom = args[0].as_custom();
+    let custom = unsafe { file_result.unwrap_unchecked() };
 
-    if custom.get_id() != CUSTOM_TYPE_FILE {
-        return Err(NativeException::new(
-            line,
-            column,
-            "First argument should be `File(path)`",
-        ));
+    if !CustomFile::from_custom(custom.as_ref()).close() {
+        return Err(NativeException::new(line, column, "File is closed"));
     }
 
-    let mut file: CustomFile = CustomFile::from_custom(custom.as_ref());
-    unsafe {
-        libc::fclose(file.get_file());
-    }
-    file.close();
-    //unsafe { ManuallyDrop::<File>::drop(&mut file.file) };
     Ok(Rc::new(Void::new()))
 });
 
-native_function!(parse_int, line, column, _scope, args, {
-    if args.len() != 1 {
+/// Parses integer with optional sign in specified radix, from 2 to 36.
+fn parse_int_radix(line: u32, column: u32, text: &str, radix: i64) -> Result<i64, NativeException> {
+    if !(2..=36).contains(&radix) {
         return Err(NativeException::new(
             line,
             column,
-            &format!("This function takes 1 argument, {} given", args.len()),
+            "Radix should be from 2 to 36",
         ));
     }
 
-    if args[0].get_type() != Type::Str {
+    let result = i64::from_str_radix(text, radix as u32);
+
+    if result.is_err() {
+        return Err(NativeException::new(line, column, "Invalid number string"));
+    }
+
+    Ok(unsafe { result.unwrap_unchecked() })
+}
+
+native_function!(parse_int, line, column, _scope, args, {
+    if args.len() != 1 && args.len() != 2 {
         return Err(NativeException::new(
             line,
             column,
-            "First argument of this function should be `Str(number)`",
+            &format!("This function takes 1 or 2 arguments, {} given", args.len()),
         ));
     }
 
-    let integer = args[0].as_str().text;
-    let parse_result = integer.parse::<i64>();
+    let text = get_str_arg(line, column, &args, 0, "number")?;
+    let radix = if args.len() == 2 {
+        get_int_arg(line, column, &args, 1, "radix")?
+    } else {
+        10
+    };
 
-    if parse_result.is_err() {
+    Ok(Rc::new(Int::new(parse_int_radix(
+        line, column, &text, radix,
+    )?)))
+});
+
+native_function!(parse_float, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let text = get_str_arg(line, column, &args, 0, "number")?;
+    let number = text.trim().parse::<f64>();
+
+    if number.is_err() {
         return Err(NativeException::new(line, column, "Invalid number string"));
     }
 
-    Ok(Rc::new(Int::new(unsafe {
-        parse_result.unwrap_unchecked()
-    })))
+    Ok(Rc::new(Float::new(unsafe { number.unwrap_unchecked() })))
 });
 
 native_function!(lf, line, column, _scope, args, {
@@ -471,6 +1107,10 @@ native_function!(declfunc, line, column, scope, args, {
 });
 
 native_function!(set, line, column, scope, args, {
+    if is_list_call(&args) {
+        return list_set(line, column, scope, args);
+    }
+
     if args.len() != 2 {
         return Err(NativeException::new(
             line,
@@ -522,10 +1162,23 @@ native_function!(null, line, column, scope, args, {
     let var = unsafe { var_result.unwrap_unchecked() };
     let var_type = var.get_type();
 
+    #[cfg(feature = "bigint")]
+    if var_type == Type::BigInt {
+        scope.set_variable(&var_name, Rc::new(BigInt::zero()));
+    }
+
     if var_type == Type::Int {
         scope.set_variable(&var_name, Rc::new(Int::new(0)));
+    } else if var_type == Type::Float {
+        scope.set_variable(&var_name, Rc::new(Float::new(0.0)));
+    } else if var_type == Type::Bool {
+        scope.set_variable(&var_name, Rc::new(Bool::new(false)));
     } else if var_type == Type::Str {
         scope.set_variable(&var_name, Rc::new(Str::new("")));
+    } else if var_type == Type::List {
+        scope.set_variable(&var_name, Rc::new(List::new(Vec::new())));
+    } else if var_type == Type::Map {
+        scope.set_variable(&var_name, Rc::new(Map::new(MapItems::new())));
     } else if var_type == Type::Func {
         scope.set_variable(
             &var_name,
@@ -551,13 +1204,7 @@ native_function!(if_, line, column, scope, args, {
         ));
     }
 
-    if args[0].get_type() != Type::Int {
-        return Err(NativeException::new(
-            line,
-            column,
-            "First argument of this function should be `Int(condition)`",
-        ));
-    }
+    let condition = get_condition_arg(line, column, scope, &args, 0, "condition")?;
 
     if args[1].get_type() != Type::Func {
         return Err(NativeException::new(
@@ -567,7 +1214,7 @@ native_function!(if_, line, column, scope, args, {
         ));
     }
 
-    if args[0].as_int().number == 0 {
+    if !condition {
         return Ok(Rc::new(Void::new()));
     }
 
@@ -610,13 +1257,7 @@ native_function!(if_else, line, column, scope, args, {
         ));
     }
 
-    if args[0].get_type() != Type::Int {
-        return Err(NativeException::new(
-            line,
-            column,
-            "First argument of this function should be `Int(condition)`",
-        ));
-    }
+    let condition = get_condition_arg(line, column, scope, &args, 0, "condition")?;
 
     if args[1].get_type() != Type::Func {
         return Err(NativeException::new(
@@ -634,7 +1275,7 @@ native_function!(if_else, line, column, scope, args, {
         ));
     }
 
-    if args[0].as_int().number == 0 {
+    if !condition {
         let func_body = args[2].as_func().body;
 
         if func_body.is_none() {
@@ -695,73 +1336,34 @@ native_function!(if_else, line, column, scope, args, {
     Ok(Rc::new(Void::new()))
 });
 
-native_function!(add, line, column, _scope, args, {
-    if args.len() != 2 {
-        return Err(NativeException::new(
-            line,
-            column,
-            &format!("This function takes 2 arguments, {} given", args.len()),
-        ));
-    }
-
-    if args[0].get_type() != Type::Int {
-        return Err(NativeException::new(
-            line,
-            column,
-            "First argument of this function should be `Int(a)`",
-        ));
-    }
+native_function!(and, line, column, scope, args, {
+    check_min_args_count(line, column, &args, 2)?;
+    let mut result = true;
 
-    if args[1].get_type() != Type::Int {
-        return Err(NativeException::new(
-            line,
-            column,
-            "Second argument of this function should be `Int(b)`",
-        ));
+    for index in 0..args.len() {
+        result &= get_condition_arg(line, column, scope, &args, index, "condition")?;
     }
 
-    Ok(Rc::new(Int::new(
-        args[0]
-            .as_int()
-            .number
-            .wrapping_add(args[1].as_int().number),
-    )))
+    Ok(Rc::new(Bool::new(result)))
 });
 
-native_function!(subt, line, column, _scope, args, {
-    if args.len() != 2 {
-        return Err(NativeException::new(
-            line,
-            column,
-            &format!("This function takes 2 arguments, {} given", args.len()),
-        ));
-    }
+native_function!(or, line, column, scope, args, {
+    check_min_args_count(line, column, &args, 2)?;
+    let mut result = false;
 
-    if args[0].get_type() != Type::Int {
-        return Err(NativeException::new(
-            line,
-            column,
-            "First argument of this function should be `Int(a)`",
-        ));
+    for index in 0..args.len() {
+        result |= get_condition_arg(line, column, scope, &args, index, "condition")?;
     }
 
-    if args[1].get_type() != Type::Int {
-        return Err(NativeException::new(
-            line,
-            column,
-            "Second argument of this function should be `Int(b)`",
-        ));
-    }
+    Ok(Rc::new(Bool::new(result)))
+});
 
-    Ok(Rc::new(Int::new(
-        args[0]
-            .as_int()
-            .number
-            .wrapping_sub(args[1].as_int().number),
-    )))
+native_function!(to_bool, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    Ok(Rc::new(Bool::new(is_truthy(args[0].as_ref()))))
 });
 
-native_function!(mult, line, column, _scope, args, {
+native_function!(eq, line, column, _scope, args, {
     if args.len() != 2 {
         return Err(NativeException::new(
             line,
@@ -770,31 +1372,18 @@ native_function!(mult, line, column, _scope, args, {
         ));
     }
 
-    if args[0].get_type() != Type::Int {
-        return Err(NativeException::new(
-            line,
-            column,
-            "First argument of this function should be `Int(a)`",
-        ));
-    }
+    let a = &args[0];
+    let b = &args[1];
+    let a_type = a.get_type();
 
-    if args[1].get_type() != Type::Int {
-        return Err(NativeException::new(
-            line,
-            column,
-            "Second argument of this function should be `Int(b)`",
-        ));
+    if a_type != b.get_type() {
+        return Ok(Rc::new(Bool::new(false)));
     }
 
-    Ok(Rc::new(Int::new(
-        args[0]
-            .as_int()
-            .number
-            .wrapping_mul(args[1].as_int().number),
-    )))
+    Ok(Rc::new(Bool::new(a == b)))
 });
 
-native_function!(idiv, line, column, _scope, args, {
+native_function!(neq, line, column, _scope, args, {
     if args.len() != 2 {
         return Err(NativeException::new(
             line,
@@ -803,162 +1392,112 @@ native_function!(idiv, line, column, _scope, args, {
         ));
     }
 
-    if args[0].get_type() != Type::Int {
-        return Err(NativeException::new(
-            line,
-            column,
-            "First argument of this function should be `Int(a)`",
-        ));
-    }
-
-    if args[1].get_type() != Type::Int {
-        return Err(NativeException::new(
-            line,
-            column,
-            "Second argument of this function should be `Int(b)`",
-        ));
-    }
-
-    let b = args[1].as_int().number;
+    let a = &args[0];
+    let b = &args[1];
+    let a_type = a.get_type();
 
-    if b == 0 {
-        return Err(NativeException::new(line, column, "Division by zero"));
+    if a_type != b.get_type() {
+        return Ok(Rc::new(Bool::new(false)));
     }
 
-    Ok(Rc::new(Int::new(args[0].as_int().number / b)))
+    Ok(Rc::new(Bool::new(a != b)))
 });
 
-native_function!(and, line, column, _scope, args, {
-    if args.len() != 2 {
-        return Err(NativeException::new(
-            line,
-            column,
-            &format!("This function takes 2 arguments, {} given", args.len()),
-        ));
-    }
-
-    if args[0].get_type() != Type::Int {
+#[cfg(feature = "parser")]
+native_function!(import, line, column, scope, args, {
+    if args.len() != 1 {
         return Err(NativeException::new(
             line,
             column,
-            "First argument of this function should be `Int(a)`",
+            &format!("This function takes 1 argument, {} given", args.len()),
         ));
     }
 
-    if args[1].get_type() != Type::Int {
+    if args[0].get_type() != Type::Str {
         return Err(NativeException::new(
             line,
             column,
-            "Second argument of this function should be `Int(b)`",
+            "First argument of this function should be `Str(module)`",
         ));
     }
 
-    Ok(Rc::new(Int::new(
-        (args[0].as_int().number != 0 && args[1].as_int().number != 0) as i64,
-    )))
-});
+    require_permission!(line, column, scope, Permission::FileRead);
+    require_deterministic_file_system!(line, column, scope, "import");
+    let resolve_result = resolve_module(&mut scope.get_engine_mut(), &args[0].as_str().text);
 
-native_function!(or, line, column, _scope, args, {
-    if args.len() != 2 {
+    if resolve_result.is_err() {
+        let error = unsafe { resolve_result.unwrap_err_unchecked() };
         return Err(NativeException::new(
             line,
             column,
-            &format!("This function takes 2 arguments, {} given", args.len()),
+            &format!("Module error: {}", error.description),
         ));
     }
 
-    if args[0].get_type() != Type::Int {
-        return Err(NativeException::new(
-            line,
-            column,
-            "First argument of this function should be `Int(a)`",
-        ));
-    }
+    let path = unsafe { resolve_result.unwrap_unchecked() };
+    require_path_permission!(line, column, scope, &path);
+    let file_system = scope.get_engine().get_file_system();
+    let canonical_path = file_system.canonicalize(&path).unwrap_or(path.clone());
 
-    if args[1].get_type() != Type::Int {
-        return Err(NativeException::new(
-            line,
-            column,
-            "Second argument of this function should be `Int(b)`",
-        ));
+    if scope.get_engine().is_module_loaded(&canonical_path) {
+        return Ok(Rc::new(Void::new()));
     }
 
-    Ok(Rc::new(Int::new(
-        (args[0].as_int().number != 0 || args[1].as_int().number != 0) as i64,
-    )))
-});
+    let code = file_system.read_to_string(&path);
 
-native_function!(eq, line, column, _scope, args, {
-    if args.len() != 2 {
+    if code.is_err() {
         return Err(NativeException::new(
             line,
             column,
-            &format!("This function takes 2 arguments, {} given", args.len()),
+            &format!("Module error: {}: {}", path, unsafe {
+                code.unwrap_err_unchecked()
+            }),
         ));
     }
 
-    let a = &args[0];
-    let b = &args[1];
-    let a_type = a.get_type();
-
-    if a_type != b.get_type() {
-        return Ok(Rc::new(Int::new(0)));
-    }
-
-    Ok(Rc::new(Int::new((a == b) as i64)))
-});
+    let parse_result = parse(&unsafe { code.unwrap_unchecked() });
 
-native_function!(neq, line, column, _scope, args, {
-    if args.len() != 2 {
+    if parse_result.is_err() {
+        let error = unsafe { parse_result.unwrap_err_unchecked() };
         return Err(NativeException::new(
             line,
             column,
-            &format!("This function takes 2 arguments, {} given", args.len()),
+            &format!(
+                "Module error: {}: Error on line {} column {}: {}",
+                path, error.line, error.column, error.description
+            ),
         ));
     }
 
-    let a = &args[0];
-    let b = &args[1];
-    let a_type = a.get_type();
-
-    if a_type != b.get_type() {
-        return Ok(Rc::new(Int::new(0)));
-    }
-
-    Ok(Rc::new(Int::new((a != b) as i64)))
-});
-
-native_function!(exit, line, column, _scope, args, {
-    let exit_code: i32;
-    let args_len = args.len();
-
-    if args_len == 0 {
-        exit_code = 0i32;
-    } else if args_len == 1 {
-        if args[0].get_type() != Type::Int {
-            return Err(NativeException::new(
-                line,
-                column,
-                "First argument of this function should be `Int(code)`",
-            ));
-        }
+    // Module is marked loaded while it runs, so circular imports are skipped.
+    // It's unmarked on failure, so importing it again runs it again.
+    scope.get_engine_mut().set_module_loaded(&canonical_path);
+    let result = execute_frame(scope, Some(MODULE_FRAME), Some(&path), &unsafe {
+        parse_result.unwrap_unchecked()
+    });
 
-        exit_code = args[0].as_int().number as i32;
-    } else {
+    if result.is_some() {
+        scope.get_engine_mut().unset_module_loaded(&canonical_path);
+        let (error_line, error_column, description) = match unsafe { result.unwrap_unchecked() } {
+            Ok(error) => (error.line, error.column, error.description),
+            Err(error) => (error.line, error.column, error.description),
+        };
         return Err(NativeException::new(
             line,
             column,
             &format!(
-                "This function takes at most 1 argument, {} given",
-                args.len()
+                "Module error: {}: Error on line {} column {}: {}",
+                path, error_line, error_column, description
             ),
         ));
     }
 
-    process::exit(exit_code)
+    Ok(Rc::new(Void::new()))
 });
 
 native_function!(inspect_scope, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::Debug);
+
     if !args.is_empty() {
         return Err(NativeException::new(
             line,
@@ -973,8 +1512,15 @@ native_function!(inspect_scope, line, column, scope, args, {
         println!("Variable {} = {:?}", variable.0, variable.1);
     }
 
-    for function in scope.get_functions() {
-        println!("Function {} = {:?}", function.0, function.1);
+    let deterministic = scope.get_engine().is_deterministic();
+
+    for (name, function) in scope.get_functions() {
+        // Addresses of native functions differ between runs, so only bodies are printed.
+        match &function.body {
+            Some(body) if deterministic => println!("Function {} = {:?}", name, body),
+            None if deterministic => println!("Function {} = <native>", name),
+            _ => println!("Function {} = {:?}", name, function),
+        }
     }
 
     println!("End of inspection");
@@ -1001,32 +1547,9 @@ pub fn add_input(scope: &mut Scope) {
     scope.set_function("input", func);
 }
 
-pub fn destructor_close_files(scope: &mut Scope) {
-    for variable in scope.get_variables() {
-        let var = variable.1;
-
-        if var.get_type() == Type::Custom {
-            let custom = var.as_custom();
-
-            if custom.get_id() == CUSTOM_TYPE_FILE {
-                let mut file: CustomFile = CustomFile::from_custom(custom.as_ref());
-
-                if !file.is_closed() {
-                    println!("Closed unclosed file");
-                    unsafe {
-                        libc::fclose(file.get_file());
-                    }
-                    file.close();
-                }
-            }
-        }
-    }
-}
-
 pub fn add_fopen(scope: &mut Scope) {
     let func = Function::new_native(fopen);
     scope.set_function("fopen", func);
-    add_cleanup_destructor(destructor_close_files);
 }
 
 pub fn add_fread(scope: &mut Scope) {
@@ -1034,11 +1557,41 @@ pub fn add_fread(scope: &mut Scope) {
     scope.set_function("fread", func);
 }
 
+pub fn add_fread_line(scope: &mut Scope) {
+    let func = Function::new_native(fread_line);
+    scope.set_function("fread_line", func);
+}
+
+pub fn add_fread_bytes(scope: &mut Scope) {
+    let func = Function::new_native(fread_bytes);
+    scope.set_function("fread_bytes", func);
+}
+
 pub fn add_fwrite(scope: &mut Scope) {
     let func = Function::new_native(fwrite);
     scope.set_function("fwrite", func);
 }
 
+pub fn add_fwrite_bytes(scope: &mut Scope) {
+    let func = Function::new_native(fwrite_bytes);
+    scope.set_function("fwrite_bytes", func);
+}
+
+pub fn add_fseek(scope: &mut Scope) {
+    let func = Function::new_native(fseek);
+    scope.set_function("fseek", func);
+}
+
+pub fn add_ftell(scope: &mut Scope) {
+    let func = Function::new_native(ftell);
+    scope.set_function("ftell", func);
+}
+
+pub fn add_feof(scope: &mut Scope) {
+    let func = Function::new_native(feof);
+    scope.set_function("feof", func);
+}
+
 pub fn add_fclose(scope: &mut Scope) {
     let func = Function::new_native(fclose);
     scope.set_function("fclose", func);
@@ -1049,6 +1602,11 @@ pub fn add_parse_int(scope: &mut Scope) {
     scope.set_function("parse_int", func);
 }
 
+pub fn add_parse_float(scope: &mut Scope) {
+    let func = Function::new_native(parse_float);
+    scope.set_function("parse_float", func);
+}
+
 pub fn add_lf(scope: &mut Scope) {
     let func = Function::new_native(lf);
     scope.set_function("lf", func);
@@ -1084,26 +1642,6 @@ pub fn add_if_else(scope: &mut Scope) {
     scope.set_function("if_else", func);
 }
 
-pub fn add_add(scope: &mut Scope) {
-    let func = Function::new_native(add);
-    scope.set_function("add", func);
-}
-
-pub fn add_subt(scope: &mut Scope) {
-    let func = Function::new_native(subt);
-    scope.set_function("subt", func);
-}
-
-pub fn add_mult(scope: &mut Scope) {
-    let func = Function::new_native(mult);
-    scope.set_function("mult", func);
-}
-
-pub fn add_idiv(scope: &mut Scope) {
-    let func = Function::new_native(idiv);
-    scope.set_function("idiv", func);
-}
-
 pub fn add_and(scope: &mut Scope) {
     let func = Function::new_native(and);
     scope.set_function("and", func);
@@ -1114,6 +1652,11 @@ pub fn add_or(scope: &mut Scope) {
     scope.set_function("or", func);
 }
 
+pub fn add_to_bool(scope: &mut Scope) {
+    let func = Function::new_native(to_bool);
+    scope.set_function("to_bool", func);
+}
+
 pub fn add_eq(scope: &mut Scope) {
     let func = Function::new_native(eq);
     scope.set_function("eq", func);
@@ -1124,11 +1667,15 @@ pub fn add_neq(scope: &mut Scope) {
     scope.set_function("neq", func);
 }
 
-pub fn add_exit(scope: &mut Scope) {
-    let func = Function::new_native(exit);
-    scope.set_function("exit", func);
+#[cfg(feature = "parser")]
+pub fn add_import(scope: &mut Scope) {
+    let func = Function::new_native(import);
+    scope.set_function("import", func);
 }
 
+#[cfg(not(feature = "parser"))]
+pub fn add_import(_scope: &mut Scope) {}
+
 pub fn add_inspect_scope(scope: &mut Scope) {
     let func = Function::new_native(inspect_scope);
     scope.set_function("inspect_scope", func);
@@ -1144,24 +1691,34 @@ pub fn add_stdio(scope: &mut Scope) {
 pub fn add_file_io(scope: &mut Scope) {
     add_fopen(scope);
     add_fread(scope);
+    add_fread_line(scope);
+    add_fread_bytes(scope);
     add_fwrite(scope);
+    add_fwrite_bytes(scope);
+    add_fseek(scope);
+    add_ftell(scope);
+    add_feof(scope);
     add_fclose(scope);
 }
 
 pub fn add_io(scope: &mut Scope) {
     add_stdio(scope);
     add_file_io(scope);
+    add_fs(scope);
 }
 
 pub fn add_string(scope: &mut Scope) {
     add_parse_int(scope);
+    add_parse_float(scope);
     add_lf(scope);
     add_cr(scope);
+    add_string_functions(scope);
+    add_format(scope);
 }
 
 pub fn add_vars(scope: &mut Scope) {
-    scope.set_variable("true", Rc::new(Int::new(1)));
-    scope.set_variable("false", Rc::new(Int::new(0)));
+    scope.set_variable("true", Rc::new(Bool::new(true)));
+    scope.set_variable("false", Rc::new(Bool::new(false)));
 }
 
 pub fn add_core(scope: &mut Scope) {
@@ -1170,25 +1727,36 @@ pub fn add_core(scope: &mut Scope) {
     add_null(scope);
     add_if(scope);
     add_if_else(scope);
-    add_add(scope);
-    add_subt(scope);
-    add_mult(scope);
-    add_idiv(scope);
     add_and(scope);
     add_or(scope);
+    add_to_bool(scope);
     add_eq(scope);
     add_neq(scope);
+    add_math(scope);
     add_exit(scope);
     add_vars(scope);
 }
 
+pub fn add_modules(scope: &mut Scope) {
+    add_import(scope);
+}
+
 pub fn add_debug(scope: &mut Scope) {
     add_inspect_scope(scope);
 }
 
 pub fn add_stdlib(scope: &mut Scope) {
     add_io(scope);
+    add_env(scope);
     add_string(scope);
+    add_list(scope);
+    add_map(scope);
+    add_convert(scope);
+    add_json(scope);
+    add_random(scope);
+    add_time(scope);
+    add_process(scope);
     add_core(scope);
+    add_modules(scope);
     add_debug(scope);
 }
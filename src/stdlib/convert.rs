@@ -0,0 +1,116 @@
+use std::rc::Rc;
+
+use super::{check_args_count, parse_int_radix, to_scope_display_string};
+use crate::types::{Bool, Function, Int, NativeException, Scope, Str, Type, Variant};
+
+native_function!(type_of, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    Ok(Rc::new(Str::new(&format!("{:?}", args[0].get_type()))))
+});
+
+macro_rules! type_check {
+    ($name: ident, $type: expr) => {
+        native_function!($name, line, column, _scope, args, {
+            check_args_count(line, column, &args, 1)?;
+            Ok(Rc::new(Bool::new(args[0].get_type() == $type)))
+        });
+    };
+}
+
+type_check!(is_void, Type::Void);
+type_check!(is_int, Type::Int);
+type_check!(is_float, Type::Float);
+type_check!(is_bool, Type::Bool);
+type_check!(is_str, Type::Str);
+type_check!(is_list, Type::List);
+type_check!(is_map, Type::Map);
+type_check!(is_func, Type::Func);
+type_check!(is_custom, Type::Custom);
+
+native_function!(to_str, line, column, scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    Ok(Rc::new(Str::new(&to_scope_display_string(scope, &args[0]))))
+});
+
+native_function!(to_int, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+
+    let number = match args[0].get_type() {
+        Type::Int => return Ok(args[0].clone()),
+        Type::Bool => args[0].as_bool().value as i64,
+        Type::Str => parse_int_radix(line, column, &args[0].as_str().text, 10)?,
+        Type::Float => {
+            let number = args[0].as_float().number.trunc();
+
+            // `i64::MAX as f64` is rounded up to 2^63, so it's out of range too.
+            if number.is_nan() || number < i64::MIN as f64 || number >= i64::MAX as f64 {
+                return Err(NativeException::new(
+                    line,
+                    column,
+                    "Number is NaN or out of `Int` range",
+                ));
+            }
+
+            number as i64
+        }
+        #[cfg(feature = "bigint")]
+        Type::BigInt => {
+            let number = args[0].as_bigint().to_i64();
+
+            if number.is_none() {
+                return Err(NativeException::new(
+                    line,
+                    column,
+                    "Number is NaN or out of `Int` range",
+                ));
+            }
+
+            unsafe { number.unwrap_unchecked() }
+        }
+        _ => {
+            return Err(NativeException::new(
+                line,
+                column,
+                "First argument of this function should be `Int(value)`, `Float(value)`, `Bool(value)` or `Str(value)`",
+            ));
+        }
+    };
+
+    Ok(Rc::new(Int::new(number)))
+});
+
+pub fn add_type_of(scope: &mut Scope) {
+    let func = Function::new_native(type_of);
+    scope.set_function("type_of", func);
+}
+
+pub fn add_type_checks(scope: &mut Scope) {
+    scope.set_function("is_void", Function::new_native(is_void));
+    scope.set_function("is_int", Function::new_native(is_int));
+    scope.set_function("is_float", Function::new_native(is_float));
+    scope.set_function("is_bool", Function::new_native(is_bool));
+    scope.set_function("is_str", Function::new_native(is_str));
+    scope.set_function("is_list", Function::new_native(is_list));
+    scope.set_function("is_map", Function::new_native(is_map));
+    scope.set_function("is_func", Function::new_native(is_func));
+    scope.set_function("is_custom", Function::new_native(is_custom));
+}
+
+pub fn add_to_str(scope: &mut Scope) {
+    let func = Function::new_native(to_str);
+    scope.set_function("to_str", func);
+}
+
+pub fn add_to_int(scope: &mut Scope) {
+    let func = Function::new_native(to_int);
+    scope.set_function("to_int", func);
+}
+
+/// Adds type introspection and conversion functions. `to_bool` is added by core group,
+/// `parse_int` and `parse_float` by string group.
+pub fn add_convert(scope: &mut Scope) {
+    add_type_of(scope);
+    add_type_checks(scope);
+    add_to_str(scope);
+    add_to_int(scope);
+}
@@ -0,0 +1,100 @@
+use std::env;
+use std::rc::Rc;
+
+use super::{check_args_count, get_int_arg, get_str_arg};
+use crate::{
+    engine::Permission,
+    types::{Function, Int, Map, MapItems, MapKey, NativeException, Scope, Str, Variant, Void},
+};
+
+native_function!(argc, line, column, scope, args, {
+    check_args_count(line, column, &args, 0)?;
+    let count = scope.get_engine().get_script_args().len();
+    Ok(Rc::new(Int::new(count as i64)))
+});
+
+native_function!(argv, line, column, scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let index = get_int_arg(line, column, &args, 0, "index")?;
+    let engine = scope.get_engine();
+    let script_args = engine.get_script_args();
+
+    if index < 0 || index as usize >= script_args.len() {
+        return Err(NativeException::new(line, column, "Index out of range"));
+    }
+
+    Ok(Rc::new(Str::new(&script_args[index as usize])))
+});
+
+native_function!(env_get, line, column, scope, args, {
+    if args.len() != 1 && args.len() != 2 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 1 or 2 arguments, {} given", args.len()),
+        ));
+    }
+
+    require_permission!(line, column, scope, Permission::Env);
+    require_deterministic!(line, column, scope, "env_get");
+    let name = get_str_arg(line, column, &args, 0, "name")?;
+
+    // Variables with invalid names or non-Unicode values are treated as missing.
+    let value = if name.is_empty() || name.contains(['=', '\0']) {
+        None
+    } else {
+        env::var(&name).ok()
+    };
+
+    match value {
+        Some(value) => Ok(Rc::new(Str::new(&value))),
+        None if args.len() == 2 => Ok(args[1].clone()),
+        None => Ok(Rc::new(Void::new())),
+    }
+});
+
+native_function!(env_vars, line, column, scope, args, {
+    check_args_count(line, column, &args, 0)?;
+    require_permission!(line, column, scope, Permission::Env);
+    require_deterministic!(line, column, scope, "env_vars");
+    let mut vars: Vec<(String, String)> = env::vars_os()
+        .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+        .collect();
+    vars.sort();
+    let mut items = MapItems::new();
+
+    for (name, value) in vars {
+        items.insert(MapKey::Str(name), Rc::new(Str::new(&value)));
+    }
+
+    Ok(Rc::new(Map::new(items)))
+});
+
+pub fn add_argc(scope: &mut Scope) {
+    let func = Function::new_native(argc);
+    scope.set_function("argc", func);
+}
+
+pub fn add_argv(scope: &mut Scope) {
+    let func = Function::new_native(argv);
+    scope.set_function("argv", func);
+}
+
+pub fn add_env_get(scope: &mut Scope) {
+    let func = Function::new_native(env_get);
+    scope.set_function("env_get", func);
+}
+
+pub fn add_env_vars(scope: &mut Scope) {
+    let func = Function::new_native(env_vars);
+    scope.set_function("env_vars", func);
+}
+
+/// Adds functions for script arguments, that are set by host with `Engine::set_script_args`,
+/// and environment variables.
+pub fn add_env(scope: &mut Scope) {
+    add_argc(scope);
+    add_argv(scope);
+    add_env_get(scope);
+    add_env_vars(scope);
+}
@@ -0,0 +1,388 @@
+use std::rc::Rc;
+
+use super::{check_min_args_count, get_str_arg, to_scope_display_string};
+use crate::types::{Function, MapKey, NativeException, Scope, Str, Type, Variant};
+
+/// Parsed `[[fill]align][+][#][0][width][.precision][type]` part of placeholder.
+struct FormatSpec {
+    fill: char,
+    align: Option<char>,
+    plus: bool,
+    alternate: bool,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+    kind: Option<char>,
+}
+
+fn is_align_char(chr: char) -> bool {
+    chr == '<' || chr == '^' || chr == '>'
+}
+
+/// Maximal width and precision of placeholder.
+static MAX_SPEC_NUMBER: usize = 65535;
+
+/// Reads width or precision. Returns `Err` if number is greater than `MAX_SPEC_NUMBER`.
+fn read_spec_number(chars: &[char], index: &mut usize) -> Result<Option<usize>, ()> {
+    let start = *index;
+
+    while *index < chars.len() && chars[*index].is_ascii_digit() {
+        *index += 1;
+    }
+
+    if start == *index {
+        return Ok(None);
+    }
+
+    let number = chars[start..*index]
+        .iter()
+        .collect::<String>()
+        .parse::<usize>()
+        .ok()
+        .filter(|number| *number <= MAX_SPEC_NUMBER);
+
+    if number.is_none() {
+        return Err(());
+    }
+
+    Ok(number)
+}
+
+fn spec_number_error(line: u32, column: u32) -> NativeException {
+    NativeException::new(
+        line,
+        column,
+        &format!(
+            "Width and precision should not be greater than {}",
+            MAX_SPEC_NUMBER
+        ),
+    )
+}
+
+fn parse_spec(line: u32, column: u32, spec: &str) -> Result<FormatSpec, NativeException> {
+    let chars: Vec<char> = spec.chars().collect();
+    let mut result = FormatSpec {
+        fill: ' ',
+        align: None,
+        plus: false,
+        alternate: false,
+        zero: false,
+        width: 0,
+        precision: None,
+        kind: None,
+    };
+    let mut index = 0;
+
+    if chars.len() >= 2 && is_align_char(chars[1]) {
+        result.fill = chars[0];
+        result.align = Some(chars[1]);
+        index = 2;
+    } else if !chars.is_empty() && is_align_char(chars[0]) {
+        result.align = Some(chars[0]);
+        index = 1;
+    }
+
+    if chars.get(index) == Some(&'+') {
+        result.plus = true;
+        index += 1;
+    }
+
+    if chars.get(index) == Some(&'#') {
+        result.alternate = true;
+        index += 1;
+    }
+
+    if chars.get(index) == Some(&'0') {
+        result.zero = true;
+        index += 1;
+    }
+
+    let width = read_spec_number(&chars, &mut index);
+
+    if width.is_err() {
+        return Err(spec_number_error(line, column));
+    }
+
+    result.width = unsafe { width.unwrap_unchecked() }.unwrap_or(0);
+
+    if chars.get(index) == Some(&'.') {
+        index += 1;
+        let precision = read_spec_number(&chars, &mut index);
+
+        if precision.is_err() {
+            return Err(spec_number_error(line, column));
+        }
+
+        result.precision = unsafe { precision.unwrap_unchecked() };
+
+        if result.precision.is_none() {
+            index = chars.len() + 1;
+        }
+    }
+
+    if index < chars.len() && "xXbo?".contains(chars[index]) {
+        result.kind = Some(chars[index]);
+        index += 1;
+    }
+
+    if index != chars.len() {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("Invalid format specifier '{}'", spec),
+        ));
+    }
+
+    Ok(result)
+}
+
+fn is_number(value: &Rc<dyn Variant>) -> bool {
+    match value.get_type() {
+        Type::Int | Type::Float => true,
+        #[cfg(feature = "bigint")]
+        Type::BigInt => true,
+        _ => false,
+    }
+}
+
+/// Pads `text` to width of `spec`. Zero padding is inserted after sign and radix prefix.
+fn pad(spec: &FormatSpec, prefix: &str, body: &str, is_number: bool) -> String {
+    let len = prefix.chars().count() + body.chars().count();
+
+    if len >= spec.width {
+        return format!("{}{}", prefix, body);
+    }
+
+    let padding = spec.width - len;
+
+    if spec.zero && is_number {
+        return format!("{}{}{}", prefix, "0".repeat(padding), body);
+    }
+
+    let fill = |count: usize| spec.fill.to_string().repeat(count);
+
+    match spec.align.unwrap_or(if is_number { '>' } else { '<' }) {
+        '<' => format!("{}{}{}", prefix, body, fill(padding)),
+        '^' => format!(
+            "{}{}{}{}",
+            fill(padding / 2),
+            prefix,
+            body,
+            fill(padding - padding / 2)
+        ),
+        _ => format!("{}{}{}", fill(padding), prefix, body),
+    }
+}
+
+fn format_value(
+    line: u32,
+    column: u32,
+    scope: &Scope,
+    spec: &FormatSpec,
+    value: &Rc<dyn Variant>,
+) -> Result<String, NativeException> {
+    let value_type = value.get_type();
+
+    if let Some(kind @ ('x' | 'X' | 'b' | 'o')) = spec.kind {
+        if value_type != Type::Int {
+            return Err(NativeException::new(
+                line,
+                column,
+                "Radix format requires `Int` argument",
+            ));
+        }
+
+        let number = value.as_int().number;
+        let magnitude = number.unsigned_abs();
+        let (body, radix_prefix) = match kind {
+            'x' => (format!("{:x}", magnitude), "0x"),
+            'X' => (format!("{:X}", magnitude), "0x"),
+            'b' => (format!("{:b}", magnitude), "0b"),
+            _ => (format!("{:o}", magnitude), "0o"),
+        };
+        let mut prefix = String::new();
+
+        if number < 0 {
+            prefix.push('-');
+        } else if spec.plus {
+            prefix.push('+');
+        }
+
+        if spec.alternate {
+            prefix.push_str(radix_prefix);
+        }
+
+        return Ok(pad(spec, &prefix, &body, true));
+    }
+
+    let mut text = if spec.kind == Some('?') {
+        format!("{:?}", value)
+    } else {
+        to_scope_display_string(scope, value)
+    };
+
+    if spec.precision.is_some() {
+        let precision = unsafe { spec.precision.unwrap_unchecked() };
+
+        match value_type {
+            Type::Float => text = format!("{:.*}", precision, value.as_float().number),
+            Type::Str if spec.kind.is_none() => text = text.chars().take(precision).collect(),
+            _ => {
+                return Err(NativeException::new(
+                    line,
+                    column,
+                    "Precision requires `Float` or `Str` argument",
+                ));
+            }
+        }
+    }
+
+    if !is_number(value) || spec.kind.is_some() {
+        return Ok(pad(spec, "", &text, false));
+    }
+
+    let prefix = if text.starts_with('-') {
+        text.remove(0);
+        "-"
+    } else if spec.plus {
+        "+"
+    } else {
+        ""
+    };
+
+    Ok(pad(spec, prefix, &text, true))
+}
+
+/// Gets value of named placeholder from `Map`, passed as the last argument, or from variable.
+fn get_named_arg(
+    line: u32,
+    column: u32,
+    scope: &Scope,
+    args: &[Rc<dyn Variant>],
+    name: &str,
+) -> Result<Rc<dyn Variant>, NativeException> {
+    let last = args.last();
+
+    if last.is_some_and(|last| last.get_type() == Type::Map) {
+        let map = unsafe { last.unwrap_unchecked() }.as_map();
+        let value = map
+            .items
+            .borrow()
+            .get(&MapKey::Str(name.to_string()))
+            .cloned();
+
+        if value.is_some() {
+            return Ok(unsafe { value.unwrap_unchecked() });
+        }
+    }
+
+    let variable = scope.get_variable(name);
+
+    if variable.is_none() {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("Unknown format argument '{}'", name),
+        ));
+    }
+
+    Ok(unsafe { variable.unwrap_unchecked() }.clone())
+}
+
+/// Formats `template` with `args`. See `format` in StdLib.md for syntax of placeholders.
+pub fn format_template(
+    line: u32,
+    column: u32,
+    scope: &Scope,
+    template: &str,
+    args: &[Rc<dyn Variant>],
+) -> Result<String, NativeException> {
+    let mut result = String::new();
+    let mut chars = template.chars().peekable();
+    let mut next_index = 0;
+
+    while let Some(chr) = chars.next() {
+        if chr == '}' {
+            if chars.next_if_eq(&'}').is_none() {
+                return Err(NativeException::new(
+                    line,
+                    column,
+                    "Unmatched `}` in format string",
+                ));
+            }
+
+            result.push('}');
+            continue;
+        }
+
+        if chr != '{' {
+            result.push(chr);
+            continue;
+        }
+
+        if chars.next_if_eq(&'{').is_some() {
+            result.push('{');
+            continue;
+        }
+
+        let mut placeholder = String::new();
+        let mut is_closed = false;
+
+        for chr in chars.by_ref() {
+            if chr == '}' {
+                is_closed = true;
+                break;
+            }
+
+            placeholder.push(chr);
+        }
+
+        if !is_closed {
+            return Err(NativeException::new(
+                line,
+                column,
+                "Unmatched `{` in format string",
+            ));
+        }
+
+        let (name, spec) = placeholder.split_once(':').unwrap_or((&placeholder, ""));
+        let name = name.trim();
+        let value = if name.is_empty() || name.chars().all(|chr| chr.is_ascii_digit()) {
+            let index = if name.is_empty() {
+                next_index += 1;
+                next_index - 1
+            } else {
+                name.parse::<usize>().unwrap_or(usize::MAX)
+            };
+
+            if index >= args.len() {
+                return Err(NativeException::new(
+                    line,
+                    column,
+                    "Not enough arguments for format string",
+                ));
+            }
+
+            args[index].clone()
+        } else {
+            get_named_arg(line, column, scope, args, name)?
+        };
+
+        let spec = parse_spec(line, column, spec)?;
+        result.push_str(&format_value(line, column, scope, &spec, &value)?);
+    }
+
+    Ok(result)
+}
+
+native_function!(format, line, column, scope, args, {
+    check_min_args_count(line, column, &args, 1)?;
+    let template = get_str_arg(line, column, &args, 0, "template")?;
+    let text = format_template(line, column, scope, &template, &args[1..])?;
+    Ok(Rc::new(Str::new(&text)))
+});
+
+pub fn add_format(scope: &mut Scope) {
+    let func = Function::new_native(format);
+    scope.set_function("format", func);
+}
@@ -0,0 +1,469 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::{
+    engine::Permission,
+    filesystem::FileInfo,
+    types::{Bool, Function, Int, List, NativeException, Scope, Str, Type, Variant, Void},
+};
+
+fn fs_error(line: u32, column: u32, path: &str, error: io::Error) -> NativeException {
+    NativeException::new(line, column, &format!("I/O error: {}: {}", path, error))
+}
+
+fn get_metadata(
+    line: u32,
+    column: u32,
+    scope: &Scope,
+    path: &str,
+) -> Result<FileInfo, NativeException> {
+    let result = scope.get_engine().get_file_system().metadata(path);
+
+    if result.is_err() {
+        return Err(fs_error(line, column, path, unsafe {
+            result.unwrap_err_unchecked()
+        }));
+    }
+
+    Ok(unsafe { result.unwrap_unchecked() })
+}
+
+native_function!(exists, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::FileRead);
+    require_deterministic_file_system!(line, column, scope, "exists");
+    if args.len() != 1 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 1 argument, {} given", args.len()),
+        ));
+    }
+
+    if args[0].get_type() != Type::Str {
+        return Err(NativeException::new(
+            line,
+            column,
+            "First argument of this function should be `Str(path)`",
+        ));
+    }
+
+    let path = args[0].as_str().text;
+    require_path_permission!(line, column, scope, &path);
+    let result = scope.get_engine().get_file_system().metadata(&path);
+    Ok(Rc::new(Bool::new(result.is_ok())))
+});
+
+native_function!(is_dir, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::FileRead);
+    require_deterministic_file_system!(line, column, scope, "is_dir");
+    if args.len() != 1 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 1 argument, {} given", args.len()),
+        ));
+    }
+
+    if args[0].get_type() != Type::Str {
+        return Err(NativeException::new(
+            line,
+            column,
+            "First argument of this function should be `Str(path)`",
+        ));
+    }
+
+    let path = args[0].as_str().text;
+    require_path_permission!(line, column, scope, &path);
+    let result = scope.get_engine().get_file_system().metadata(&path);
+    Ok(Rc::new(Bool::new(
+        result.map(|info| info.is_dir).unwrap_or(false),
+    )))
+});
+
+native_function!(list_dir, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::FileRead);
+    require_deterministic_file_system!(line, column, scope, "list_dir");
+    if args.len() != 1 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 1 argument, {} given", args.len()),
+        ));
+    }
+
+    if args[0].get_type() != Type::Str {
+        return Err(NativeException::new(
+            line,
+            column,
+            "First argument of this function should be `Str(path)`",
+        ));
+    }
+
+    let path = args[0].as_str().text;
+    require_path_permission!(line, column, scope, &path);
+    let result = scope.get_engine().get_file_system().list_dir(&path);
+
+    if result.is_err() {
+        return Err(fs_error(line, column, &path, unsafe {
+            result.unwrap_err_unchecked()
+        }));
+    }
+
+    let names: Vec<Rc<dyn Variant>> = unsafe { result.unwrap_unchecked() }
+        .iter()
+        .map(|name| Rc::new(Str::new(name)) as Rc<dyn Variant>)
+        .collect();
+    Ok(Rc::new(List::new(names)))
+});
+
+native_function!(mkdir, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::FileWrite);
+    require_deterministic_file_system!(line, column, scope, "mkdir");
+    if args.len() != 1 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 1 argument, {} given", args.len()),
+        ));
+    }
+
+    if args[0].get_type() != Type::Str {
+        return Err(NativeException::new(
+            line,
+            column,
+            "First argument of this function should be `Str(path)`",
+        ));
+    }
+
+    let path = args[0].as_str().text;
+    require_path_permission!(line, column, scope, &path);
+    let result = scope.get_engine().get_file_system().create_dir(&path);
+
+    if result.is_err() {
+        return Err(fs_error(line, column, &path, unsafe {
+            result.unwrap_err_unchecked()
+        }));
+    }
+
+    Ok(Rc::new(Void::new()))
+});
+
+native_function!(remove, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::FileWrite);
+    require_deterministic_file_system!(line, column, scope, "remove");
+    if args.len() != 1 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 1 argument, {} given", args.len()),
+        ));
+    }
+
+    if args[0].get_type() != Type::Str {
+        return Err(NativeException::new(
+            line,
+            column,
+            "First argument of this function should be `Str(path)`",
+        ));
+    }
+
+    let path = args[0].as_str().text;
+    require_path_permission!(line, column, scope, &path);
+    let result = scope.get_engine().get_file_system().remove(&path);
+
+    if result.is_err() {
+        return Err(fs_error(line, column, &path, unsafe {
+            result.unwrap_err_unchecked()
+        }));
+    }
+
+    Ok(Rc::new(Void::new()))
+});
+
+native_function!(rename, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::FileWrite);
+    require_deterministic_file_system!(line, column, scope, "rename");
+    if args.len() != 2 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 2 arguments, {} given", args.len()),
+        ));
+    }
+
+    if args[0].get_type() != Type::Str {
+        return Err(NativeException::new(
+            line,
+            column,
+            "First argument of this function should be `Str(from)`",
+        ));
+    }
+
+    if args[1].get_type() != Type::Str {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Second argument of this function should be `Str(to)`",
+        ));
+    }
+
+    let from = args[0].as_str().text;
+    let to = args[1].as_str().text;
+    require_path_permission!(line, column, scope, &from);
+    require_path_permission!(line, column, scope, &to);
+    let result = scope.get_engine().get_file_system().rename(&from, &to);
+
+    if result.is_err() {
+        return Err(fs_error(line, column, &from, unsafe {
+            result.unwrap_err_unchecked()
+        }));
+    }
+
+    Ok(Rc::new(Void::new()))
+});
+
+native_function!(file_size, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::FileRead);
+    require_deterministic_file_system!(line, column, scope, "file_size");
+    if args.len() != 1 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 1 argument, {} given", args.len()),
+        ));
+    }
+
+    if args[0].get_type() != Type::Str {
+        return Err(NativeException::new(
+            line,
+            column,
+            "First argument of this function should be `Str(path)`",
+        ));
+    }
+
+    let path = args[0].as_str().text;
+    require_path_permission!(line, column, scope, &path);
+    let result = get_metadata(line, column, scope, &path);
+
+    if result.is_err() {
+        return Err(unsafe { result.unwrap_err_unchecked() });
+    }
+
+    let info = unsafe { result.unwrap_unchecked() };
+    Ok(Rc::new(Int::new(info.size as i64)))
+});
+
+native_function!(file_mtime, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::FileRead);
+    require_deterministic_file_system!(line, column, scope, "file_mtime");
+    if args.len() != 1 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 1 argument, {} given", args.len()),
+        ));
+    }
+
+    if args[0].get_type() != Type::Str {
+        return Err(NativeException::new(
+            line,
+            column,
+            "First argument of this function should be `Str(path)`",
+        ));
+    }
+
+    let path = args[0].as_str().text;
+    require_path_permission!(line, column, scope, &path);
+    let result = get_metadata(line, column, scope, &path);
+
+    if result.is_err() {
+        return Err(unsafe { result.unwrap_err_unchecked() });
+    }
+
+    let info = unsafe { result.unwrap_unchecked() };
+
+    if info.modified.is_none() {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Modification time is unavailable",
+        ));
+    }
+
+    Ok(Rc::new(Int::new(unsafe {
+        info.modified.unwrap_unchecked()
+    })))
+});
+
+native_function!(path_join, line, column, _scope, args, {
+    let mut path = PathBuf::new();
+
+    for (i, arg) in args.iter().enumerate() {
+        if arg.get_type() != Type::Str {
+            return Err(NativeException::new(
+                line,
+                column,
+                &format!("Argument {} of this function should be `Str(part)`", i + 1),
+            ));
+        }
+
+        path.push(arg.as_str().text);
+    }
+
+    Ok(Rc::new(Str::new(&path.to_string_lossy())))
+});
+
+native_function!(basename, line, column, _scope, args, {
+    if args.len() != 1 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 1 argument, {} given", args.len()),
+        ));
+    }
+
+    if args[0].get_type() != Type::Str {
+        return Err(NativeException::new(
+            line,
+            column,
+            "First argument of this function should be `Str(path)`",
+        ));
+    }
+
+    let path = args[0].as_str().text;
+    let name = Path::new(&path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Ok(Rc::new(Str::new(&name)))
+});
+
+native_function!(dirname, line, column, _scope, args, {
+    if args.len() != 1 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 1 argument, {} given", args.len()),
+        ));
+    }
+
+    if args[0].get_type() != Type::Str {
+        return Err(NativeException::new(
+            line,
+            column,
+            "First argument of this function should be `Str(path)`",
+        ));
+    }
+
+    let path = args[0].as_str().text;
+    let parent = Path::new(&path)
+        .parent()
+        .map(|parent| parent.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Ok(Rc::new(Str::new(&parent)))
+});
+
+native_function!(extension, line, column, _scope, args, {
+    if args.len() != 1 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 1 argument, {} given", args.len()),
+        ));
+    }
+
+    if args[0].get_type() != Type::Str {
+        return Err(NativeException::new(
+            line,
+            column,
+            "First argument of this function should be `Str(path)`",
+        ));
+    }
+
+    let path = args[0].as_str().text;
+    let extension = Path::new(&path)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Ok(Rc::new(Str::new(&extension)))
+});
+
+pub fn add_exists(scope: &mut Scope) {
+    let func = Function::new_native(exists);
+    scope.set_function("exists", func);
+}
+
+pub fn add_is_dir(scope: &mut Scope) {
+    let func = Function::new_native(is_dir);
+    scope.set_function("is_dir", func);
+}
+
+pub fn add_list_dir(scope: &mut Scope) {
+    let func = Function::new_native(list_dir);
+    scope.set_function("list_dir", func);
+}
+
+pub fn add_mkdir(scope: &mut Scope) {
+    let func = Function::new_native(mkdir);
+    scope.set_function("mkdir", func);
+}
+
+pub fn add_remove(scope: &mut Scope) {
+    let func = Function::new_native(remove);
+    scope.set_function("remove", func);
+}
+
+pub fn add_rename(scope: &mut Scope) {
+    let func = Function::new_native(rename);
+    scope.set_function("rename", func);
+}
+
+pub fn add_file_size(scope: &mut Scope) {
+    let func = Function::new_native(file_size);
+    scope.set_function("file_size", func);
+}
+
+pub fn add_file_mtime(scope: &mut Scope) {
+    let func = Function::new_native(file_mtime);
+    scope.set_function("file_mtime", func);
+}
+
+pub fn add_path_join(scope: &mut Scope) {
+    let func = Function::new_native(path_join);
+    scope.set_function("path_join", func);
+}
+
+pub fn add_basename(scope: &mut Scope) {
+    let func = Function::new_native(basename);
+    scope.set_function("basename", func);
+}
+
+pub fn add_dirname(scope: &mut Scope) {
+    let func = Function::new_native(dirname);
+    scope.set_function("dirname", func);
+}
+
+pub fn add_extension(scope: &mut Scope) {
+    let func = Function::new_native(extension);
+    scope.set_function("extension", func);
+}
+
+pub fn add_path(scope: &mut Scope) {
+    add_path_join(scope);
+    add_basename(scope);
+    add_dirname(scope);
+    add_extension(scope);
+}
+
+pub fn add_fs(scope: &mut Scope) {
+    add_exists(scope);
+    add_is_dir(scope);
+    add_list_dir(scope);
+    add_mkdir(scope);
+    add_remove(scope);
+    add_rename(scope);
+    add_file_size(scope);
+    add_file_mtime(scope);
+    add_path(scope);
+}
@@ -0,0 +1,561 @@
+use std::rc::Rc;
+
+use super::{check_args_count, get_int_arg, get_str_arg};
+#[cfg(feature = "bigint")]
+use crate::bigint::BigInt;
+use crate::types::{
+    Bool, Float, Function, Int, List, Map, MapItems, MapKey, NativeException, Scope, Str, Type,
+    Variant, Void,
+};
+
+pub(crate) const MAX_JSON_DEPTH: usize = 512;
+
+/// Parser of JSON text, that tracks line and column for errors.
+struct JsonParser {
+    chars: Vec<char>,
+    index: usize,
+    line: u32,
+    column: u32,
+    depth: usize,
+}
+
+/// Error with position in JSON text.
+struct JsonError {
+    line: u32,
+    column: u32,
+    description: String,
+}
+
+impl JsonParser {
+    fn new(text: &str) -> JsonParser {
+        JsonParser {
+            chars: text.chars().collect(),
+            index: 0,
+            line: 1,
+            column: 1,
+            depth: 0,
+        }
+    }
+
+    fn error(&self, description: &str) -> JsonError {
+        JsonError {
+            line: self.line,
+            column: self.column,
+            description: description.to_string(),
+        }
+    }
+
+    fn unexpected(&self) -> JsonError {
+        match self.peek() {
+            Some(chr) => self.error(&format!("Unexpected character '{}'", chr)),
+            None => self.error("Unexpected end of JSON"),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let chr = self.peek()?;
+        self.index += 1;
+
+        if chr == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        Some(chr)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), JsonError> {
+        if self.peek() != Some(expected) {
+            return Err(self.unexpected());
+        }
+
+        self.next();
+        Ok(())
+    }
+
+    fn parse_document(&mut self) -> Result<Rc<dyn Variant>, JsonError> {
+        let value = self.parse_value()?;
+        self.skip_whitespace();
+
+        if self.peek().is_some() {
+            return Err(self.unexpected());
+        }
+
+        Ok(value)
+    }
+
+    fn parse_value(&mut self) -> Result<Rc<dyn Variant>, JsonError> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some('{') => self.parse_object(),
+            Some('[') => self.parse_array(),
+            Some('"') => Ok(Rc::new(Str::new(&self.parse_string()?))),
+            Some('-' | '0'..='9') => self.parse_number(),
+            Some('t') => self.parse_keyword("true", Rc::new(Bool::new(true))),
+            Some('f') => self.parse_keyword("false", Rc::new(Bool::new(false))),
+            Some('n') => self.parse_keyword("null", Rc::new(Void::new())),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn parse_keyword(
+        &mut self,
+        keyword: &str,
+        value: Rc<dyn Variant>,
+    ) -> Result<Rc<dyn Variant>, JsonError> {
+        for chr in keyword.chars() {
+            self.expect(chr)?;
+        }
+
+        Ok(value)
+    }
+
+    fn enter(&mut self) -> Result<(), JsonError> {
+        self.depth += 1;
+
+        if self.depth > MAX_JSON_DEPTH {
+            return Err(self.error("Nesting is too deep"));
+        }
+
+        Ok(())
+    }
+
+    fn parse_array(&mut self) -> Result<Rc<dyn Variant>, JsonError> {
+        self.enter()?;
+        self.next();
+        let mut items: Vec<Rc<dyn Variant>> = Vec::new();
+        self.skip_whitespace();
+
+        if self.peek() == Some(']') {
+            self.next();
+            self.depth -= 1;
+            return Ok(Rc::new(List::new(items)));
+        }
+
+        loop {
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(',') => {
+                    self.next();
+                }
+                Some(']') => {
+                    self.next();
+                    break;
+                }
+                _ => return Err(self.unexpected()),
+            }
+        }
+
+        self.depth -= 1;
+        Ok(Rc::new(List::new(items)))
+    }
+
+    fn parse_object(&mut self) -> Result<Rc<dyn Variant>, JsonError> {
+        self.enter()?;
+        self.next();
+        let mut items = MapItems::new();
+        self.skip_whitespace();
+
+        if self.peek() == Some('}') {
+            self.next();
+            self.depth -= 1;
+            return Ok(Rc::new(Map::new(items)));
+        }
+
+        loop {
+            self.skip_whitespace();
+
+            if self.peek() != Some('"') {
+                return Err(self.unexpected());
+            }
+
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            let value = self.parse_value()?;
+            items.insert(MapKey::Str(key), value);
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(',') => {
+                    self.next();
+                }
+                Some('}') => {
+                    self.next();
+                    break;
+                }
+                _ => return Err(self.unexpected()),
+            }
+        }
+
+        self.depth -= 1;
+        Ok(Rc::new(Map::new(items)))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, JsonError> {
+        let mut code = 0;
+
+        for _ in 0..4 {
+            let digit = self.peek().and_then(|chr| chr.to_digit(16));
+
+            if digit.is_none() {
+                return Err(self.error("Invalid unicode escape"));
+            }
+
+            self.next();
+            code = code * 16 + unsafe { digit.unwrap_unchecked() };
+        }
+
+        Ok(code)
+    }
+
+    fn parse_unicode_escape(&mut self) -> Result<char, JsonError> {
+        let code = self.parse_hex4()?;
+
+        if !(0xD800..0xDC00).contains(&code) {
+            return char::from_u32(code).ok_or_else(|| self.error("Invalid unicode escape"));
+        }
+
+        // High surrogate should be followed by `\uXXXX` with low surrogate.
+        if self.next() != Some('\\') || self.next() != Some('u') {
+            return Err(self.error("Invalid unicode escape"));
+        }
+
+        let low = self.parse_hex4()?;
+
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error("Invalid unicode escape"));
+        }
+
+        char::from_u32(0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00))
+            .ok_or_else(|| self.error("Invalid unicode escape"))
+    }
+
+    fn parse_string(&mut self) -> Result<String, JsonError> {
+        self.next();
+        let mut result = String::new();
+
+        loop {
+            let chr = self.peek();
+
+            if chr.is_none() {
+                return Err(self.error("Unterminated string"));
+            }
+
+            let chr = unsafe { chr.unwrap_unchecked() };
+
+            if chr < ' ' {
+                return Err(self.error("Control character in string"));
+            }
+
+            self.next();
+
+            match chr {
+                '"' => return Ok(result),
+                '\\' => {
+                    let escape = match self.peek() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => {
+                            self.next();
+                            result.push(self.parse_unicode_escape()?);
+                            continue;
+                        }
+                        _ => return Err(self.error("Invalid escape sequence")),
+                    };
+                    self.next();
+                    result.push(escape);
+                }
+                _ => result.push(chr),
+            }
+        }
+    }
+
+    fn read_digits(&mut self, text: &mut String) -> Result<(), JsonError> {
+        if !self.peek().is_some_and(|chr| chr.is_ascii_digit()) {
+            return Err(self.unexpected());
+        }
+
+        while let Some(chr @ '0'..='9') = self.peek() {
+            text.push(chr);
+            self.next();
+        }
+
+        Ok(())
+    }
+
+    fn parse_number(&mut self) -> Result<Rc<dyn Variant>, JsonError> {
+        let (line, column) = (self.line, self.column);
+        let mut text = String::new();
+        let mut is_float = false;
+
+        if self.peek() == Some('-') {
+            text.push('-');
+            self.next();
+        }
+
+        if self.peek() == Some('0') {
+            text.push('0');
+            self.next();
+        } else {
+            self.read_digits(&mut text)?;
+        }
+
+        if self.peek() == Some('.') {
+            is_float = true;
+            text.push('.');
+            self.next();
+            self.read_digits(&mut text)?;
+        }
+
+        if matches!(self.peek(), Some('e' | 'E')) {
+            is_float = true;
+            text.push('e');
+            self.next();
+
+            if let Some(sign @ ('+' | '-')) = self.peek() {
+                text.push(sign);
+                self.next();
+            }
+
+            self.read_digits(&mut text)?;
+        }
+
+        if !is_float {
+            if let Ok(number) = text.parse::<i64>() {
+                return Ok(Rc::new(Int::new(number)));
+            }
+
+            #[cfg(feature = "bigint")]
+            if let Some(number) = BigInt::parse(&text) {
+                return Ok(Rc::new(number));
+            }
+        }
+
+        match text.parse::<f64>() {
+            Ok(number) if number.is_finite() => Ok(Rc::new(Float::new(number))),
+            _ => Err(JsonError {
+                line,
+                column,
+                description: "Number is out of range".to_string(),
+            }),
+        }
+    }
+}
+
+/// Parses JSON text. Objects become `Map`, arrays become `List`, `null` becomes `Void`.
+pub fn json_to_value(
+    line: u32,
+    column: u32,
+    text: &str,
+) -> Result<Rc<dyn Variant>, NativeException> {
+    let mut parser = JsonParser::new(text);
+    let result = parser.parse_document();
+
+    if result.is_err() {
+        let error = unsafe { result.unwrap_err_unchecked() };
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!(
+                "Invalid JSON on line {} column {}: {}",
+                error.line, error.column, error.description
+            ),
+        ));
+    }
+
+    Ok(unsafe { result.unwrap_unchecked() })
+}
+
+fn write_json_string(text: &str, result: &mut String) {
+    result.push('"');
+
+    for chr in text.chars() {
+        match chr {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            chr if chr < ' ' => result.push_str(&format!("\\u{:04x}", chr as u32)),
+            chr => result.push(chr),
+        }
+    }
+
+    result.push('"');
+}
+
+fn write_newline(indent: usize, depth: usize, result: &mut String) {
+    if indent != 0 {
+        result.push('\n');
+        result.push_str(&" ".repeat(indent * depth));
+    }
+}
+
+fn write_json(
+    line: u32,
+    column: u32,
+    value: &Rc<dyn Variant>,
+    indent: usize,
+    depth: usize,
+    result: &mut String,
+) -> Result<(), NativeException> {
+    match value.get_type() {
+        Type::Void => result.push_str("null"),
+        Type::Bool => result.push_str(&value.as_bool().value.to_string()),
+        Type::Int => result.push_str(&value.as_int().number.to_string()),
+        #[cfg(feature = "bigint")]
+        Type::BigInt => result.push_str(&value.as_bigint().to_string()),
+        Type::Float => {
+            let number = value.as_float().number;
+
+            if !number.is_finite() {
+                return Err(NativeException::new(
+                    line,
+                    column,
+                    "NaN and infinity can't be converted to JSON",
+                ));
+            }
+
+            result.push_str(&format!("{:?}", number));
+        }
+        Type::Str => write_json_string(&value.as_str().text, result),
+        Type::List => {
+            let list = value.as_list();
+            let items = list.items.borrow();
+            result.push('[');
+
+            for (i, item) in items.iter().enumerate() {
+                if i != 0 {
+                    result.push(',');
+                }
+
+                write_newline(indent, depth + 1, result);
+                write_json(line, column, item, indent, depth + 1, result)?;
+            }
+
+            if !items.is_empty() {
+                write_newline(indent, depth, result);
+            }
+
+            result.push(']');
+        }
+        Type::Map => {
+            let map = value.as_map();
+            let items = map.items.borrow();
+            result.push('{');
+
+            for (i, (key, item)) in items.entries().iter().enumerate() {
+                if i != 0 {
+                    result.push(',');
+                }
+
+                write_newline(indent, depth + 1, result);
+
+                match key {
+                    MapKey::Int(number) => write_json_string(&number.to_string(), result),
+                    MapKey::Str(text) => write_json_string(text, result),
+                }
+
+                result.push_str(if indent != 0 { ": " } else { ":" });
+                write_json(line, column, item, indent, depth + 1, result)?;
+            }
+
+            if !items.is_empty() {
+                write_newline(indent, depth, result);
+            }
+
+            result.push('}');
+        }
+        value_type => {
+            return Err(NativeException::new(
+                line,
+                column,
+                &format!("`{:?}` can't be converted to JSON", value_type),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts value to JSON text. `indent` is number of spaces per level, `0` makes compact JSON.
+pub fn value_to_json(
+    line: u32,
+    column: u32,
+    value: &Rc<dyn Variant>,
+    indent: usize,
+) -> Result<String, NativeException> {
+    let mut result = String::new();
+    write_json(line, column, value, indent, 0, &mut result)?;
+    Ok(result)
+}
+
+native_function!(json_parse, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let text = get_str_arg(line, column, &args, 0, "json")?;
+    json_to_value(line, column, &text)
+});
+
+native_function!(json_stringify, line, column, _scope, args, {
+    if args.len() != 1 && args.len() != 2 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 1 or 2 arguments, {} given", args.len()),
+        ));
+    }
+
+    let indent = if args.len() == 2 {
+        get_int_arg(line, column, &args, 1, "indent")?
+    } else {
+        0
+    };
+
+    if !(0..=16).contains(&indent) {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Indent should be from 0 to 16",
+        ));
+    }
+
+    let text = value_to_json(line, column, &args[0], indent as usize)?;
+    Ok(Rc::new(Str::new(&text)))
+});
+
+pub fn add_json_parse(scope: &mut Scope) {
+    let func = Function::new_native(json_parse);
+    scope.set_function("json_parse", func);
+}
+
+pub fn add_json_stringify(scope: &mut Scope) {
+    let func = Function::new_native(json_stringify);
+    scope.set_function("json_stringify", func);
+}
+
+pub fn add_json(scope: &mut Scope) {
+    add_json_parse(scope);
+    add_json_stringify(scope);
+}
@@ -0,0 +1,196 @@
+use std::rc::Rc;
+
+use super::{
+    check_args_count, check_min_args_count, check_not_cycle, get_int_arg, get_list_arg, map_copy,
+};
+use crate::types::{Bool, Function, Int, List, NativeException, Scope, Type, Variant, Void};
+
+/// Gets index argument, which should be less than `len`, or equal to it if `allow_end` is set.
+fn get_index_arg(
+    line: u32,
+    column: u32,
+    args: &[Rc<dyn Variant>],
+    index: usize,
+    len: usize,
+    allow_end: bool,
+) -> Result<usize, NativeException> {
+    let list_index = get_int_arg(line, column, args, index, "index")?;
+
+    if list_index < 0 || list_index as usize > len || (list_index as usize == len && !allow_end) {
+        return Err(NativeException::new(line, column, "Index out of range"));
+    }
+
+    Ok(list_index as usize)
+}
+
+native_function!(push, line, column, _scope, args, {
+    check_min_args_count(line, column, &args, 2)?;
+    let list = get_list_arg(line, column, &args, 0, "list")?;
+
+    for value in &args[1..] {
+        check_not_cycle(line, column, &list, value)?;
+    }
+
+    list.items.borrow_mut().extend_from_slice(&args[1..]);
+    Ok(Rc::new(Void::new()))
+});
+
+native_function!(pop, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let list = get_list_arg(line, column, &args, 0, "list")?;
+    let value = list.items.borrow_mut().pop();
+
+    if value.is_none() {
+        return Err(NativeException::new(line, column, "List is empty"));
+    }
+
+    Ok(unsafe { value.unwrap_unchecked() })
+});
+
+native_function!(get, line, column, _scope, args, {
+    check_args_count(line, column, &args, 2)?;
+    let list = get_list_arg(line, column, &args, 0, "list")?;
+    let items = list.items.borrow();
+    let index = get_index_arg(line, column, &args, 1, items.len(), false)?;
+    Ok(items[index].clone())
+});
+
+native_function!(list_set, line, column, _scope, args, {
+    check_args_count(line, column, &args, 3)?;
+    let list = get_list_arg(line, column, &args, 0, "list")?;
+    let index = get_index_arg(line, column, &args, 1, list.items.borrow().len(), false)?;
+    check_not_cycle(line, column, &list, &args[2])?;
+    list.items.borrow_mut()[index] = args[2].clone();
+    Ok(Rc::new(Void::new()))
+});
+
+native_function!(insert, line, column, _scope, args, {
+    check_args_count(line, column, &args, 3)?;
+    let list = get_list_arg(line, column, &args, 0, "list")?;
+    let index = get_index_arg(line, column, &args, 1, list.items.borrow().len(), true)?;
+    check_not_cycle(line, column, &list, &args[2])?;
+    list.items.borrow_mut().insert(index, args[2].clone());
+    Ok(Rc::new(Void::new()))
+});
+
+native_function!(list_remove, line, column, _scope, args, {
+    check_args_count(line, column, &args, 2)?;
+    let list = get_list_arg(line, column, &args, 0, "list")?;
+    let index = get_index_arg(line, column, &args, 1, list.items.borrow().len(), false)?;
+    let value = list.items.borrow_mut().remove(index);
+    Ok(value)
+});
+
+native_function!(list_len, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let list = get_list_arg(line, column, &args, 0, "list")?;
+    let len = list.items.borrow().len();
+    Ok(Rc::new(Int::new(len as i64)))
+});
+
+native_function!(slice, line, column, _scope, args, {
+    check_args_count(line, column, &args, 3)?;
+    let list = get_list_arg(line, column, &args, 0, "list")?;
+    let start = get_int_arg(line, column, &args, 1, "start")?;
+    let count = get_int_arg(line, column, &args, 2, "count")?;
+
+    if start < 0 || count < 0 {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Start and count should not be negative",
+        ));
+    }
+
+    let items = list.items.borrow();
+
+    if start as usize > items.len() {
+        return Err(NativeException::new(line, column, "Index out of range"));
+    }
+
+    let end = items
+        .len()
+        .min((start as usize).saturating_add(count as usize));
+    Ok(Rc::new(List::new(items[start as usize..end].to_vec())))
+});
+
+native_function!(list_concat, line, column, _scope, args, {
+    let mut items: Vec<Rc<dyn Variant>> = Vec::new();
+
+    for index in 0..args.len() {
+        let list = get_list_arg(line, column, &args, index, "part")?;
+        items.extend_from_slice(&list.items.borrow());
+    }
+
+    Ok(Rc::new(List::new(items)))
+});
+
+native_function!(list_contains, line, column, _scope, args, {
+    check_args_count(line, column, &args, 2)?;
+    let list = get_list_arg(line, column, &args, 0, "list")?;
+    let found = list
+        .items
+        .borrow()
+        .iter()
+        .any(|item| item.equals(args[1].as_ref()));
+    Ok(Rc::new(Bool::new(found)))
+});
+
+native_function!(copy, line, column, scope, args, {
+    check_args_count(line, column, &args, 1)?;
+
+    if args[0].get_type() == Type::Map {
+        return map_copy(line, column, scope, args);
+    }
+
+    let list = get_list_arg(line, column, &args, 0, "list")?;
+    let items = list.items.borrow().clone();
+    Ok(Rc::new(List::new(items)))
+});
+
+pub fn add_push(scope: &mut Scope) {
+    let func = Function::new_native(push);
+    scope.set_function("push", func);
+}
+
+pub fn add_pop(scope: &mut Scope) {
+    let func = Function::new_native(pop);
+    scope.set_function("pop", func);
+}
+
+pub fn add_get(scope: &mut Scope) {
+    let func = Function::new_native(get);
+    scope.set_function("get", func);
+}
+
+pub fn add_insert(scope: &mut Scope) {
+    let func = Function::new_native(insert);
+    scope.set_function("insert", func);
+}
+
+pub fn add_list_remove(scope: &mut Scope) {
+    let func = Function::new_native(list_remove);
+    scope.set_function("list_remove", func);
+}
+
+pub fn add_slice(scope: &mut Scope) {
+    let func = Function::new_native(slice);
+    scope.set_function("slice", func);
+}
+
+pub fn add_copy(scope: &mut Scope) {
+    let func = Function::new_native(copy);
+    scope.set_function("copy", func);
+}
+
+/// Adds list functions. `set`, `len`, `concat` and `contains` are added
+/// by their groups and call list versions when their first argument is `List`.
+pub fn add_list(scope: &mut Scope) {
+    add_push(scope);
+    add_pop(scope);
+    add_get(scope);
+    add_insert(scope);
+    add_list_remove(scope);
+    add_slice(scope);
+    add_copy(scope);
+}
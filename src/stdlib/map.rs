@@ -0,0 +1,179 @@
+use std::rc::Rc;
+
+use super::{check_args_count, check_not_cycle, get_map_arg, get_ordinal};
+use crate::types::{
+    Bool, Function, Int, List, Map, MapItems, MapKey, NativeException, Scope, Variant, Void,
+};
+
+fn get_key_arg(
+    line: u32,
+    column: u32,
+    args: &[Rc<dyn Variant>],
+    index: usize,
+) -> Result<MapKey, NativeException> {
+    let key = MapKey::from_variant(args[index].as_ref());
+
+    if key.is_none() {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!(
+                "{} argument of this function should be `Int(key)` or `Str(key)`",
+                get_ordinal(index)
+            ),
+        ));
+    }
+
+    Ok(unsafe { key.unwrap_unchecked() })
+}
+
+native_function!(map_new, line, column, _scope, args, {
+    check_args_count(line, column, &args, 0)?;
+    Ok(Rc::new(Map::new(MapItems::new())))
+});
+
+native_function!(map_get, line, column, _scope, args, {
+    if args.len() != 2 && args.len() != 3 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 2 or 3 arguments, {} given", args.len()),
+        ));
+    }
+
+    let map = get_map_arg(line, column, &args, 0, "map")?;
+    let key = get_key_arg(line, column, &args, 1)?;
+    let value = map.items.borrow().get(&key).cloned();
+
+    if value.is_none() {
+        if args.len() == 3 {
+            return Ok(args[2].clone());
+        }
+
+        return Ok(Rc::new(Void::new()));
+    }
+
+    Ok(unsafe { value.unwrap_unchecked() })
+});
+
+native_function!(map_set, line, column, _scope, args, {
+    check_args_count(line, column, &args, 3)?;
+    let map = get_map_arg(line, column, &args, 0, "map")?;
+    let key = get_key_arg(line, column, &args, 1)?;
+    check_not_cycle(line, column, &map, &args[2])?;
+    map.items.borrow_mut().insert(key, args[2].clone());
+    Ok(Rc::new(Void::new()))
+});
+
+native_function!(map_has, line, column, _scope, args, {
+    check_args_count(line, column, &args, 2)?;
+    let map = get_map_arg(line, column, &args, 0, "map")?;
+    let key = get_key_arg(line, column, &args, 1)?;
+    let found = map.items.borrow().contains_key(&key);
+    Ok(Rc::new(Bool::new(found)))
+});
+
+native_function!(map_remove, line, column, _scope, args, {
+    check_args_count(line, column, &args, 2)?;
+    let map = get_map_arg(line, column, &args, 0, "map")?;
+    let key = get_key_arg(line, column, &args, 1)?;
+    let value = map.items.borrow_mut().remove(&key);
+
+    if value.is_none() {
+        return Ok(Rc::new(Void::new()));
+    }
+
+    Ok(unsafe { value.unwrap_unchecked() })
+});
+
+native_function!(map_keys, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let map = get_map_arg(line, column, &args, 0, "map")?;
+    let keys = map
+        .items
+        .borrow()
+        .entries()
+        .iter()
+        .map(|(key, _)| key.to_variant())
+        .collect();
+    Ok(Rc::new(List::new(keys)))
+});
+
+native_function!(map_values, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let map = get_map_arg(line, column, &args, 0, "map")?;
+    let values = map
+        .items
+        .borrow()
+        .entries()
+        .iter()
+        .map(|(_, value)| value.clone())
+        .collect();
+    Ok(Rc::new(List::new(values)))
+});
+
+native_function!(map_len, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let map = get_map_arg(line, column, &args, 0, "map")?;
+    let len = map.items.borrow().len();
+    Ok(Rc::new(Int::new(len as i64)))
+});
+
+native_function!(map_copy, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let map = get_map_arg(line, column, &args, 0, "map")?;
+    let items = map.items.borrow().clone();
+    Ok(Rc::new(Map::new(items)))
+});
+
+pub fn add_map_new(scope: &mut Scope) {
+    let func = Function::new_native(map_new);
+    scope.set_function("map_new", func);
+}
+
+pub fn add_map_get(scope: &mut Scope) {
+    let func = Function::new_native(map_get);
+    scope.set_function("map_get", func);
+}
+
+pub fn add_map_set(scope: &mut Scope) {
+    let func = Function::new_native(map_set);
+    scope.set_function("map_set", func);
+}
+
+pub fn add_map_has(scope: &mut Scope) {
+    let func = Function::new_native(map_has);
+    scope.set_function("map_has", func);
+}
+
+pub fn add_map_remove(scope: &mut Scope) {
+    let func = Function::new_native(map_remove);
+    scope.set_function("map_remove", func);
+}
+
+pub fn add_map_keys(scope: &mut Scope) {
+    let func = Function::new_native(map_keys);
+    scope.set_function("map_keys", func);
+}
+
+pub fn add_map_values(scope: &mut Scope) {
+    let func = Function::new_native(map_values);
+    scope.set_function("map_values", func);
+}
+
+pub fn add_map_len(scope: &mut Scope) {
+    let func = Function::new_native(map_len);
+    scope.set_function("map_len", func);
+}
+
+/// Adds map functions. `copy` is added by list group and calls `map_copy` for `Map`.
+pub fn add_map(scope: &mut Scope) {
+    add_map_new(scope);
+    add_map_get(scope);
+    add_map_set(scope);
+    add_map_has(scope);
+    add_map_remove(scope);
+    add_map_keys(scope);
+    add_map_values(scope);
+    add_map_len(scope);
+}
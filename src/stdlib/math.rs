@@ -0,0 +1,801 @@
+use std::{cmp::Ordering, rc::Rc};
+
+use super::{check_args_count, check_min_args_count, get_condition_arg, get_int_arg, get_ordinal};
+#[cfg(feature = "bigint")]
+use crate::bigint::BigInt;
+#[cfg(feature = "bigint")]
+use crate::types::Str;
+use crate::types::{Bool, Float, Function, Int, NativeException, Scope, Type, Variant};
+
+/// Maximal number of bits in `BigInt` result of `pow`.
+#[cfg(feature = "bigint")]
+static MAX_POW_BITS: u64 = 1 << 20;
+
+enum NumberPair {
+    Int(i64, i64),
+    Float(f64, f64),
+    #[cfg(feature = "bigint")]
+    BigInt(BigInt, BigInt),
+}
+
+fn is_number_type(number_type: Type) -> bool {
+    #[cfg(feature = "bigint")]
+    if number_type == Type::BigInt {
+        return true;
+    }
+
+    number_type == Type::Int || number_type == Type::Float
+}
+
+fn get_number(
+    line: u32,
+    column: u32,
+    args: &[Rc<dyn Variant>],
+    index: usize,
+    name: &str,
+) -> Result<Rc<dyn Variant>, NativeException> {
+    if !is_number_type(args[index].get_type()) {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!(
+                "{} argument of this function should be `Int({})` or `Float({})`",
+                get_ordinal(index),
+                name,
+                name
+            ),
+        ));
+    }
+
+    Ok(args[index].clone())
+}
+
+/// Checks that there are at least `count` arguments and all of them are numbers.
+fn check_numbers(
+    line: u32,
+    column: u32,
+    args: &[Rc<dyn Variant>],
+    count: usize,
+    name: &str,
+) -> Result<(), NativeException> {
+    check_min_args_count(line, column, args, count)?;
+
+    for index in 0..args.len() {
+        get_number(line, column, args, index, name)?;
+    }
+
+    Ok(())
+}
+
+fn to_float(number: &Rc<dyn Variant>) -> f64 {
+    #[cfg(feature = "bigint")]
+    if number.get_type() == Type::BigInt {
+        return number.as_bigint().to_f64();
+    }
+
+    if number.get_type() == Type::Float {
+        return number.as_float().number;
+    }
+
+    number.as_int().number as f64
+}
+
+#[cfg(feature = "bigint")]
+fn to_bigint(number: &Rc<dyn Variant>) -> BigInt {
+    if number.get_type() == Type::BigInt {
+        return number.as_bigint();
+    }
+
+    BigInt::from_i64(number.as_int().number)
+}
+
+/// If any of numbers is `Float`, both are converted to `Float`,
+/// otherwise if any of them is `BigInt`, both are converted to `BigInt`.
+fn to_number_pair(a: &Rc<dyn Variant>, b: &Rc<dyn Variant>) -> NumberPair {
+    if a.get_type() == Type::Int && b.get_type() == Type::Int {
+        return NumberPair::Int(a.as_int().number, b.as_int().number);
+    }
+
+    #[cfg(feature = "bigint")]
+    if a.get_type() != Type::Float && b.get_type() != Type::Float {
+        return NumberPair::BigInt(to_bigint(a), to_bigint(b));
+    }
+
+    NumberPair::Float(to_float(a), to_float(b))
+}
+
+fn get_number_pair(
+    line: u32,
+    column: u32,
+    args: &[Rc<dyn Variant>],
+) -> Result<NumberPair, NativeException> {
+    let a = get_number(line, column, args, 0, "a")?;
+    let b = get_number(line, column, args, 1, "b")?;
+    Ok(to_number_pair(&a, &b))
+}
+
+fn checked_int(
+    line: u32,
+    column: u32,
+    result: Option<i64>,
+) -> Result<Rc<dyn Variant>, NativeException> {
+    if result.is_none() {
+        return Err(NativeException::new(line, column, "Integer overflow"));
+    }
+
+    Ok(Rc::new(Int::new(unsafe { result.unwrap_unchecked() })))
+}
+
+/// Compares numbers of any numeric types. Returns `None` if any of them is NaN.
+fn compare_numbers(a: &Rc<dyn Variant>, b: &Rc<dyn Variant>) -> Option<Ordering> {
+    match to_number_pair(a, b) {
+        NumberPair::Int(a, b) => Some(a.cmp(&b)),
+        NumberPair::Float(a, b) => a.partial_cmp(&b),
+        #[cfg(feature = "bigint")]
+        NumberPair::BigInt(a, b) => Some(a.cmp(&b)),
+    }
+}
+
+fn checked_compare(
+    line: u32,
+    column: u32,
+    a: &Rc<dyn Variant>,
+    b: &Rc<dyn Variant>,
+) -> Result<Ordering, NativeException> {
+    let result = compare_numbers(a, b);
+
+    if result.is_none() {
+        return Err(NativeException::new(line, column, "NaN can't be compared"));
+    }
+
+    Ok(unsafe { result.unwrap_unchecked() })
+}
+
+fn add_numbers(
+    line: u32,
+    column: u32,
+    a: &Rc<dyn Variant>,
+    b: &Rc<dyn Variant>,
+) -> Result<Rc<dyn Variant>, NativeException> {
+    match to_number_pair(a, b) {
+        NumberPair::Int(a, b) => checked_int(line, column, a.checked_add(b)),
+        NumberPair::Float(a, b) => Ok(Rc::new(Float::new(a + b))),
+        #[cfg(feature = "bigint")]
+        NumberPair::BigInt(a, b) => Ok(Rc::new(&a + &b)),
+    }
+}
+
+fn mult_numbers(
+    line: u32,
+    column: u32,
+    a: &Rc<dyn Variant>,
+    b: &Rc<dyn Variant>,
+) -> Result<Rc<dyn Variant>, NativeException> {
+    match to_number_pair(a, b) {
+        NumberPair::Int(a, b) => checked_int(line, column, a.checked_mul(b)),
+        NumberPair::Float(a, b) => Ok(Rc::new(Float::new(a * b))),
+        #[cfg(feature = "bigint")]
+        NumberPair::BigInt(a, b) => Ok(Rc::new(&a * &b)),
+    }
+}
+
+/// Exponentiation by squaring. Returns `None` if `multiply` overflows.
+fn power<T>(base: T, exponent: u64, one: T, multiply: impl Fn(&T, &T) -> Option<T>) -> Option<T> {
+    let mut result = one;
+    let mut base = base;
+    let mut exponent = exponent;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = multiply(&result, &base)?;
+        }
+
+        exponent >>= 1;
+
+        if exponent > 0 {
+            base = multiply(&base, &base)?;
+        }
+    }
+
+    Some(result)
+}
+
+fn gcd_u64(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+fn get_shift_arg(
+    line: u32,
+    column: u32,
+    args: &[Rc<dyn Variant>],
+) -> Result<(i64, u32), NativeException> {
+    check_args_count(line, column, args, 2)?;
+    let number = get_int_arg(line, column, args, 0, "number")?;
+    let shift = get_int_arg(line, column, args, 1, "shift")?;
+
+    if !(0..64).contains(&shift) {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Shift should be from 0 to 63",
+        ));
+    }
+
+    Ok((number, shift as u32))
+}
+
+native_function!(add, line, column, _scope, args, {
+    check_numbers(line, column, &args, 2, "term")?;
+    args[1..]
+        .iter()
+        .try_fold(args[0].clone(), |result, number| {
+            add_numbers(line, column, &result, number)
+        })
+});
+
+native_function!(subt, line, column, _scope, args, {
+    check_args_count(line, column, &args, 2)?;
+
+    match get_number_pair(line, column, &args)? {
+        NumberPair::Int(a, b) => checked_int(line, column, a.checked_sub(b)),
+        NumberPair::Float(a, b) => Ok(Rc::new(Float::new(a - b))),
+        #[cfg(feature = "bigint")]
+        NumberPair::BigInt(a, b) => Ok(Rc::new(&a - &b)),
+    }
+});
+
+native_function!(mult, line, column, _scope, args, {
+    check_numbers(line, column, &args, 2, "factor")?;
+    args[1..]
+        .iter()
+        .try_fold(args[0].clone(), |result, number| {
+            mult_numbers(line, column, &result, number)
+        })
+});
+
+native_function!(idiv, line, column, _scope, args, {
+    check_args_count(line, column, &args, 2)?;
+
+    match get_number_pair(line, column, &args)? {
+        NumberPair::Int(a, b) => {
+            if b == 0 {
+                return Err(NativeException::new(line, column, "Division by zero"));
+            }
+
+            checked_int(line, column, a.checked_div(b))
+        }
+        NumberPair::Float(_, _) => Err(NativeException::new(
+            line,
+            column,
+            "Integer division isn't available for `Float`, use `div`",
+        )),
+        #[cfg(feature = "bigint")]
+        NumberPair::BigInt(a, b) => {
+            let result = a.div_rem(&b);
+
+            if result.is_none() {
+                return Err(NativeException::new(line, column, "Division by zero"));
+            }
+
+            Ok(Rc::new(unsafe { result.unwrap_unchecked() }.0))
+        }
+    }
+});
+
+native_function!(div, line, column, _scope, args, {
+    check_args_count(line, column, &args, 2)?;
+    let (a, b) = match get_number_pair(line, column, &args)? {
+        NumberPair::Int(a, b) => (a as f64, b as f64),
+        NumberPair::Float(a, b) => (a, b),
+        #[cfg(feature = "bigint")]
+        NumberPair::BigInt(a, b) => (a.to_f64(), b.to_f64()),
+    };
+
+    if b == 0.0 {
+        return Err(NativeException::new(line, column, "Division by zero"));
+    }
+
+    Ok(Rc::new(Float::new(a / b)))
+});
+
+native_function!(r#mod, line, column, _scope, args, {
+    check_args_count(line, column, &args, 2)?;
+
+    match get_number_pair(line, column, &args)? {
+        NumberPair::Int(a, b) => {
+            if b == 0 {
+                return Err(NativeException::new(line, column, "Division by zero"));
+            }
+
+            Ok(Rc::new(Int::new(a.wrapping_rem(b))))
+        }
+        NumberPair::Float(a, b) => {
+            if b == 0.0 {
+                return Err(NativeException::new(line, column, "Division by zero"));
+            }
+
+            Ok(Rc::new(Float::new(a % b)))
+        }
+        #[cfg(feature = "bigint")]
+        NumberPair::BigInt(a, b) => {
+            let result = a.div_rem(&b);
+
+            if result.is_none() {
+                return Err(NativeException::new(line, column, "Division by zero"));
+            }
+
+            Ok(Rc::new(unsafe { result.unwrap_unchecked() }.1))
+        }
+    }
+});
+
+native_function!(pow, line, column, _scope, args, {
+    check_args_count(line, column, &args, 2)?;
+
+    match get_number_pair(line, column, &args)? {
+        NumberPair::Int(base, exponent) => {
+            if exponent < 0 {
+                return Err(NativeException::new(
+                    line,
+                    column,
+                    "Exponent should not be negative",
+                ));
+            }
+
+            checked_int(
+                line,
+                column,
+                power(base, exponent as u64, 1, |a, b| a.checked_mul(*b)),
+            )
+        }
+        NumberPair::Float(base, exponent) => Ok(Rc::new(Float::new(base.powf(exponent)))),
+        #[cfg(feature = "bigint")]
+        NumberPair::BigInt(base, exponent) => {
+            if exponent.is_negative() {
+                return Err(NativeException::new(
+                    line,
+                    column,
+                    "Exponent should not be negative",
+                ));
+            }
+
+            let exponent = exponent.to_i64();
+
+            if exponent.is_none() {
+                return Err(NativeException::new(line, column, "Integer overflow"));
+            }
+
+            let exponent = unsafe { exponent.unwrap_unchecked() } as u64;
+
+            // Result has at least (bits - 1) * exponent bits, so 0, 1 and -1 may have any exponent.
+            if base.bits().saturating_sub(1).saturating_mul(exponent) > MAX_POW_BITS {
+                return Err(NativeException::new(line, column, "Result is too large"));
+            }
+
+            let result = power(base, exponent, BigInt::from_i64(1), |a, b| Some(a * b));
+            Ok(Rc::new(unsafe { result.unwrap_unchecked() }))
+        }
+    }
+});
+
+native_function!(abs, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let number = get_number(line, column, &args, 0, "number")?;
+
+    #[cfg(feature = "bigint")]
+    if number.get_type() == Type::BigInt {
+        let number = number.as_bigint();
+
+        if number.is_negative() {
+            return Ok(Rc::new(-&number));
+        }
+
+        return Ok(Rc::new(number));
+    }
+
+    if number.get_type() == Type::Float {
+        return Ok(Rc::new(Float::new(number.as_float().number.abs())));
+    }
+
+    checked_int(line, column, number.as_int().number.checked_abs())
+});
+
+native_function!(min, line, column, _scope, args, {
+    check_numbers(line, column, &args, 1, "number")?;
+    let mut result = args[0].clone();
+
+    for number in &args[1..] {
+        if checked_compare(line, column, number, &result)? == Ordering::Less {
+            result = number.clone();
+        }
+    }
+
+    Ok(result)
+});
+
+native_function!(max, line, column, _scope, args, {
+    check_numbers(line, column, &args, 1, "number")?;
+    let mut result = args[0].clone();
+
+    for number in &args[1..] {
+        if checked_compare(line, column, number, &result)? == Ordering::Greater {
+            result = number.clone();
+        }
+    }
+
+    Ok(result)
+});
+
+native_function!(clamp, line, column, _scope, args, {
+    check_args_count(line, column, &args, 3)?;
+    let number = get_number(line, column, &args, 0, "number")?;
+    let min = get_number(line, column, &args, 1, "min")?;
+    let max = get_number(line, column, &args, 2, "max")?;
+
+    if checked_compare(line, column, &min, &max)? == Ordering::Greater {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Minimum is greater than maximum",
+        ));
+    }
+
+    if checked_compare(line, column, &number, &min)? == Ordering::Less {
+        return Ok(min);
+    }
+
+    if checked_compare(line, column, &number, &max)? == Ordering::Greater {
+        return Ok(max);
+    }
+
+    Ok(number)
+});
+
+native_function!(gcd, line, column, _scope, args, {
+    check_min_args_count(line, column, &args, 1)?;
+    let mut result: u64 = 0;
+
+    for index in 0..args.len() {
+        let number = get_int_arg(line, column, &args, index, "number")?;
+        result = gcd_u64(result, number.unsigned_abs());
+    }
+
+    checked_int(line, column, i64::try_from(result).ok())
+});
+
+macro_rules! comparison {
+    ($name: ident, $pattern: pat) => {
+        native_function!($name, line, column, _scope, args, {
+            check_args_count(line, column, &args, 2)?;
+            let a = get_number(line, column, &args, 0, "a")?;
+            let b = get_number(line, column, &args, 1, "b")?;
+            Ok(Rc::new(Bool::new(matches!(
+                checked_compare(line, column, &a, &b)?,
+                $pattern
+            ))))
+        });
+    };
+}
+
+comparison!(lt, Ordering::Less);
+comparison!(gt, Ordering::Greater);
+comparison!(le, Ordering::Less | Ordering::Equal);
+comparison!(ge, Ordering::Greater | Ordering::Equal);
+
+native_function!(not, line, column, scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let condition = get_condition_arg(line, column, scope, &args, 0, "condition")?;
+    Ok(Rc::new(Bool::new(!condition)))
+});
+
+macro_rules! bitwise_operation {
+    ($name: ident, $operator: tt) => {
+        native_function!($name, line, column, _scope, args, {
+            check_min_args_count(line, column, &args, 2)?;
+            let mut result = get_int_arg(line, column, &args, 0, "number")?;
+
+            for index in 1..args.len() {
+                result $operator get_int_arg(line, column, &args, index, "number")?;
+            }
+
+            Ok(Rc::new(Int::new(result)))
+        });
+    };
+}
+
+bitwise_operation!(band, &=);
+bitwise_operation!(bor, |=);
+bitwise_operation!(bxor, ^=);
+
+native_function!(shl, line, column, _scope, args, {
+    let (number, shift) = get_shift_arg(line, column, &args)?;
+    Ok(Rc::new(Int::new(number << shift)))
+});
+
+native_function!(shr, line, column, _scope, args, {
+    let (number, shift) = get_shift_arg(line, column, &args)?;
+    Ok(Rc::new(Int::new(number >> shift)))
+});
+
+macro_rules! int_operation {
+    ($name: ident, $operation: ident) => {
+        native_function!($name, line, column, _scope, args, {
+            check_args_count(line, column, &args, 2)?;
+            let a = get_int_arg(line, column, &args, 0, "a")?;
+            let b = get_int_arg(line, column, &args, 1, "b")?;
+            Ok(Rc::new(Int::new(a.$operation(b))))
+        });
+    };
+}
+
+int_operation!(wrapping_add, wrapping_add);
+int_operation!(wrapping_sub, wrapping_sub);
+int_operation!(wrapping_mul, wrapping_mul);
+int_operation!(saturating_add, saturating_add);
+int_operation!(saturating_sub, saturating_sub);
+int_operation!(saturating_mul, saturating_mul);
+
+#[cfg(feature = "bigint")]
+native_function!(bigint, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+
+    match args[0].get_type() {
+        Type::Int => Ok(Rc::new(BigInt::from_i64(args[0].as_int().number))),
+        Type::BigInt => Ok(args[0].clone()),
+        Type::Str => {
+            let text = args[0].as_str().text;
+            let result = BigInt::parse(text.trim());
+
+            if result.is_none() {
+                return Err(NativeException::new(line, column, "Invalid number string"));
+            }
+
+            Ok(Rc::new(unsafe { result.unwrap_unchecked() }))
+        }
+        _ => Err(NativeException::new(
+            line,
+            column,
+            "First argument of this function should be `Int(number)` or `Str(number)`",
+        )),
+    }
+});
+
+#[cfg(feature = "bigint")]
+native_function!(bigint_to_int, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+
+    if args[0].get_type() != Type::BigInt {
+        return Err(NativeException::new(
+            line,
+            column,
+            "First argument of this function should be `BigInt(number)`",
+        ));
+    }
+
+    let result = args[0].as_bigint().to_i64();
+
+    if result.is_none() {
+        return Err(NativeException::new(line, column, "Integer overflow"));
+    }
+
+    Ok(Rc::new(Int::new(unsafe { result.unwrap_unchecked() })))
+});
+
+#[cfg(feature = "bigint")]
+native_function!(bigint_to_str, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+
+    if args[0].get_type() != Type::BigInt {
+        return Err(NativeException::new(
+            line,
+            column,
+            "First argument of this function should be `BigInt(number)`",
+        ));
+    }
+
+    Ok(Rc::new(Str::new(&args[0].as_bigint().to_string())))
+});
+
+pub fn add_add(scope: &mut Scope) {
+    let func = Function::new_native(add);
+    scope.set_function("add", func);
+}
+
+pub fn add_subt(scope: &mut Scope) {
+    let func = Function::new_native(subt);
+    scope.set_function("subt", func);
+}
+
+pub fn add_mult(scope: &mut Scope) {
+    let func = Function::new_native(mult);
+    scope.set_function("mult", func);
+}
+
+pub fn add_idiv(scope: &mut Scope) {
+    let func = Function::new_native(idiv);
+    scope.set_function("idiv", func);
+}
+
+pub fn add_div(scope: &mut Scope) {
+    let func = Function::new_native(div);
+    scope.set_function("div", func);
+}
+
+pub fn add_mod(scope: &mut Scope) {
+    let func = Function::new_native(r#mod);
+    scope.set_function("mod", func);
+}
+
+pub fn add_pow(scope: &mut Scope) {
+    let func = Function::new_native(pow);
+    scope.set_function("pow", func);
+}
+
+pub fn add_abs(scope: &mut Scope) {
+    let func = Function::new_native(abs);
+    scope.set_function("abs", func);
+}
+
+pub fn add_min(scope: &mut Scope) {
+    let func = Function::new_native(min);
+    scope.set_function("min", func);
+}
+
+pub fn add_max(scope: &mut Scope) {
+    let func = Function::new_native(max);
+    scope.set_function("max", func);
+}
+
+pub fn add_clamp(scope: &mut Scope) {
+    let func = Function::new_native(clamp);
+    scope.set_function("clamp", func);
+}
+
+pub fn add_gcd(scope: &mut Scope) {
+    let func = Function::new_native(gcd);
+    scope.set_function("gcd", func);
+}
+
+pub fn add_lt(scope: &mut Scope) {
+    let func = Function::new_native(lt);
+    scope.set_function("lt", func);
+}
+
+pub fn add_gt(scope: &mut Scope) {
+    let func = Function::new_native(gt);
+    scope.set_function("gt", func);
+}
+
+pub fn add_le(scope: &mut Scope) {
+    let func = Function::new_native(le);
+    scope.set_function("le", func);
+}
+
+pub fn add_ge(scope: &mut Scope) {
+    let func = Function::new_native(ge);
+    scope.set_function("ge", func);
+}
+
+pub fn add_not(scope: &mut Scope) {
+    let func = Function::new_native(not);
+    scope.set_function("not", func);
+}
+
+pub fn add_band(scope: &mut Scope) {
+    let func = Function::new_native(band);
+    scope.set_function("band", func);
+}
+
+pub fn add_bor(scope: &mut Scope) {
+    let func = Function::new_native(bor);
+    scope.set_function("bor", func);
+}
+
+pub fn add_bxor(scope: &mut Scope) {
+    let func = Function::new_native(bxor);
+    scope.set_function("bxor", func);
+}
+
+pub fn add_shl(scope: &mut Scope) {
+    let func = Function::new_native(shl);
+    scope.set_function("shl", func);
+}
+
+pub fn add_shr(scope: &mut Scope) {
+    let func = Function::new_native(shr);
+    scope.set_function("shr", func);
+}
+
+pub fn add_wrapping_add(scope: &mut Scope) {
+    let func = Function::new_native(wrapping_add);
+    scope.set_function("wrapping_add", func);
+}
+
+pub fn add_wrapping_sub(scope: &mut Scope) {
+    let func = Function::new_native(wrapping_sub);
+    scope.set_function("wrapping_sub", func);
+}
+
+pub fn add_wrapping_mul(scope: &mut Scope) {
+    let func = Function::new_native(wrapping_mul);
+    scope.set_function("wrapping_mul", func);
+}
+
+pub fn add_saturating_add(scope: &mut Scope) {
+    let func = Function::new_native(saturating_add);
+    scope.set_function("saturating_add", func);
+}
+
+pub fn add_saturating_sub(scope: &mut Scope) {
+    let func = Function::new_native(saturating_sub);
+    scope.set_function("saturating_sub", func);
+}
+
+pub fn add_saturating_mul(scope: &mut Scope) {
+    let func = Function::new_native(saturating_mul);
+    scope.set_function("saturating_mul", func);
+}
+
+#[cfg(feature = "bigint")]
+pub fn add_bigint(scope: &mut Scope) {
+    let func = Function::new_native(bigint);
+    scope.set_function("bigint", func);
+}
+
+#[cfg(feature = "bigint")]
+pub fn add_bigint_to_int(scope: &mut Scope) {
+    let func = Function::new_native(bigint_to_int);
+    scope.set_function("bigint_to_int", func);
+}
+
+#[cfg(feature = "bigint")]
+pub fn add_bigint_to_str(scope: &mut Scope) {
+    let func = Function::new_native(bigint_to_str);
+    scope.set_function("bigint_to_str", func);
+}
+
+#[cfg(feature = "bigint")]
+pub fn add_bigint_functions(scope: &mut Scope) {
+    add_bigint(scope);
+    add_bigint_to_int(scope);
+    add_bigint_to_str(scope);
+}
+
+#[cfg(not(feature = "bigint"))]
+pub fn add_bigint_functions(_scope: &mut Scope) {}
+
+pub fn add_math(scope: &mut Scope) {
+    add_add(scope);
+    add_subt(scope);
+    add_mult(scope);
+    add_idiv(scope);
+    add_div(scope);
+    add_mod(scope);
+    add_pow(scope);
+    add_abs(scope);
+    add_min(scope);
+    add_max(scope);
+    add_clamp(scope);
+    add_gcd(scope);
+    add_lt(scope);
+    add_gt(scope);
+    add_le(scope);
+    add_ge(scope);
+    add_not(scope);
+    add_band(scope);
+    add_bor(scope);
+    add_bxor(scope);
+    add_shl(scope);
+    add_shr(scope);
+    add_wrapping_add(scope);
+    add_wrapping_sub(scope);
+    add_wrapping_mul(scope);
+    add_saturating_add(scope);
+    add_saturating_sub(scope);
+    add_saturating_mul(scope);
+    add_bigint_functions(scope);
+}
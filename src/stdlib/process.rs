@@ -0,0 +1,351 @@
+use std::io::{self, Read, Write};
+use std::process::{self, Child, Command, ExitStatus, Stdio};
+use std::rc::Rc;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{get_list_arg, get_map_arg, get_str_arg};
+use crate::{
+    engine::Permission,
+    types::{
+        Function, Int, Map, MapItems, MapKey, NativeException, Scope, Str, Type, Variant, Void,
+    },
+};
+
+native_function!(exit, line, column, scope, args, {
+    require_permission!(line, column, scope, Permission::ProcessExit);
+
+    let exit_code: i32;
+    let args_len = args.len();
+
+    if args_len == 0 {
+        exit_code = 0i32;
+    } else if args_len == 1 {
+        if args[0].get_type() != Type::Int {
+            return Err(NativeException::new(
+                line,
+                column,
+                "First argument of this function should be `Int(code)`",
+            ));
+        }
+
+        exit_code = args[0].as_int().number as i32;
+    } else {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!(
+                "This function takes at most 1 argument, {} given",
+                args.len()
+            ),
+        ));
+    }
+
+    process::exit(exit_code)
+});
+
+#[derive(Default)]
+struct ProcessOptions {
+    cwd: Option<String>,
+    env: Vec<(String, String)>,
+    stdin: Option<String>,
+    timeout: Option<u64>,
+}
+
+fn option_error(line: u32, column: u32, name: &str, expected: &str) -> NativeException {
+    NativeException::new(
+        line,
+        column,
+        &format!("Option '{}' should be {}", name, expected),
+    )
+}
+
+fn get_process_options(
+    line: u32,
+    column: u32,
+    options: &Map,
+) -> Result<ProcessOptions, NativeException> {
+    let mut result = ProcessOptions::default();
+
+    for (key, value) in options.items.borrow().entries() {
+        let name = match key {
+            MapKey::Str(name) => name.as_str(),
+            MapKey::Int(number) => {
+                return Err(NativeException::new(
+                    line,
+                    column,
+                    &format!("Unknown option '{}'", number),
+                ));
+            }
+        };
+
+        match name {
+            "cwd" if value.get_type() == Type::Str => result.cwd = Some(value.as_str().text),
+            "cwd" => return Err(option_error(line, column, name, "`Str`")),
+            "stdin" if value.get_type() == Type::Str => result.stdin = Some(value.as_str().text),
+            "stdin" => return Err(option_error(line, column, name, "`Str`")),
+            "timeout_ms" if value.get_type() == Type::Int && value.as_int().number >= 0 => {
+                result.timeout = Some(value.as_int().number as u64);
+            }
+            "timeout_ms" => return Err(option_error(line, column, name, "non-negative `Int`")),
+            "env" if value.get_type() == Type::Map => {
+                for (key, value) in value.as_map().items.borrow().entries() {
+                    match key {
+                        MapKey::Str(key) if value.get_type() == Type::Str => {
+                            result.env.push((key.clone(), value.as_str().text));
+                        }
+                        _ => {
+                            return Err(option_error(
+                                line,
+                                column,
+                                name,
+                                "`Map` from `Str` to `Str`",
+                            ));
+                        }
+                    }
+                }
+            }
+            "env" => {
+                return Err(option_error(
+                    line,
+                    column,
+                    name,
+                    "`Map` from `Str` to `Str`",
+                ))
+            }
+            _ => {
+                return Err(NativeException::new(
+                    line,
+                    column,
+                    &format!("Unknown option '{}'", name),
+                ));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+fn read_pipe<T: Read + Send + 'static>(pipe: Option<T>) -> Receiver<Vec<u8>> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let mut output: Vec<u8> = Vec::new();
+
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut output);
+        }
+
+        let _ = sender.send(output);
+    });
+
+    receiver
+}
+
+/// Waits until pipe is closed. Returns `None` after deadline,
+/// which is possible, when process left child with the same pipe.
+fn wait_pipe(output: Receiver<Vec<u8>>, deadline: Option<Instant>) -> Option<Vec<u8>> {
+    if deadline.is_none() {
+        return Some(output.recv().unwrap_or_default());
+    }
+
+    let timeout = unsafe { deadline.unwrap_unchecked() }.saturating_duration_since(Instant::now());
+
+    match output.recv_timeout(timeout) {
+        Ok(output) => Some(output),
+        Err(RecvTimeoutError::Timeout) => None,
+        Err(RecvTimeoutError::Disconnected) => Some(Vec::new()),
+    }
+}
+
+/// Waits for process. Returns `None`, if it was killed after deadline.
+fn wait_child(child: &mut Child, deadline: Option<Instant>) -> io::Result<Option<ExitStatus>> {
+    if deadline.is_none() {
+        return child.wait().map(Some);
+    }
+
+    let deadline = unsafe { deadline.unwrap_unchecked() };
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+native_function!(run_process, line, column, scope, args, {
+    if args.is_empty() || args.len() > 3 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 1 to 3 arguments, {} given", args.len()),
+        ));
+    }
+
+    require_permission!(line, column, scope, Permission::ProcessSpawn);
+    require_deterministic!(line, column, scope, "run_process");
+    let program = get_str_arg(line, column, &args, 0, "program")?;
+    let mut process_args: Vec<String> = Vec::new();
+
+    if args.len() >= 2 {
+        let list = get_list_arg(line, column, &args, 1, "args")?;
+
+        for item in list.items.borrow().iter() {
+            if item.get_type() != Type::Str {
+                return Err(NativeException::new(
+                    line,
+                    column,
+                    "Arguments of process should be `Str`",
+                ));
+            }
+
+            process_args.push(item.as_str().text);
+        }
+    }
+
+    let options = if args.len() == 3 {
+        let options = get_map_arg(line, column, &args, 2, "options")?;
+        get_process_options(line, column, &options)?
+    } else {
+        ProcessOptions::default()
+    };
+
+    let mut command = Command::new(&program);
+    command
+        .args(&process_args)
+        .envs(options.env)
+        .stdin(if options.stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    if let Some(cwd) = &options.cwd {
+        require_path_permission!(line, column, scope, cwd);
+        // Process runs on host, so directory is mapped from file system of engine to host disk.
+        let host_path = scope.get_engine().get_file_system().to_host_path(cwd);
+
+        if host_path.is_none() {
+            return Err(NativeException::new(
+                line,
+                column,
+                &format!("Directory '{}' isn't on host disk", cwd),
+            ));
+        }
+
+        let host_path = unsafe { host_path.unwrap_unchecked() };
+
+        // Missing directory is reported as missing program by OS, so it's checked separately.
+        if !host_path.is_dir() {
+            return Err(NativeException::new(
+                line,
+                column,
+                &format!("Directory '{}' doesn't exist", cwd),
+            ));
+        }
+
+        command.current_dir(host_path);
+    }
+
+    let spawn_result = command.spawn();
+
+    if spawn_result.is_err() {
+        let error = unsafe { spawn_result.unwrap_err_unchecked() };
+        let description = if error.kind() == io::ErrorKind::NotFound {
+            format!("Program '{}' not found", program)
+        } else {
+            format!("Failed to start '{}': {}", program, error)
+        };
+        return Err(NativeException::new(line, column, &description));
+    }
+
+    let mut child = unsafe { spawn_result.unwrap_unchecked() };
+
+    if let (Some(mut pipe), Some(input)) = (child.stdin.take(), options.stdin) {
+        // Writing in another thread avoids deadlock, when process fills output pipe before reading input.
+        thread::spawn(move || {
+            let _ = pipe.write_all(input.as_bytes());
+        });
+    }
+
+    let deadline = options
+        .timeout
+        .map(|timeout| Instant::now() + Duration::from_millis(timeout));
+    let timeout_error = NativeException::new(
+        line,
+        column,
+        &format!(
+            "Process '{}' timed out after {} ms",
+            program,
+            options.timeout.unwrap_or(0)
+        ),
+    );
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+    let status = wait_child(&mut child, deadline);
+
+    if status.is_err() {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("I/O error: {}", unsafe { status.unwrap_err_unchecked() }),
+        ));
+    }
+
+    let status = unsafe { status.unwrap_unchecked() };
+
+    if status.is_none() {
+        return Err(timeout_error);
+    }
+
+    let code: Rc<dyn Variant> = match unsafe { status.unwrap_unchecked() }.code() {
+        Some(code) => Rc::new(Int::new(code as i64)),
+        None => Rc::new(Void::new()),
+    };
+    let mut result = MapItems::new();
+    result.insert(MapKey::Str("code".to_string()), code);
+
+    for (name, output) in [("stdout", stdout), ("stderr", stderr)] {
+        let output = wait_pipe(output, deadline);
+
+        if output.is_none() {
+            return Err(timeout_error);
+        }
+
+        let output = unsafe { output.unwrap_unchecked() };
+        result.insert(
+            MapKey::Str(name.to_string()),
+            Rc::new(Str::new(&String::from_utf8_lossy(&output))),
+        );
+    }
+
+    Ok(Rc::new(Map::new(result)))
+});
+
+pub fn add_exit(scope: &mut Scope) {
+    let func = Function::new_native(exit);
+    scope.set_function("exit", func);
+}
+
+pub fn add_run_process(scope: &mut Scope) {
+    let func = Function::new_native(run_process);
+    scope.set_function("run_process", func);
+}
+
+/// Adds process functions. `run_process` requires *process spawn* permission,
+/// which isn't granted by default policy.
+pub fn add_process(scope: &mut Scope) {
+    add_exit(scope);
+    add_run_process(scope);
+}
@@ -0,0 +1,96 @@
+use std::rc::Rc;
+
+use super::{check_args_count, get_int_arg, get_list_arg};
+use crate::types::{Function, Int, NativeException, Scope, Variant, Void};
+
+native_function!(rand_seed, line, column, scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let seed = get_int_arg(line, column, &args, 0, "seed")?;
+
+    if scope.get_engine().is_random_seed_locked() {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Random seed is locked by host",
+        ));
+    }
+
+    scope.get_engine_mut().set_random_seed(seed as u64);
+    Ok(Rc::new(Void::new()))
+});
+
+native_function!(rand_int, line, column, scope, args, {
+    check_args_count(line, column, &args, 2)?;
+    let min = get_int_arg(line, column, &args, 0, "min")?;
+    let max = get_int_arg(line, column, &args, 1, "max")?;
+
+    if min > max {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Minimum is greater than maximum",
+        ));
+    }
+
+    let number = scope.get_engine_mut().get_random_mut().next_range(min, max);
+    Ok(Rc::new(Int::new(number)))
+});
+
+native_function!(rand_choice, line, column, scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let list = get_list_arg(line, column, &args, 0, "list")?;
+    let items = list.items.borrow();
+
+    if items.is_empty() {
+        return Err(NativeException::new(line, column, "List is empty"));
+    }
+
+    let index = scope
+        .get_engine_mut()
+        .get_random_mut()
+        .next_below(items.len() as u64);
+    Ok(items[index as usize].clone())
+});
+
+native_function!(shuffle, line, column, scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let list = get_list_arg(line, column, &args, 0, "list")?;
+    let mut items = list.items.borrow_mut();
+    let mut engine = scope.get_engine_mut();
+    let random = engine.get_random_mut();
+
+    for i in (1..items.len()).rev() {
+        let j = random.next_below(i as u64 + 1) as usize;
+        items.swap(i, j);
+    }
+
+    Ok(Rc::new(Void::new()))
+});
+
+pub fn add_rand_seed(scope: &mut Scope) {
+    let func = Function::new_native(rand_seed);
+    scope.set_function("rand_seed", func);
+}
+
+pub fn add_rand_int(scope: &mut Scope) {
+    let func = Function::new_native(rand_int);
+    scope.set_function("rand_int", func);
+}
+
+pub fn add_rand_choice(scope: &mut Scope) {
+    let func = Function::new_native(rand_choice);
+    scope.set_function("rand_choice", func);
+}
+
+pub fn add_shuffle(scope: &mut Scope) {
+    let func = Function::new_native(shuffle);
+    scope.set_function("shuffle", func);
+}
+
+/// Adds pseudorandom functions. Generator is stored in engine and starts from seed `0`.
+pub fn add_random(scope: &mut Scope) {
+    add_rand_seed(scope);
+    add_rand_int(scope);
+    add_rand_choice(scope);
+    add_shuffle(scope);
+}
@@ -0,0 +1,304 @@
+use std::rc::Rc;
+
+use super::{
+    check_args_count, check_min_args_count, get_int_arg, get_str_arg, is_list_call, list_concat,
+    list_contains, list_len,
+};
+use crate::types::{Bool, Function, Int, List, NativeException, Scope, Str, Type, Variant};
+
+/// Maximal length in bytes of string, built by `repeat`.
+static MAX_STRING_LENGTH: usize = 1 << 28;
+
+fn get_str_list(
+    line: u32,
+    column: u32,
+    args: &[Rc<dyn Variant>],
+    from: usize,
+) -> Result<Vec<String>, NativeException> {
+    let mut list: Vec<String> = Vec::with_capacity(args.len() - from.min(args.len()));
+
+    for (i, arg) in args.iter().enumerate().skip(from) {
+        if arg.get_type() != Type::Str {
+            return Err(NativeException::new(
+                line,
+                column,
+                &format!("Argument {} of this function should be `Str(part)`", i + 1),
+            ));
+        }
+
+        list.push(arg.as_str().text);
+    }
+
+    Ok(list)
+}
+
+fn check_not_empty(
+    line: u32,
+    column: u32,
+    pattern: &str,
+    name: &str,
+) -> Result<(), NativeException> {
+    if pattern.is_empty() {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("`{}` should not be empty", name),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Converts index in characters to index in bytes. Index equal to the length is valid.
+fn get_byte_index(text: &str, index: usize) -> Option<usize> {
+    text.char_indices()
+        .map(|(byte_index, _)| byte_index)
+        .chain(std::iter::once(text.len()))
+        .nth(index)
+}
+
+native_function!(len, line, column, scope, args, {
+    if is_list_call(&args) {
+        return list_len(line, column, scope, args);
+    }
+
+    check_args_count(line, column, &args, 1)?;
+    let text = get_str_arg(line, column, &args, 0, "text")?;
+    Ok(Rc::new(Int::new(text.chars().count() as i64)))
+});
+
+native_function!(concat, line, column, scope, args, {
+    if is_list_call(&args) {
+        return list_concat(line, column, scope, args);
+    }
+
+    let parts = get_str_list(line, column, &args, 0)?;
+    Ok(Rc::new(Str::new(&parts.concat())))
+});
+
+native_function!(substr, line, column, _scope, args, {
+    check_args_count(line, column, &args, 3)?;
+    let text = get_str_arg(line, column, &args, 0, "text")?;
+    let start = get_int_arg(line, column, &args, 1, "start")?;
+    let count = get_int_arg(line, column, &args, 2, "count")?;
+
+    if start < 0 || count < 0 {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Start and count should not be negative",
+        ));
+    }
+
+    let start_index = get_byte_index(&text, start as usize);
+
+    if start_index.is_none() {
+        return Err(NativeException::new(line, column, "Index out of range"));
+    }
+
+    let start_index = unsafe { start_index.unwrap_unchecked() };
+    let substring: String = text[start_index..].chars().take(count as usize).collect();
+    Ok(Rc::new(Str::new(&substring)))
+});
+
+native_function!(find, line, column, _scope, args, {
+    check_args_count(line, column, &args, 2)?;
+    let text = get_str_arg(line, column, &args, 0, "text")?;
+    let pattern = get_str_arg(line, column, &args, 1, "pattern")?;
+    let index = text
+        .find(&pattern)
+        .map(|byte_index| text[..byte_index].chars().count() as i64)
+        .unwrap_or(-1);
+    Ok(Rc::new(Int::new(index)))
+});
+
+native_function!(replace, line, column, _scope, args, {
+    check_args_count(line, column, &args, 3)?;
+    let text = get_str_arg(line, column, &args, 0, "text")?;
+    let from = get_str_arg(line, column, &args, 1, "from")?;
+    let to = get_str_arg(line, column, &args, 2, "to")?;
+    check_not_empty(line, column, &from, "from")?;
+    Ok(Rc::new(Str::new(&text.replace(&from, &to))))
+});
+
+native_function!(split, line, column, _scope, args, {
+    check_args_count(line, column, &args, 2)?;
+    let text = get_str_arg(line, column, &args, 0, "text")?;
+    let separator = get_str_arg(line, column, &args, 1, "separator")?;
+    check_not_empty(line, column, &separator, "separator")?;
+    let parts: Vec<Rc<dyn Variant>> = text
+        .split(&separator)
+        .map(|part| Rc::new(Str::new(part)) as Rc<dyn Variant>)
+        .collect();
+    Ok(Rc::new(List::new(parts)))
+});
+
+native_function!(join, line, column, _scope, args, {
+    check_min_args_count(line, column, &args, 1)?;
+    let separator = get_str_arg(line, column, &args, 0, "separator")?;
+
+    if args.len() == 2 && args[1].get_type() == Type::List {
+        let items = args[1].as_list().items.borrow().clone();
+        let parts = get_str_list(line, column, &items, 0)?;
+        return Ok(Rc::new(Str::new(&parts.join(&separator))));
+    }
+
+    let parts = get_str_list(line, column, &args, 1)?;
+    Ok(Rc::new(Str::new(&parts.join(&separator))))
+});
+
+native_function!(trim, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let text = get_str_arg(line, column, &args, 0, "text")?;
+    Ok(Rc::new(Str::new(text.trim())))
+});
+
+native_function!(upper, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let text = get_str_arg(line, column, &args, 0, "text")?;
+    Ok(Rc::new(Str::new(&text.to_uppercase())))
+});
+
+native_function!(lower, line, column, _scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let text = get_str_arg(line, column, &args, 0, "text")?;
+    Ok(Rc::new(Str::new(&text.to_lowercase())))
+});
+
+native_function!(starts_with, line, column, _scope, args, {
+    check_args_count(line, column, &args, 2)?;
+    let text = get_str_arg(line, column, &args, 0, "text")?;
+    let prefix = get_str_arg(line, column, &args, 1, "prefix")?;
+    Ok(Rc::new(Bool::new(text.starts_with(&prefix))))
+});
+
+native_function!(ends_with, line, column, _scope, args, {
+    check_args_count(line, column, &args, 2)?;
+    let text = get_str_arg(line, column, &args, 0, "text")?;
+    let suffix = get_str_arg(line, column, &args, 1, "suffix")?;
+    Ok(Rc::new(Bool::new(text.ends_with(&suffix))))
+});
+
+native_function!(contains, line, column, scope, args, {
+    if is_list_call(&args) {
+        return list_contains(line, column, scope, args);
+    }
+
+    check_args_count(line, column, &args, 2)?;
+    let text = get_str_arg(line, column, &args, 0, "text")?;
+    let pattern = get_str_arg(line, column, &args, 1, "pattern")?;
+    Ok(Rc::new(Bool::new(text.contains(&pattern))))
+});
+
+native_function!(repeat, line, column, _scope, args, {
+    check_args_count(line, column, &args, 2)?;
+    let text = get_str_arg(line, column, &args, 0, "text")?;
+    let count = get_int_arg(line, column, &args, 1, "count")?;
+
+    if count < 0 {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Count should not be negative",
+        ));
+    }
+
+    let length = text.len().checked_mul(count as usize);
+
+    if length.is_none() || unsafe { length.unwrap_unchecked() } > MAX_STRING_LENGTH {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Result string is too long",
+        ));
+    }
+
+    Ok(Rc::new(Str::new(&text.repeat(count as usize))))
+});
+
+pub fn add_len(scope: &mut Scope) {
+    let func = Function::new_native(len);
+    scope.set_function("len", func);
+}
+
+pub fn add_concat(scope: &mut Scope) {
+    let func = Function::new_native(concat);
+    scope.set_function("concat", func);
+}
+
+pub fn add_substr(scope: &mut Scope) {
+    let func = Function::new_native(substr);
+    scope.set_function("substr", func);
+}
+
+pub fn add_find(scope: &mut Scope) {
+    let func = Function::new_native(find);
+    scope.set_function("find", func);
+}
+
+pub fn add_replace(scope: &mut Scope) {
+    let func = Function::new_native(replace);
+    scope.set_function("replace", func);
+}
+
+pub fn add_split(scope: &mut Scope) {
+    let func = Function::new_native(split);
+    scope.set_function("split", func);
+}
+
+pub fn add_join(scope: &mut Scope) {
+    let func = Function::new_native(join);
+    scope.set_function("join", func);
+}
+
+pub fn add_trim(scope: &mut Scope) {
+    let func = Function::new_native(trim);
+    scope.set_function("trim", func);
+}
+
+pub fn add_upper(scope: &mut Scope) {
+    let func = Function::new_native(upper);
+    scope.set_function("upper", func);
+}
+
+pub fn add_lower(scope: &mut Scope) {
+    let func = Function::new_native(lower);
+    scope.set_function("lower", func);
+}
+
+pub fn add_starts_with(scope: &mut Scope) {
+    let func = Function::new_native(starts_with);
+    scope.set_function("starts_with", func);
+}
+
+pub fn add_ends_with(scope: &mut Scope) {
+    let func = Function::new_native(ends_with);
+    scope.set_function("ends_with", func);
+}
+
+pub fn add_contains(scope: &mut Scope) {
+    let func = Function::new_native(contains);
+    scope.set_function("contains", func);
+}
+
+pub fn add_repeat(scope: &mut Scope) {
+    let func = Function::new_native(repeat);
+    scope.set_function("repeat", func);
+}
+
+pub fn add_string_functions(scope: &mut Scope) {
+    add_len(scope);
+    add_concat(scope);
+    add_substr(scope);
+    add_find(scope);
+    add_replace(scope);
+    add_split(scope);
+    add_join(scope);
+    add_trim(scope);
+    add_upper(scope);
+    add_lower(scope);
+    add_starts_with(scope);
+    add_ends_with(scope);
+    add_contains(scope);
+    add_repeat(scope);
+}
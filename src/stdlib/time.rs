@@ -0,0 +1,296 @@
+use std::rc::Rc;
+
+use super::{check_args_count, get_int_arg, get_str_arg};
+use crate::clock::Clock;
+use crate::types::{Function, Int, NativeException, Scope, Str, Variant, Void};
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S.%fZ";
+const MS_PER_DAY: i64 = 86_400_000;
+
+/// Converts date to number of days since 1970-01-01 (proleptic Gregorian calendar).
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Converts number of days since 1970-01-01 to year, month and day.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn invalid_specifier(line: u32, column: u32, specifier: &str) -> NativeException {
+    NativeException::new(
+        line,
+        column,
+        &format!("Invalid date format specifier '{}'", specifier),
+    )
+}
+
+fn format_date_ms(
+    line: u32,
+    column: u32,
+    time: i64,
+    format: &str,
+) -> Result<String, NativeException> {
+    let (year, month, day) = civil_from_days(time.div_euclid(MS_PER_DAY));
+    let ms_of_day = time.rem_euclid(MS_PER_DAY);
+    let mut result = String::new();
+    let mut chars = format.chars();
+
+    while let Some(chr) = chars.next() {
+        if chr != '%' {
+            result.push(chr);
+            continue;
+        }
+
+        match chars.next() {
+            Some('Y') if year < 0 => result.push_str(&format!("-{:04}", -year)),
+            Some('Y') => result.push_str(&format!("{:04}", year)),
+            Some('m') => result.push_str(&format!("{:02}", month)),
+            Some('d') => result.push_str(&format!("{:02}", day)),
+            Some('H') => result.push_str(&format!("{:02}", ms_of_day / 3_600_000)),
+            Some('M') => result.push_str(&format!("{:02}", ms_of_day / 60_000 % 60)),
+            Some('S') => result.push_str(&format!("{:02}", ms_of_day / 1000 % 60)),
+            Some('f') => result.push_str(&format!("{:03}", ms_of_day % 1000)),
+            Some('%') => result.push('%'),
+            Some(chr) => return Err(invalid_specifier(line, column, &format!("%{}", chr))),
+            None => return Err(invalid_specifier(line, column, "%")),
+        }
+    }
+
+    Ok(result)
+}
+
+/// Reads exactly `count` decimal digits.
+fn read_digits(chars: &[char], index: &mut usize, count: usize) -> Option<i64> {
+    let digits = chars.get(*index..*index + count)?;
+
+    if !digits.iter().all(|chr| chr.is_ascii_digit()) {
+        return None;
+    }
+
+    *index += count;
+    digits.iter().collect::<String>().parse().ok()
+}
+
+fn parse_date_ms(line: u32, column: u32, text: &str, format: &str) -> Result<i64, NativeException> {
+    let mismatch = || NativeException::new(line, column, "Date doesn't match format");
+    let text: Vec<char> = text.chars().collect();
+    let mut index = 0;
+    let (mut year, mut month, mut day) = (1970, 1, 1);
+    let (mut hour, mut minute, mut second, mut millisecond) = (0, 0, 0, 0);
+    let mut chars = format.chars();
+
+    while let Some(chr) = chars.next() {
+        if chr != '%' {
+            if text.get(index) != Some(&chr) {
+                return Err(mismatch());
+            }
+
+            index += 1;
+            continue;
+        }
+
+        let (field, count) = match chars.next() {
+            Some('Y') => {
+                let negative = text.get(index) == Some(&'-');
+
+                if negative {
+                    index += 1;
+                }
+
+                year = read_digits(&text, &mut index, 4).ok_or_else(mismatch)?;
+
+                if negative {
+                    year = -year;
+                }
+
+                continue;
+            }
+            Some('m') => (&mut month, 2),
+            Some('d') => (&mut day, 2),
+            Some('H') => (&mut hour, 2),
+            Some('M') => (&mut minute, 2),
+            Some('S') => (&mut second, 2),
+            Some('f') => (&mut millisecond, 3),
+            Some('%') => {
+                if text.get(index) != Some(&'%') {
+                    return Err(mismatch());
+                }
+
+                index += 1;
+                continue;
+            }
+            Some(chr) => return Err(invalid_specifier(line, column, &format!("%{}", chr))),
+            None => return Err(invalid_specifier(line, column, "%")),
+        };
+
+        *field = read_digits(&text, &mut index, count).ok_or_else(mismatch)?;
+    }
+
+    if index != text.len() {
+        return Err(mismatch());
+    }
+
+    if !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+        || hour >= 24
+        || minute >= 60
+        || second >= 60
+    {
+        return Err(NativeException::new(line, column, "Invalid date"));
+    }
+
+    Ok(days_from_civil(year, month, day) * MS_PER_DAY
+        + hour * 3_600_000
+        + minute * 60_000
+        + second * 1000
+        + millisecond)
+}
+
+fn get_format_arg(
+    line: u32,
+    column: u32,
+    args: &[Rc<dyn Variant>],
+) -> Result<String, NativeException> {
+    if args.len() != 1 && args.len() != 2 {
+        return Err(NativeException::new(
+            line,
+            column,
+            &format!("This function takes 1 or 2 arguments, {} given", args.len()),
+        ));
+    }
+
+    if args.len() == 2 {
+        return get_str_arg(line, column, args, 1, "format");
+    }
+
+    Ok(DEFAULT_DATE_FORMAT.to_string())
+}
+
+/// Gets clock of engine. In deterministic mode the clock should be deterministic.
+fn get_clock(
+    line: u32,
+    column: u32,
+    scope: &Scope,
+    name: &str,
+) -> Result<Rc<dyn Clock>, NativeException> {
+    let engine = scope.get_engine();
+    let clock = engine.get_clock();
+
+    if engine.is_deterministic() && !clock.is_deterministic() {
+        return Err(NativeException::nondeterministic(
+            line,
+            column,
+            &format!(
+                "`{}` requires deterministic clock in deterministic mode",
+                name
+            ),
+        ));
+    }
+
+    Ok(clock)
+}
+
+native_function!(now_ms, line, column, scope, args, {
+    check_args_count(line, column, &args, 0)?;
+    let time = get_clock(line, column, scope, "now_ms")?.now_ms();
+    Ok(Rc::new(Int::new(time)))
+});
+
+native_function!(monotonic_ms, line, column, scope, args, {
+    check_args_count(line, column, &args, 0)?;
+    let time = get_clock(line, column, scope, "monotonic_ms")?.monotonic_ms();
+    Ok(Rc::new(Int::new(time)))
+});
+
+native_function!(sleep_ms, line, column, scope, args, {
+    check_args_count(line, column, &args, 1)?;
+    let duration = get_int_arg(line, column, &args, 0, "duration")?;
+
+    if duration < 0 {
+        return Err(NativeException::new(
+            line,
+            column,
+            "Duration should not be negative",
+        ));
+    }
+
+    get_clock(line, column, scope, "sleep_ms")?.sleep_ms(duration as u64);
+    Ok(Rc::new(Void::new()))
+});
+
+native_function!(format_date, line, column, _scope, args, {
+    let format = get_format_arg(line, column, &args)?;
+    let time = get_int_arg(line, column, &args, 0, "time")?;
+    let text = format_date_ms(line, column, time, &format)?;
+    Ok(Rc::new(Str::new(&text)))
+});
+
+native_function!(parse_date, line, column, _scope, args, {
+    let format = get_format_arg(line, column, &args)?;
+    let text = get_str_arg(line, column, &args, 0, "date")?;
+    let time = parse_date_ms(line, column, &text, &format)?;
+    Ok(Rc::new(Int::new(time)))
+});
+
+pub fn add_now_ms(scope: &mut Scope) {
+    let func = Function::new_native(now_ms);
+    scope.set_function("now_ms", func);
+}
+
+pub fn add_monotonic_ms(scope: &mut Scope) {
+    let func = Function::new_native(monotonic_ms);
+    scope.set_function("monotonic_ms", func);
+}
+
+pub fn add_sleep_ms(scope: &mut Scope) {
+    let func = Function::new_native(sleep_ms);
+    scope.set_function("sleep_ms", func);
+}
+
+pub fn add_format_date(scope: &mut Scope) {
+    let func = Function::new_native(format_date);
+    scope.set_function("format_date", func);
+}
+
+pub fn add_parse_date(scope: &mut Scope) {
+    let func = Function::new_native(parse_date);
+    scope.set_function("parse_date", func);
+}
+
+/// Adds time functions. Time is read from clock of engine, see `Engine::set_clock`.
+pub fn add_time(scope: &mut Scope) {
+    add_now_ms(scope);
+    add_monotonic_ms(scope);
+    add_sleep_ms(scope);
+    add_format_date(scope);
+    add_parse_date(scope);
+}
@@ -1,7 +1,11 @@
-use std::collections::HashMap;
+use std::cell::{Ref, RefCell, RefMut};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
 
+#[cfg(feature = "bigint")]
+use crate::bigint::BigInt;
+use crate::engine::Engine;
 #[cfg(feature = "stdlib")]
 use crate::stdlib::add_stdlib;
 
@@ -13,6 +17,12 @@ pub enum Type {
     Str,
     Func,
     Custom,
+    Float,
+    List,
+    Map,
+    Bool,
+    #[cfg(feature = "bigint")]
+    BigInt,
 }
 
 #[repr(u8)]
@@ -23,16 +33,28 @@ pub enum NodeType {
     ConstInt = 2,
     ConstStr = 3,
     Identifier = 4,
+    ConstFloat = 5,
+    List = 6,
+    Map = 7,
 }
 
 pub trait Variant {
     fn get_type(&self) -> Type;
     fn as_int(&self) -> Int;
+    fn as_float(&self) -> Float;
     fn as_str(&self) -> Str;
     fn as_func(&self) -> Function;
     fn as_custom(&self) -> Rc<dyn Custom>;
+    fn as_list(&self) -> List;
+    fn as_map(&self) -> Map;
+    fn as_bool(&self) -> Bool;
     fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result;
     fn equals(&self, other: &dyn Variant) -> bool;
+
+    #[cfg(feature = "bigint")]
+    fn as_bigint(&self) -> BigInt {
+        unimplemented!()
+    }
 }
 
 impl Debug for dyn Variant {
@@ -47,6 +69,19 @@ impl PartialEq for dyn Variant {
     }
 }
 
+macro_rules! print_list {
+    ($f: ident, $iter: ident) => {
+        while $iter.len() != 0 {
+            let arg = unsafe { $iter.next().unwrap_unchecked() };
+            arg.print($f)?;
+
+            if $iter.len() != 0 {
+                $f.write_str(", ")?;
+            }
+        }
+    };
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct Int {
     pub number: i64,
@@ -67,6 +102,10 @@ impl Variant for Int {
         *self
     }
 
+    fn as_float(&self) -> Float {
+        unimplemented!()
+    }
+
     fn as_str(&self) -> Str {
         unimplemented!()
     }
@@ -79,15 +118,208 @@ impl Variant for Int {
         unimplemented!()
     }
 
+    fn as_list(&self) -> List {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> Map {
+        unimplemented!()
+    }
+
+    fn as_bool(&self) -> Bool {
+        unimplemented!()
+    }
+
     fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!("{}", self.number))
     }
 
     fn equals(&self, other: &dyn Variant) -> bool {
+        if self.get_type() != other.get_type() {
+            return false;
+        }
+
         self.number == other.as_int().number
     }
 }
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Bool {
+    pub value: bool,
+}
+
+impl Bool {
+    pub fn new(value: bool) -> Bool {
+        Bool { value }
+    }
+}
+
+impl Variant for Bool {
+    fn get_type(&self) -> Type {
+        Type::Bool
+    }
+
+    fn as_int(&self) -> Int {
+        unimplemented!()
+    }
+
+    fn as_float(&self) -> Float {
+        unimplemented!()
+    }
+
+    fn as_str(&self) -> Str {
+        unimplemented!()
+    }
+
+    fn as_func(&self) -> Function {
+        unimplemented!()
+    }
+
+    fn as_custom(&self) -> Rc<dyn Custom> {
+        unimplemented!()
+    }
+
+    fn as_list(&self) -> List {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> Map {
+        unimplemented!()
+    }
+
+    fn as_bool(&self) -> Bool {
+        *self
+    }
+
+    fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}", self.value))
+    }
+
+    fn equals(&self, other: &dyn Variant) -> bool {
+        if self.get_type() != other.get_type() {
+            return false;
+        }
+
+        self.value == other.as_bool().value
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Float {
+    pub number: f64,
+}
+
+impl Float {
+    pub fn new(number: f64) -> Float {
+        Float { number }
+    }
+}
+
+impl Variant for Float {
+    fn get_type(&self) -> Type {
+        Type::Float
+    }
+
+    fn as_int(&self) -> Int {
+        unimplemented!()
+    }
+
+    fn as_float(&self) -> Float {
+        *self
+    }
+
+    fn as_str(&self) -> Str {
+        unimplemented!()
+    }
+
+    fn as_func(&self) -> Function {
+        unimplemented!()
+    }
+
+    fn as_custom(&self) -> Rc<dyn Custom> {
+        unimplemented!()
+    }
+
+    fn as_list(&self) -> List {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> Map {
+        unimplemented!()
+    }
+
+    fn as_bool(&self) -> Bool {
+        unimplemented!()
+    }
+
+    fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:?}", self.number))
+    }
+
+    fn equals(&self, other: &dyn Variant) -> bool {
+        if self.get_type() != other.get_type() {
+            return false;
+        }
+
+        self.number == other.as_float().number
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl Variant for BigInt {
+    fn get_type(&self) -> Type {
+        Type::BigInt
+    }
+
+    fn as_int(&self) -> Int {
+        unimplemented!()
+    }
+
+    fn as_float(&self) -> Float {
+        unimplemented!()
+    }
+
+    fn as_str(&self) -> Str {
+        unimplemented!()
+    }
+
+    fn as_func(&self) -> Function {
+        unimplemented!()
+    }
+
+    fn as_custom(&self) -> Rc<dyn Custom> {
+        unimplemented!()
+    }
+
+    fn as_list(&self) -> List {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> Map {
+        unimplemented!()
+    }
+
+    fn as_bool(&self) -> Bool {
+        unimplemented!()
+    }
+
+    fn as_bigint(&self) -> BigInt {
+        self.to_owned()
+    }
+
+    fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("BigInt({})", self))
+    }
+
+    fn equals(&self, other: &dyn Variant) -> bool {
+        if self.get_type() != other.get_type() {
+            return false;
+        }
+
+        *self == other.as_bigint()
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Str {
     pub text: String,
@@ -110,6 +342,10 @@ impl Variant for Str {
         unimplemented!()
     }
 
+    fn as_float(&self) -> Float {
+        unimplemented!()
+    }
+
     fn as_str(&self) -> Str {
         self.to_owned()
     }
@@ -122,6 +358,18 @@ impl Variant for Str {
         unimplemented!()
     }
 
+    fn as_list(&self) -> List {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> Map {
+        unimplemented!()
+    }
+
+    fn as_bool(&self) -> Bool {
+        unimplemented!()
+    }
+
     fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!("\"{}\"", self.text))
     }
@@ -135,6 +383,314 @@ impl Variant for Str {
     }
 }
 
+/// List of values. `List` is a reference: its copies share the same items,
+/// so changes made through one of them are visible through all others.
+#[derive(Debug, Clone)]
+pub struct List {
+    pub items: Rc<RefCell<Vec<Rc<dyn Variant>>>>,
+}
+
+impl List {
+    pub fn new(items: Vec<Rc<dyn Variant>>) -> List {
+        List {
+            items: Rc::new(RefCell::new(items)),
+        }
+    }
+
+    pub fn ptr_eq(&self, other: &List) -> bool {
+        Rc::ptr_eq(&self.items, &other.items)
+    }
+}
+
+impl Variant for List {
+    fn get_type(&self) -> Type {
+        Type::List
+    }
+
+    fn as_int(&self) -> Int {
+        unimplemented!()
+    }
+
+    fn as_float(&self) -> Float {
+        unimplemented!()
+    }
+
+    fn as_str(&self) -> Str {
+        unimplemented!()
+    }
+
+    fn as_func(&self) -> Function {
+        unimplemented!()
+    }
+
+    fn as_custom(&self) -> Rc<dyn Custom> {
+        unimplemented!()
+    }
+
+    fn as_list(&self) -> List {
+        self.to_owned()
+    }
+
+    fn as_map(&self) -> Map {
+        unimplemented!()
+    }
+
+    fn as_bool(&self) -> Bool {
+        unimplemented!()
+    }
+
+    fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[")?;
+        let items = self.items.borrow();
+        let mut iter = items.iter();
+        print_list!(f, iter);
+        f.write_str("]")
+    }
+
+    fn equals(&self, other: &dyn Variant) -> bool {
+        if self.get_type() != other.get_type() {
+            return false;
+        }
+
+        let other_var = other.as_list();
+
+        if self.ptr_eq(&other_var) {
+            return true;
+        }
+
+        let items = self.items.borrow();
+        let other_items = other_var.items.borrow();
+
+        items.len() == other_items.len()
+            && items
+                .iter()
+                .zip(other_items.iter())
+                .all(|(item, other_item)| item.equals(other_item.as_ref()))
+    }
+}
+
+/// Gets address of items of `List` or `Map`, that identifies collection.
+fn get_collection_ptr(value: &dyn Variant) -> Option<*const ()> {
+    match value.get_type() {
+        Type::List => Some(Rc::as_ptr(&value.as_list().items) as *const ()),
+        Type::Map => Some(Rc::as_ptr(&value.as_map().items) as *const ()),
+        _ => None,
+    }
+}
+
+/// Checks if `collection` is `value` or is nested in it at any depth.
+pub fn contains_collection(value: &dyn Variant, collection: &dyn Variant) -> bool {
+    let value_ptr = get_collection_ptr(value);
+
+    if value_ptr.is_none() {
+        return false;
+    }
+
+    if value_ptr == get_collection_ptr(collection) {
+        return true;
+    }
+
+    match value.get_type() {
+        Type::List => value
+            .as_list()
+            .items
+            .borrow()
+            .iter()
+            .any(|item| contains_collection(item.as_ref(), collection)),
+        _ => value
+            .as_map()
+            .items
+            .borrow()
+            .entries()
+            .iter()
+            .any(|(_, item)| contains_collection(item.as_ref(), collection)),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MapKey {
+    Int(i64),
+    Str(String),
+}
+
+impl MapKey {
+    /// Converts `Int` or `Str` to key, other types can't be keys.
+    pub fn from_variant(value: &dyn Variant) -> Option<MapKey> {
+        match value.get_type() {
+            Type::Int => Some(MapKey::Int(value.as_int().number)),
+            Type::Str => Some(MapKey::Str(value.as_str().text)),
+            _ => None,
+        }
+    }
+
+    pub fn to_variant(&self) -> Rc<dyn Variant> {
+        match self {
+            MapKey::Int(number) => Rc::new(Int::new(*number)),
+            MapKey::Str(text) => Rc::new(Str::new(text)),
+        }
+    }
+}
+
+/// Entries of `Map` in insertion order with index for lookup by key.
+#[derive(Debug, Clone, Default)]
+pub struct MapItems {
+    entries: Vec<(MapKey, Rc<dyn Variant>)>,
+    indices: HashMap<MapKey, usize>,
+}
+
+impl MapItems {
+    pub fn new() -> MapItems {
+        MapItems::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[(MapKey, Rc<dyn Variant>)] {
+        &self.entries
+    }
+
+    pub fn get(&self, key: &MapKey) -> Option<&Rc<dyn Variant>> {
+        self.indices.get(key).map(|index| &self.entries[*index].1)
+    }
+
+    pub fn contains_key(&self, key: &MapKey) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    /// Sets value of key. New keys are added to the end, existing keep their position.
+    pub fn insert(&mut self, key: MapKey, value: Rc<dyn Variant>) -> Option<Rc<dyn Variant>> {
+        let index = self.indices.get(&key);
+
+        if index.is_some() {
+            let index = *unsafe { index.unwrap_unchecked() };
+            return Some(std::mem::replace(&mut self.entries[index].1, value));
+        }
+
+        self.indices.insert(key.clone(), self.entries.len());
+        self.entries.push((key, value));
+        None
+    }
+
+    pub fn remove(&mut self, key: &MapKey) -> Option<Rc<dyn Variant>> {
+        let index = self.indices.remove(key)?;
+        let (_, value) = self.entries.remove(index);
+
+        for (entry_key, _) in &self.entries[index..] {
+            *unsafe { self.indices.get_mut(entry_key).unwrap_unchecked() } -= 1;
+        }
+
+        Some(value)
+    }
+}
+
+/// Map from `Int` or `Str` keys to values, that keeps insertion order.
+/// Like `List`, `Map` is a reference: its copies share the same entries.
+#[derive(Debug, Clone)]
+pub struct Map {
+    pub items: Rc<RefCell<MapItems>>,
+}
+
+impl Map {
+    pub fn new(items: MapItems) -> Map {
+        Map {
+            items: Rc::new(RefCell::new(items)),
+        }
+    }
+
+    pub fn ptr_eq(&self, other: &Map) -> bool {
+        Rc::ptr_eq(&self.items, &other.items)
+    }
+}
+
+impl Variant for Map {
+    fn get_type(&self) -> Type {
+        Type::Map
+    }
+
+    fn as_int(&self) -> Int {
+        unimplemented!()
+    }
+
+    fn as_float(&self) -> Float {
+        unimplemented!()
+    }
+
+    fn as_str(&self) -> Str {
+        unimplemented!()
+    }
+
+    fn as_func(&self) -> Function {
+        unimplemented!()
+    }
+
+    fn as_custom(&self) -> Rc<dyn Custom> {
+        unimplemented!()
+    }
+
+    fn as_list(&self) -> List {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> Map {
+        self.to_owned()
+    }
+
+    fn as_bool(&self) -> Bool {
+        unimplemented!()
+    }
+
+    fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let items = self.items.borrow();
+
+        if items.is_empty() {
+            return f.write_str("[=]");
+        }
+
+        f.write_str("[")?;
+
+        for (i, (key, value)) in items.entries().iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+
+            key.to_variant().print(f)?;
+            f.write_str(" = ")?;
+            value.print(f)?;
+        }
+
+        f.write_str("]")
+    }
+
+    /// Maps are equal if they have equal values for the same keys, order doesn't matter.
+    fn equals(&self, other: &dyn Variant) -> bool {
+        if self.get_type() != other.get_type() {
+            return false;
+        }
+
+        let other_var = other.as_map();
+
+        if self.ptr_eq(&other_var) {
+            return true;
+        }
+
+        let items = self.items.borrow();
+        let other_items = other_var.items.borrow();
+
+        items.len() == other_items.len()
+            && items.entries().iter().all(|(key, value)| {
+                other_items
+                    .get(key)
+                    .is_some_and(|other_value| value.equals(other_value.as_ref()))
+            })
+    }
+}
+
 pub trait Custom {
     fn get_id(&self) -> usize;
     fn custom_equals(&self, custom: &mut Rc<dyn Custom>) -> bool;
@@ -143,11 +699,20 @@ pub trait Custom {
         None
     }
 
+    /// Serializes value for scope snapshots. Values of types, that return `None`, can't be saved.
+    /// Loading requires loader, registered with `Engine::add_custom_loader`.
+    fn snapshot(&self) -> Option<String> {
+        None
+    }
+
     fn get_ptr(&self) -> *mut () {
         self as *const Self as *mut ()
     }
 }
 
+/// Creates custom value from data, returned by `Custom::snapshot`.
+pub type CustomLoader = fn(data: &str) -> Option<Rc<dyn Custom>>;
+
 impl Variant for Rc<dyn Custom> {
     fn get_type(&self) -> Type {
         Type::Custom
@@ -157,6 +722,10 @@ impl Variant for Rc<dyn Custom> {
         unimplemented!()
     }
 
+    fn as_float(&self) -> Float {
+        unimplemented!()
+    }
+
     fn as_str(&self) -> Str {
         unimplemented!()
     }
@@ -169,6 +738,18 @@ impl Variant for Rc<dyn Custom> {
         self.to_owned()
     }
 
+    fn as_list(&self) -> List {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> Map {
+        unimplemented!()
+    }
+
+    fn as_bool(&self) -> Bool {
+        unimplemented!()
+    }
+
     fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let repr = self.repr();
 
@@ -216,6 +797,10 @@ impl Variant for Void {
         unimplemented!()
     }
 
+    fn as_float(&self) -> Float {
+        unimplemented!()
+    }
+
     fn as_str(&self) -> Str {
         unimplemented!()
     }
@@ -228,6 +813,18 @@ impl Variant for Void {
         unimplemented!()
     }
 
+    fn as_list(&self) -> List {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> Map {
+        unimplemented!()
+    }
+
+    fn as_bool(&self) -> Bool {
+        unimplemented!()
+    }
+
     fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str("Void")
     }
@@ -243,7 +840,10 @@ pub trait AstNode {
     fn as_call_func(&self) -> CallFuncNode;
     fn as_str_const(&self) -> ConstStrNode;
     fn as_int_const(&self) -> ConstIntNode;
+    fn as_float_const(&self) -> ConstFloatNode;
     fn as_variable(&self) -> VariableNode;
+    fn as_list(&self) -> ListNode;
+    fn as_map(&self) -> MapNode;
     fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result;
     fn equals(&self, other: &dyn AstNode) -> bool;
 }
@@ -317,6 +917,10 @@ impl Variant for Function {
         unimplemented!()
     }
 
+    fn as_float(&self) -> Float {
+        unimplemented!()
+    }
+
     fn as_str(&self) -> Str {
         unimplemented!()
     }
@@ -325,7 +929,19 @@ impl Variant for Function {
         self.to_owned()
     }
 
-    fn as_custom(&self) -> Rc<dyn Custom> {
+    fn as_custom(&self) -> Rc<dyn Custom> {
+        unimplemented!()
+    }
+
+    fn as_list(&self) -> List {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> Map {
+        unimplemented!()
+    }
+
+    fn as_bool(&self) -> Bool {
         unimplemented!()
     }
 
@@ -381,19 +997,6 @@ impl SequenceNode {
     }
 }
 
-macro_rules! print_list {
-    ($f: ident, $iter: ident) => {
-        while $iter.len() != 0 {
-            let arg = unsafe { $iter.next().unwrap_unchecked() };
-            arg.print($f)?;
-
-            if $iter.len() != 0 {
-                $f.write_str(", ")?;
-            }
-        }
-    };
-}
-
 impl AstNode for SequenceNode {
     fn get_type(&self) -> NodeType {
         NodeType::Sequence
@@ -415,10 +1018,22 @@ impl AstNode for SequenceNode {
         unimplemented!()
     }
 
+    fn as_float_const(&self) -> ConstFloatNode {
+        unimplemented!()
+    }
+
     fn as_variable(&self) -> VariableNode {
         unimplemented!()
     }
 
+    fn as_list(&self) -> ListNode {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> MapNode {
+        unimplemented!()
+    }
+
     fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str("Sequence(")?;
         let mut iter = self.body.iter();
@@ -476,10 +1091,22 @@ impl AstNode for CallFuncNode {
         unimplemented!()
     }
 
+    fn as_float_const(&self) -> ConstFloatNode {
+        unimplemented!()
+    }
+
     fn as_variable(&self) -> VariableNode {
         unimplemented!()
     }
 
+    fn as_list(&self) -> ListNode {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> MapNode {
+        unimplemented!()
+    }
+
     fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!("CallFunc(\"{}\", [", self.name))?;
         let mut iter = self.args.iter();
@@ -539,10 +1166,22 @@ impl AstNode for ConstIntNode {
         self.to_owned()
     }
 
+    fn as_float_const(&self) -> ConstFloatNode {
+        unimplemented!()
+    }
+
     fn as_variable(&self) -> VariableNode {
         unimplemented!()
     }
 
+    fn as_list(&self) -> ListNode {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> MapNode {
+        unimplemented!()
+    }
+
     fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         self.value.print(f)
     }
@@ -556,6 +1195,74 @@ impl AstNode for ConstIntNode {
     }
 }
 
+#[repr(C)]
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConstFloatNode {
+    pub line: u32,
+    pub column: u32,
+    pub value: Float,
+}
+
+impl ConstFloatNode {
+    pub fn new(line: u32, column: u32, value: Float) -> ConstFloatNode {
+        ConstFloatNode {
+            line,
+            column,
+            value,
+        }
+    }
+}
+
+impl AstNode for ConstFloatNode {
+    fn get_type(&self) -> NodeType {
+        NodeType::ConstFloat
+    }
+
+    fn as_sequence(&self) -> SequenceNode {
+        unimplemented!()
+    }
+
+    fn as_call_func(&self) -> CallFuncNode {
+        unimplemented!()
+    }
+
+    fn as_str_const(&self) -> ConstStrNode {
+        unimplemented!()
+    }
+
+    fn as_int_const(&self) -> ConstIntNode {
+        unimplemented!()
+    }
+
+    fn as_float_const(&self) -> ConstFloatNode {
+        self.to_owned()
+    }
+
+    fn as_variable(&self) -> VariableNode {
+        unimplemented!()
+    }
+
+    fn as_list(&self) -> ListNode {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> MapNode {
+        unimplemented!()
+    }
+
+    fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.value.print(f)
+    }
+
+    fn equals(&self, other: &dyn AstNode) -> bool {
+        if self.get_type() != other.get_type() {
+            return false;
+        }
+
+        self.as_float_const().value == other.as_float_const().value
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct ConstStrNode {
@@ -595,10 +1302,22 @@ impl AstNode for ConstStrNode {
         unimplemented!()
     }
 
+    fn as_float_const(&self) -> ConstFloatNode {
+        unimplemented!()
+    }
+
     fn as_variable(&self) -> VariableNode {
         unimplemented!()
     }
 
+    fn as_list(&self) -> ListNode {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> MapNode {
+        unimplemented!()
+    }
+
     fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         self.value.print(f)
     }
@@ -651,10 +1370,22 @@ impl AstNode for VariableNode {
         unimplemented!()
     }
 
+    fn as_float_const(&self) -> ConstFloatNode {
+        unimplemented!()
+    }
+
     fn as_variable(&self) -> VariableNode {
         self.to_owned()
     }
 
+    fn as_list(&self) -> ListNode {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> MapNode {
+        unimplemented!()
+    }
+
     fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str(&self.name)
     }
@@ -668,11 +1399,171 @@ impl AstNode for VariableNode {
     }
 }
 
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct ListNode {
+    pub line: u32,
+    pub column: u32,
+    pub items: Vec<Rc<dyn AstNode>>,
+}
+
+impl ListNode {
+    pub fn new(line: u32, column: u32, items: Vec<Rc<dyn AstNode>>) -> ListNode {
+        ListNode {
+            line,
+            column,
+            items,
+        }
+    }
+}
+
+impl AstNode for ListNode {
+    fn get_type(&self) -> NodeType {
+        NodeType::List
+    }
+
+    fn as_sequence(&self) -> SequenceNode {
+        unimplemented!()
+    }
+
+    fn as_call_func(&self) -> CallFuncNode {
+        unimplemented!()
+    }
+
+    fn as_str_const(&self) -> ConstStrNode {
+        unimplemented!()
+    }
+
+    fn as_int_const(&self) -> ConstIntNode {
+        unimplemented!()
+    }
+
+    fn as_float_const(&self) -> ConstFloatNode {
+        unimplemented!()
+    }
+
+    fn as_variable(&self) -> VariableNode {
+        unimplemented!()
+    }
+
+    fn as_list(&self) -> ListNode {
+        self.to_owned()
+    }
+
+    fn as_map(&self) -> MapNode {
+        unimplemented!()
+    }
+
+    fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("List([")?;
+        let mut iter = self.items.iter();
+        print_list!(f, iter);
+        f.write_str("])")
+    }
+
+    fn equals(&self, other: &dyn AstNode) -> bool {
+        if self.get_type() != other.get_type() {
+            return false;
+        }
+
+        self.items == other.as_list().items
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct MapNode {
+    pub line: u32,
+    pub column: u32,
+    pub items: Vec<(Rc<dyn AstNode>, Rc<dyn AstNode>)>,
+}
+
+impl MapNode {
+    pub fn new(line: u32, column: u32, items: Vec<(Rc<dyn AstNode>, Rc<dyn AstNode>)>) -> MapNode {
+        MapNode {
+            line,
+            column,
+            items,
+        }
+    }
+}
+
+impl AstNode for MapNode {
+    fn get_type(&self) -> NodeType {
+        NodeType::Map
+    }
+
+    fn as_sequence(&self) -> SequenceNode {
+        unimplemented!()
+    }
+
+    fn as_call_func(&self) -> CallFuncNode {
+        unimplemented!()
+    }
+
+    fn as_str_const(&self) -> ConstStrNode {
+        unimplemented!()
+    }
+
+    fn as_int_const(&self) -> ConstIntNode {
+        unimplemented!()
+    }
+
+    fn as_float_const(&self) -> ConstFloatNode {
+        unimplemented!()
+    }
+
+    fn as_variable(&self) -> VariableNode {
+        unimplemented!()
+    }
+
+    fn as_list(&self) -> ListNode {
+        unimplemented!()
+    }
+
+    fn as_map(&self) -> MapNode {
+        self.to_owned()
+    }
+
+    fn print(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Map([")?;
+
+        for (i, (key, value)) in self.items.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+
+            key.print(f)?;
+            f.write_str(" = ")?;
+            value.print(f)?;
+        }
+
+        f.write_str("])")
+    }
+
+    fn equals(&self, other: &dyn AstNode) -> bool {
+        if self.get_type() != other.get_type() {
+            return false;
+        }
+
+        self.items == other.as_map().items
+    }
+}
+
+#[repr(u8)]
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum NativeExceptionCode {
+    Other,
+    PermissionDenied,
+    Nondeterministic,
+}
+
 #[derive(Debug, Clone)]
 pub struct NativeException {
     pub line: u32,
     pub column: u32,
     pub description: String,
+    pub code: NativeExceptionCode,
 }
 
 impl NativeException {
@@ -681,34 +1572,75 @@ impl NativeException {
             line,
             column,
             description: description.to_string(),
+            code: NativeExceptionCode::Other,
+        }
+    }
+
+    pub fn with_code(
+        line: u32,
+        column: u32,
+        description: &str,
+        code: NativeExceptionCode,
+    ) -> NativeException {
+        NativeException {
+            line,
+            column,
+            description: description.to_string(),
+            code,
         }
     }
+
+    pub fn permission_denied(line: u32, column: u32, description: &str) -> NativeException {
+        NativeException::with_code(
+            line,
+            column,
+            &format!("Permission denied: {}", description),
+            NativeExceptionCode::PermissionDenied,
+        )
+    }
+
+    pub fn nondeterministic(line: u32, column: u32, description: &str) -> NativeException {
+        NativeException::with_code(
+            line,
+            column,
+            &format!("Nondeterministic call: {}", description),
+            NativeExceptionCode::Nondeterministic,
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Scope {
-    variables: HashMap<String, Rc<dyn Variant>>,
-    functions: HashMap<String, Function>,
+    variables: BTreeMap<String, Rc<dyn Variant>>,
+    functions: BTreeMap<String, Function>,
     parent_scope: Option<*const Scope>,
     destructors: Vec<fn(&mut Scope)>,
+    /// Shared by root scope, scopes created from it and clones.
+    engine: Rc<RefCell<Engine>>,
 }
 
 impl Scope {
+    /// Variables and functions may be passed as `HashMap`, `BTreeMap` or any other pairs,
+    /// scope keeps them sorted by name. Scope with parent uses engine of parent.
     pub fn new(
-        variables: HashMap<String, Rc<dyn Variant>>,
-        functions: HashMap<String, Function>,
+        variables: impl IntoIterator<Item = (String, Rc<dyn Variant>)>,
+        functions: impl IntoIterator<Item = (String, Function)>,
         parent: Option<*const Scope>,
     ) -> Scope {
         Scope {
-            variables,
-            functions,
+            variables: variables.into_iter().collect(),
+            functions: functions.into_iter().collect(),
             parent_scope: parent,
             destructors: Vec::new(),
+            engine: match parent {
+                Some(parent) => unsafe { (*parent).engine.clone() },
+                None => Rc::new(RefCell::new(Engine::new())),
+            },
         }
     }
 
     pub fn empty() -> Scope {
-        Scope::new(HashMap::new(), HashMap::new(), None)
+        Scope::new(BTreeMap::new(), BTreeMap::new(), None)
     }
 
     #[cfg(feature = "stdlib")]
@@ -724,9 +1656,11 @@ impl Scope {
     }
 
     pub fn from_scope(scope: &Scope) -> Scope {
-        let mut new_scope = Scope::empty();
-        new_scope.parent_scope = Some(scope as *const Scope);
-        new_scope
+        Scope::new(
+            BTreeMap::new(),
+            BTreeMap::new(),
+            Some(scope as *const Scope),
+        )
     }
 
     pub fn has_variable(&self, name: &str) -> bool {
@@ -749,13 +1683,15 @@ impl Scope {
         None
     }
 
-    pub fn get_variables(&self) -> HashMap<String, Rc<dyn Variant>> {
-        let mut variables = self.variables.clone();
+    /// Gets variables of this scope and its parents, sorted by name.
+    pub fn get_variables(&self) -> BTreeMap<String, Rc<dyn Variant>> {
+        let mut variables = BTreeMap::new();
 
         if self.parent_scope.is_some() {
-            variables.extend(unsafe { (*self.parent_scope.unwrap_unchecked()).get_variables() })
+            variables = unsafe { (*self.parent_scope.unwrap_unchecked()).get_variables() };
         }
 
+        variables.extend(self.variables.clone());
         variables
     }
 
@@ -783,13 +1719,15 @@ impl Scope {
         None
     }
 
-    pub fn get_functions(&self) -> HashMap<String, Function> {
-        let mut functions = self.functions.clone();
+    /// Gets functions of this scope and its parents, sorted by name.
+    pub fn get_functions(&self) -> BTreeMap<String, Function> {
+        let mut functions = BTreeMap::new();
 
         if self.parent_scope.is_some() {
-            functions.extend(unsafe { (*self.parent_scope.unwrap_unchecked()).get_functions() })
+            functions = unsafe { (*self.parent_scope.unwrap_unchecked()).get_functions() };
         }
 
+        functions.extend(self.functions.clone());
         functions
     }
 
@@ -800,6 +1738,15 @@ impl Scope {
     pub fn add_destructor(&mut self, destructor: fn(&mut Scope)) {
         self.destructors.push(destructor)
     }
+
+    /// Engine shouldn't be kept borrowed while scripts run, they may change it.
+    pub fn get_engine(&self) -> Ref<'_, Engine> {
+        self.engine.borrow()
+    }
+
+    pub fn get_engine_mut(&mut self) -> RefMut<'_, Engine> {
+        self.engine.borrow_mut()
+    }
 }
 
 impl Drop for Scope {
@@ -821,6 +1768,9 @@ pub enum TokenType {
     Rparen,
     Lbrace,
     Rbrace,
+    Lbracket,
+    Rbracket,
+    Equals,
     Comma,
     _CommentUnknown,
     _CommentLine,
@@ -0,0 +1,54 @@
+use easy_prog::types::{Scope, Type};
+
+#[test]
+fn test_bool_conditions() {
+    let mut scope = Scope::with_stdlib();
+
+    assert_eq!(eval!(scope, "lt(1, 2)").get_type(), Type::Bool);
+    assert_eq!(
+        format!("{:?}", eval!(scope, "and(true, not(false))")),
+        "true"
+    );
+    assert!(eval!(scope, "eq(gt(1, 2), false)").as_bool().value);
+
+    eval!(scope, "set(\"flag\", true)");
+    eval!(scope, "null(\"flag\")");
+    assert!(!scope.get_variable("flag").unwrap().as_bool().value);
+
+    eval!(scope, "if(true, {set(\"branch\", \"if\")})");
+    assert_eq!(scope.get_variable("branch").unwrap().as_str().text, "if");
+    eval!(
+        scope,
+        "if_else(0, {set(\"branch\", \"if\")}, {set(\"branch\", \"else\")})"
+    );
+    assert_eq!(scope.get_variable("branch").unwrap().as_str().text, "else");
+    assert_eq!(
+        error!(scope, "if(\"yes\", {print()})"),
+        "First argument of this function should be `Bool(condition)` or `Int(condition)`"
+    );
+
+    assert!(!eval!(scope, "to_bool(\"\")").as_bool().value);
+    assert!(eval!(scope, "to_bool(\"0\")").as_bool().value);
+    assert!(!eval!(scope, "to_bool(0.0)").as_bool().value);
+    assert!(!eval!(scope, "to_bool([])").as_bool().value);
+    assert!(eval!(scope, "to_bool([1 = 1])").as_bool().value);
+    assert!(eval!(scope, "to_bool([0])").as_bool().value);
+    assert!(!eval!(scope, "to_bool(print())").as_bool().value);
+    assert!(eval!(scope, "to_bool({print()})").as_bool().value);
+
+    // Strict mode rejects `Int` conditions.
+    scope.get_engine_mut().set_strict_conditions(true);
+    assert_eq!(
+        error!(scope, "if(1, {print()})"),
+        "First argument of this function should be `Bool(condition)`"
+    );
+    assert_eq!(
+        error!(scope, "and(true, 0)"),
+        "Second argument of this function should be `Bool(condition)`"
+    );
+    eval!(scope, "if(to_bool(1), {set(\"branch\", \"strict\")})");
+    assert_eq!(
+        scope.get_variable("branch").unwrap().as_str().text,
+        "strict"
+    );
+}
@@ -0,0 +1,53 @@
+use easy_prog::types::Scope;
+
+#[test]
+fn test_convert_functions() {
+    let mut scope = Scope::with_stdlib();
+
+    assert_eq!(eval!(scope, "type_of(1)").as_str().text, "Int");
+    assert_eq!(eval!(scope, "type_of(\"1\")").as_str().text, "Str");
+    assert_eq!(eval!(scope, "type_of([1 = 1])").as_str().text, "Map");
+    assert_eq!(eval!(scope, "type_of(true)").as_str().text, "Bool");
+    assert_eq!(eval!(scope, "type_of({print()})").as_str().text, "Func");
+    assert_eq!(eval!(scope, "type_of(print())").as_str().text, "Void");
+    assert!(eval!(scope, "is_int(1)").as_bool().value);
+    assert!(!eval!(scope, "is_int(1.0)").as_bool().value);
+    assert!(eval!(scope, "is_str(\"\")").as_bool().value);
+    assert!(eval!(scope, "is_func({print()})").as_bool().value);
+    assert!(eval!(scope, "is_void(print())").as_bool().value);
+    assert!(!eval!(scope, "is_custom([])").as_bool().value);
+
+    assert_eq!(eval!(scope, "to_str(42)").as_str().text, "42");
+    assert_eq!(eval!(scope, "to_str(2.0)").as_str().text, "2.0");
+    assert_eq!(eval!(scope, "to_str(\"text\")").as_str().text, "text");
+    assert_eq!(
+        eval!(scope, "to_str([\"a\", [1 = 2]])").as_str().text,
+        "[\"a\", [1 = 2]]"
+    );
+    assert_eq!(eval!(scope, "to_str(print())").as_str().text, "<null>");
+
+    assert_eq!(eval!(scope, "to_int(\"-17\")").as_int().number, -17);
+    assert_eq!(eval!(scope, "to_int(-2.9)").as_int().number, -2);
+    assert_eq!(eval!(scope, "to_int(true)").as_int().number, 1);
+    assert_eq!(error!(scope, "to_int(\"1.5\")"), "Invalid number string");
+    assert_eq!(
+        error!(scope, "to_int(1e19)"),
+        "Number is NaN or out of `Int` range"
+    );
+    assert_eq!(
+        error!(scope, "to_int([])"),
+        "First argument of this function should be `Int(value)`, `Float(value)`, `Bool(value)` or `Str(value)`"
+    );
+
+    assert_eq!(eval!(scope, "parse_int(\"ff\", 16)").as_int().number, 255);
+    assert_eq!(eval!(scope, "parse_int(\"-101\", 2)").as_int().number, -5);
+    assert_eq!(eval!(scope, "parse_int(\"z\", 36)").as_int().number, 35);
+    assert_eq!(
+        error!(scope, "parse_int(\"2\", 2)"),
+        "Invalid number string"
+    );
+    assert_eq!(
+        error!(scope, "parse_int(\"1\", 37)"),
+        "Radix should be from 2 to 36"
+    );
+}
@@ -0,0 +1,175 @@
+use std::cell::RefCell;
+use std::io::{Cursor, Write};
+use std::rc::Rc;
+
+use easy_prog::debugger::{debug_file_ex, DebugAction, Debugger, PauseReason};
+use easy_prog::runner::run_file_scope;
+use easy_prog::types::Scope;
+
+const SCRIPT: &str = "declfunc(\"bump\", {
+    set(\"count\", add(count, 1)),
+    set(\"last\", count)
+}),
+
+set(\"count\", 0),
+bump(),
+if(true, {
+    bump()
+}),
+set(\"done\", true)
+";
+
+fn write_script(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!(
+        "easy_prog_debugger_{}_{}.ep",
+        name,
+        std::process::id()
+    ));
+    std::fs::write(&path, SCRIPT).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+/// Runs script with debugger, that answers pauses with `actions` and records position of each pause.
+fn debug_script(name: &str, breakpoints: &[u32], actions: Vec<DebugAction>) -> Vec<String> {
+    let path = write_script(name);
+    let pauses = Rc::new(RefCell::new(Vec::new()));
+    let recorded = pauses.clone();
+    let mut actions = actions.into_iter();
+    let debugger = Rc::new(Debugger::new(move |context| {
+        let frame = context.get_frame().unwrap();
+        recorded.borrow_mut().push(format!(
+            "{}:{} {}/{}",
+            frame.name,
+            frame.line,
+            context.get_call_stack().len(),
+            if context.get_reason() == PauseReason::Breakpoint {
+                "breakpoint"
+            } else {
+                "step"
+            }
+        ));
+        actions.next().unwrap_or(DebugAction::Continue)
+    }));
+
+    for line in breakpoints {
+        let file = format!("easy_prog_debugger_{}_{}.ep", name, std::process::id());
+        assert!(debugger.add_breakpoint(&file, *line));
+    }
+
+    if breakpoints.is_empty() {
+        debugger.pause();
+    }
+
+    let mut scope = Scope::with_stdlib();
+    scope.get_engine_mut().set_debug_hook(Some(debugger));
+    assert!(run_file_scope(&path, &mut scope));
+    std::fs::remove_file(&path).unwrap();
+    assert!(scope.get_engine().get_call_stack().is_empty());
+    let result = pauses.borrow().clone();
+    result
+}
+
+#[test]
+fn test_debugger_breakpoints() {
+    assert_eq!(
+        debug_script("breakpoints", &[3, 11], Vec::new()),
+        [
+            "bump:3 2/breakpoint",
+            "bump:3 3/breakpoint",
+            "<main>:11 1/breakpoint"
+        ]
+    );
+}
+
+#[test]
+fn test_debugger_steps() {
+    use DebugAction::*;
+
+    assert_eq!(
+        debug_script(
+            "steps",
+            &[],
+            vec![StepOver, StepOver, StepInto, StepOut, StepInto, StepInto, StepOver]
+        ),
+        [
+            "<main>:1 1/step",
+            "<main>:6 1/step",
+            "<main>:7 1/step",
+            "bump:2 2/step",
+            "<main>:8 1/step",
+            "if:9 2/step",
+            "bump:2 3/step",
+            "bump:3 3/step"
+        ]
+    );
+}
+
+#[test]
+fn test_debugger_evaluate() {
+    let path = write_script("evaluate");
+    let results = Rc::new(RefCell::new(Vec::new()));
+    let recorded = results.clone();
+    let debugger = Rc::new(Debugger::new(move |context| {
+        let locals = context.get_locals();
+        recorded.borrow_mut().push(locals["count"].as_int().number);
+        let value = context.evaluate("add(count, 10)").unwrap().unwrap();
+        recorded.borrow_mut().push(value.as_int().number);
+        context.evaluate("set(\"count\", 100)").unwrap().unwrap();
+        assert!(context.evaluate("count)").is_err());
+        assert!(context.evaluate("1, 2").is_err());
+        DebugAction::Stop
+    }));
+    debugger.add_breakpoint(&path, 3);
+
+    let mut scope = Scope::with_stdlib();
+    scope.get_engine_mut().set_debug_hook(Some(debugger));
+    assert!(!run_file_scope(&path, &mut scope));
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(*results.borrow(), [1, 11]);
+    assert_eq!(scope.get_variable("count").unwrap().as_int().number, 100);
+    assert!(scope.get_variable("done").is_none());
+}
+
+#[derive(Clone, Default)]
+struct SharedOutput(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedOutput {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_debugger_console() {
+    let path = write_script("console");
+    let output = SharedOutput::default();
+    let input = Cursor::new("break 9\ncontinue\nbt\np count\nl\nnext\nfoo\nc\n");
+    let mut scope = Scope::with_stdlib();
+    assert!(debug_file_ex(&path, &mut scope, input, output.clone()));
+    std::fs::remove_file(&path).unwrap();
+    let output = String::from_utf8(output.0.borrow().clone()).unwrap();
+    let expected = [
+        format!("Paused at {}:1:1 in <main>", path),
+        "    1 | declfunc(\"bump\", {".to_string(),
+        format!("(debug) Breakpoint at {}:9", path),
+        format!("(debug) Breakpoint at {}:9:5 in if", path),
+        "    9 |     bump()".to_string(),
+        format!("(debug) #0 if at {}:9:5", path),
+        format!("#1 <main> at {}:8:1", path),
+        "(debug) 1".to_string(),
+        "(debug) count = 1".to_string(),
+        "false = false".to_string(),
+        "last = 1".to_string(),
+        "true = true".to_string(),
+        format!("(debug) Paused at {}:11:1 in <main>", path),
+        "   11 | set(\"done\", true)".to_string(),
+        "(debug) Unknown command 'foo', type 'help' for list of commands".to_string(),
+        "(debug) ".to_string(),
+    ];
+    assert_eq!(output.split('\n').collect::<Vec<&str>>(), expected);
+    assert!(scope.get_engine().get_debug_hook().is_none());
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use easy_prog::clock::ManualClock;
+use easy_prog::engine::Policy;
+use easy_prog::filesystem::MemoryFileSystem;
+use easy_prog::runner::run_line_scope;
+use easy_prog::types::{Int, NativeExceptionCode, Scope, Variant};
+
+#[test]
+fn test_scope_order() {
+    let mut parent = Scope::empty();
+    parent.set_variable("b", Rc::new(Int::new(1)));
+    parent.set_variable("a", Rc::new(Int::new(2)));
+    let mut child = Scope::from_scope(&parent);
+    child.set_variable("c", Rc::new(Int::new(3)));
+    child.set_variable("b", Rc::new(Int::new(4)));
+
+    let variables = child.get_variables();
+    let names: Vec<&String> = variables.keys().collect();
+    assert_eq!(names, ["a", "b", "c"]);
+    assert_eq!(variables["b"].as_int().number, 4);
+
+    let scope = Scope::with_stdlib();
+    let names: Vec<String> = scope.get_functions().into_keys().collect();
+    let mut sorted = names.clone();
+    sorted.sort();
+    assert_eq!(names, sorted);
+}
+
+#[test]
+fn test_deterministic_mode() {
+    let mut scope = Scope::with_stdlib();
+    scope.get_engine_mut().set_policy(Policy::allow_all());
+    scope.get_engine_mut().set_deterministic(true);
+
+    for (code, name) in [
+        ("input()", "input"),
+        ("env_get(\"PATH\")", "env_get"),
+        ("env_vars()", "env_vars"),
+        ("run_process(\"sh\")", "run_process"),
+    ] {
+        let error = run_line_scope(code, &mut scope).unwrap().unwrap_err();
+        assert_eq!(error.code, NativeExceptionCode::Nondeterministic);
+        assert_eq!(
+            error.description,
+            format!(
+                "Nondeterministic call: `{}` is not allowed in deterministic mode",
+                name
+            )
+        );
+    }
+
+    let error = run_line_scope("now_ms()", &mut scope).unwrap().unwrap_err();
+    assert_eq!(error.code, NativeExceptionCode::Nondeterministic);
+    assert_eq!(
+        error.description,
+        "Nondeterministic call: `now_ms` requires deterministic clock in deterministic mode"
+    );
+
+    scope
+        .get_engine_mut()
+        .set_clock(Rc::new(ManualClock::new(1000)));
+    run_line_scope("sleep_ms(10)", &mut scope).unwrap().unwrap();
+    assert_eq!(
+        run_line_scope("now_ms()", &mut scope)
+            .unwrap()
+            .unwrap()
+            .as_int()
+            .number,
+        1010
+    );
+    run_line_scope("rand_int(1, 6)", &mut scope)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        run_line_scope("to_str([{print()}])", &mut scope)
+            .unwrap()
+            .unwrap()
+            .as_str()
+            .text,
+        "[Function(SequenceNode { line: 1, column: 9, body: [CallFunc(\"print\", [])] })]"
+    );
+    assert_eq!(
+        run_line_scope("to_str({print()})", &mut scope)
+            .unwrap()
+            .unwrap()
+            .as_str()
+            .text,
+        "<function>"
+    );
+
+    for (code, name) in [
+        ("fopen(\"a.txt\", \"r\")", "fopen"),
+        ("exists(\"a.txt\")", "exists"),
+        ("file_mtime(\"a.txt\")", "file_mtime"),
+        ("import(\"module\")", "import"),
+    ] {
+        let error = run_line_scope(code, &mut scope).unwrap().unwrap_err();
+        assert_eq!(error.code, NativeExceptionCode::Nondeterministic);
+        assert_eq!(
+            error.description,
+            format!(
+                "Nondeterministic call: `{}` requires deterministic file system in deterministic mode",
+                name
+            )
+        );
+    }
+
+    let file_system = MemoryFileSystem::new();
+    file_system.add_file("/a.txt", b"data");
+    scope.get_engine_mut().set_file_system(Rc::new(file_system));
+    assert!(
+        run_line_scope("exists(\"a.txt\")", &mut scope)
+            .unwrap()
+            .unwrap()
+            .as_bool()
+            .value
+    );
+
+    scope.get_engine_mut().set_deterministic(false);
+    assert!(run_line_scope("to_str({print()})", &mut scope)
+        .unwrap()
+        .unwrap()
+        .as_str()
+        .text
+        .starts_with("<function at address "));
+}
+
+#[test]
+fn test_scope_new() {
+    let mut variables: HashMap<String, Rc<dyn Variant>> = HashMap::new();
+    variables.insert("b".to_string(), Rc::new(Int::new(1)));
+    variables.insert("a".to_string(), Rc::new(Int::new(2)));
+    let scope = Scope::new(variables, HashMap::new(), None);
+    let names: Vec<String> = scope.get_variables().into_keys().collect();
+    assert_eq!(names, ["a", "b"]);
+}
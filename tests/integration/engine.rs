@@ -0,0 +1,104 @@
+use easy_prog::engine::{Permission, Policy};
+use easy_prog::filesystem::{JailFileSystem, MemoryFileSystem, OsFileSystem};
+use easy_prog::runner::run_line_scope;
+use easy_prog::types::{NativeExceptionCode, Scope};
+
+#[test]
+fn test_policy_permissions() {
+    let mut policy = Policy::deny_all();
+    assert!(!policy.is_allowed(Permission::Stdio));
+    policy.allow(Permission::Stdio).allow(Permission::FileRead);
+    assert!(policy.is_allowed(Permission::Stdio));
+    assert!(policy.is_allowed(Permission::FileRead));
+    assert!(!policy.is_allowed(Permission::FileWrite));
+    policy.deny(Permission::Stdio);
+    assert!(!policy.is_allowed(Permission::Stdio));
+}
+
+#[test]
+fn test_policy_paths() {
+    let os = OsFileSystem::new();
+    let mut policy = Policy::allow_all();
+    assert!(policy.is_path_allowed(&os, "/etc/passwd"));
+    policy.allow_path("examples");
+    assert!(policy.is_path_allowed(&os, "examples/hello_world.ep"));
+    assert!(policy.is_path_allowed(&os, "./examples/../examples/file.ep"));
+    assert!(!policy.is_path_allowed(&os, "examples/../Cargo.toml"));
+    assert!(!policy.is_path_allowed(&os, "/etc/passwd"));
+    assert!(!policy.is_path_allowed(&os, "examples_other/file.ep"));
+}
+
+#[test]
+fn test_permission_denied() {
+    let mut scope = Scope::with_stdlib();
+    scope.get_engine_mut().set_policy(Policy::deny_all());
+    let error = run_line_scope("print(\"Hello\")", &mut scope)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(error.code, NativeExceptionCode::PermissionDenied);
+    assert_eq!(error.description, "Permission denied: stdio is not allowed");
+
+    scope
+        .get_engine_mut()
+        .get_policy_mut()
+        .allow(Permission::FileRead)
+        .allow_path("examples");
+    let error = run_line_scope("fopen(\"/etc/passwd\", \"r\")", &mut scope)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(error.code, NativeExceptionCode::PermissionDenied);
+    assert_eq!(
+        error.description,
+        "Permission denied: access to '/etc/passwd' is not allowed"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_policy_symlink_paths() {
+    let root = std::env::temp_dir().join(format!("easy_prog_policy_{}", std::process::id()));
+    std::fs::create_dir_all(root.join("allowed")).unwrap();
+    std::fs::create_dir_all(root.join("outside/deep")).unwrap();
+    std::fs::write(root.join("outside/secret"), "secret").unwrap();
+    std::os::unix::fs::symlink(root.join("outside/deep"), root.join("allowed/link")).unwrap();
+
+    let os = OsFileSystem::new();
+    let mut policy = Policy::allow_all();
+    policy.allow_path(root.join("allowed").to_str().unwrap());
+    let path = |path: &str| root.join(path).to_str().unwrap().to_string();
+    assert!(policy.is_path_allowed(&os, &path("allowed/file")));
+    assert!(!policy.is_path_allowed(&os, &path("allowed/link/file")));
+    assert!(!policy.is_path_allowed(&os, &path("allowed/link/../secret")));
+    assert!(!policy.is_path_allowed(&os, &path("allowed/link/../../outside/secret")));
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_policy_virtual_paths() {
+    let memory = MemoryFileSystem::new();
+    let mut policy = Policy::allow_all();
+    policy.allow_path("/data");
+    assert!(policy.is_path_allowed(&memory, "/data/file"));
+    assert!(policy.is_path_allowed(&memory, "data/../data/file"));
+    assert!(!policy.is_path_allowed(&memory, "/data/../etc/passwd"));
+    assert!(!policy.is_path_allowed(&memory, "/data_other/file"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_policy_jail_paths() {
+    let root = std::env::temp_dir().join(format!("easy_prog_policy_jail_{}", std::process::id()));
+    std::fs::create_dir_all(root.join("allowed")).unwrap();
+    std::fs::create_dir_all(root.join("secret")).unwrap();
+    std::os::unix::fs::symlink(root.join("secret"), root.join("allowed/link")).unwrap();
+
+    let jail = JailFileSystem::new(root.to_str().unwrap());
+    let mut policy = Policy::allow_all();
+    policy.allow_path("/allowed");
+    assert!(policy.is_path_allowed(&jail, "/allowed/file"));
+    assert!(policy.is_path_allowed(&jail, "allowed/file"));
+    assert!(policy.is_path_allowed(&jail, "/../allowed/file"));
+    assert!(!policy.is_path_allowed(&jail, "/allowed/link/file"));
+    assert!(!policy.is_path_allowed(&jail, "/secret/file"));
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,91 @@
+use std::env;
+
+use easy_prog::engine::{Permission, Policy};
+use easy_prog::runner::run_line_scope;
+use easy_prog::types::{MapKey, NativeExceptionCode, Scope};
+
+#[test]
+fn test_script_args() {
+    let mut scope = Scope::with_stdlib();
+    assert_eq!(
+        run_line_scope("argc()", &mut scope)
+            .unwrap()
+            .unwrap()
+            .as_int()
+            .number,
+        0
+    );
+
+    scope
+        .get_engine_mut()
+        .set_script_args(vec!["first".to_string(), "second arg".to_string()]);
+    assert_eq!(
+        run_line_scope("argc()", &mut scope)
+            .unwrap()
+            .unwrap()
+            .as_int()
+            .number,
+        2
+    );
+    assert_eq!(
+        run_line_scope("argv(1)", &mut scope)
+            .unwrap()
+            .unwrap()
+            .as_str()
+            .text,
+        "second arg"
+    );
+    assert_eq!(
+        run_line_scope("argv(2)", &mut scope)
+            .unwrap()
+            .unwrap_err()
+            .description,
+        "Index out of range"
+    );
+}
+
+#[test]
+fn test_env_functions() {
+    let mut scope = Scope::with_stdlib();
+    let path = env::var("PATH").unwrap();
+
+    assert_eq!(eval!(scope, "env_get(\"PATH\")").as_str().text, path);
+    assert!(
+        eval!(scope, "is_void(env_get(\"EASY_PROG_MISSING_VARIABLE\"))")
+            .as_bool()
+            .value
+    );
+    assert_eq!(
+        eval!(
+            scope,
+            "env_get(\"EASY_PROG_MISSING_VARIABLE\", \"default\")"
+        )
+        .as_str()
+        .text,
+        "default"
+    );
+    assert!(eval!(scope, "is_void(env_get(\"A=B\"))").as_bool().value);
+
+    let vars = eval!(scope, "env_vars()").as_map();
+    let items = vars.items.borrow();
+    assert_eq!(
+        items
+            .get(&MapKey::Str("PATH".to_string()))
+            .unwrap()
+            .as_str()
+            .text,
+        path
+    );
+
+    let mut policy = Policy::allow_all();
+    policy.deny(Permission::Env);
+    scope.get_engine_mut().set_policy(policy);
+    let error = run_line_scope("env_vars()", &mut scope)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(error.code, NativeExceptionCode::PermissionDenied);
+    assert_eq!(
+        error.description,
+        "Permission denied: environment is not allowed"
+    );
+}
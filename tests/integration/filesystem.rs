@@ -0,0 +1,180 @@
+use easy_prog::filesystem::{FileSystem, JailFileSystem, MemoryFileSystem, OpenMode};
+use easy_prog::runner::run_line_scope;
+use easy_prog::types::Scope;
+use std::rc::Rc;
+
+#[test]
+fn test_open_mode() {
+    let mode = OpenMode::parse("a+").unwrap();
+    assert!(mode.read && mode.write && mode.append && mode.create && !mode.truncate);
+    assert_eq!(OpenMode::parse("rb"), OpenMode::parse("r"));
+    assert!(OpenMode::parse("x").is_none());
+    assert!(OpenMode::parse("r++").is_none());
+}
+
+#[test]
+fn test_memory_file_system() {
+    let file_system = MemoryFileSystem::new();
+    file_system.add_file("/data/config.txt", b"speed=5");
+    let mut scope = Scope::with_stdlib();
+    scope
+        .get_engine_mut()
+        .set_file_system(Rc::new(file_system.clone()));
+
+    let content = run_line_scope("fread(fopen(\"data/config.txt\", \"r\"))", &mut scope)
+        .unwrap()
+        .unwrap();
+    assert_eq!(content.as_str().text, "speed=5");
+
+    run_line_scope(
+        "fwrite(fopen(\"/save.txt\", \"w\"), \"level=2\")",
+        &mut scope,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(file_system.get_file("/save.txt").unwrap(), b"level=2");
+    assert!(run_line_scope("fopen(\"/missing.txt\", \"r\")", &mut scope)
+        .unwrap()
+        .is_err());
+}
+
+#[test]
+fn test_jail_file_system() {
+    let file_system = JailFileSystem::new("examples");
+    assert!(file_system
+        .open("/hello_world.ep", OpenMode::parse("r").unwrap())
+        .is_ok());
+    assert!(file_system
+        .open("../Cargo.toml", OpenMode::parse("r").unwrap())
+        .is_err());
+    assert!(file_system
+        .open("project/../../Cargo.toml", OpenMode::parse("r").unwrap())
+        .is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_jail_file_system_symlinks() {
+    let base = std::env::temp_dir().join(format!("easy_prog_jail_{}", std::process::id()));
+    let root = base.join("root");
+    let outside = base.join("outside");
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::create_dir_all(&outside).unwrap();
+    std::fs::write(root.join("inside.txt"), "inside").unwrap();
+    std::os::unix::fs::symlink(&outside, root.join("escape")).unwrap();
+    std::os::unix::fs::symlink(outside.join("missing.txt"), root.join("dangling")).unwrap();
+
+    let file_system = JailFileSystem::new(root.to_str().unwrap());
+    assert!(file_system
+        .open("escape/new.txt", OpenMode::parse("w").unwrap())
+        .is_err());
+    assert!(file_system
+        .open("dangling", OpenMode::parse("w").unwrap())
+        .is_err());
+    assert!(file_system.create_dir("escape/sub/dir").is_err());
+    assert!(file_system
+        .rename("inside.txt", "escape/moved.txt")
+        .is_err());
+    assert!(file_system.create_dir("sub/dir").is_ok());
+    assert!(file_system
+        .open("sub/dir/new.txt", OpenMode::parse("w").unwrap())
+        .is_ok());
+
+    let escaped = std::fs::read_dir(&outside).unwrap().count();
+    std::fs::remove_dir_all(&base).unwrap();
+    assert_eq!(escaped, 0);
+}
+
+#[test]
+fn test_file_handle_functions() {
+    let file_system = MemoryFileSystem::new();
+    file_system.add_file("/log.txt", b"first\r\nsecond\nthird");
+    let mut scope = Scope::with_stdlib();
+    scope
+        .get_engine_mut()
+        .set_file_system(Rc::new(file_system.clone()));
+    run_line_scope("set(\"file\", fopen(\"log.txt\", \"r\"))", &mut scope)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(eval!(scope, "fread_line(file)").as_str().text, "first");
+    assert_eq!(eval!(scope, "ftell(file)").as_int().number, 7);
+    assert_eq!(eval!(scope, "fread_bytes(file, 3)").as_str().text, "sec");
+    assert_eq!(eval!(scope, "fread_line(file)").as_str().text, "ond");
+    assert!(!eval!(scope, "feof(file)").as_bool().value);
+    assert_eq!(eval!(scope, "fread_line(file)").as_str().text, "third");
+    assert!(eval!(scope, "feof(file)").as_bool().value);
+    assert_eq!(
+        eval!(scope, "fread_line(file)").get_type(),
+        easy_prog::types::Type::Void
+    );
+    assert_eq!(eval!(scope, "fseek(file, -5, \"end\")").as_int().number, 14);
+    assert_eq!(eval!(scope, "fread_line(file)").as_str().text, "third");
+    eval!(scope, "fclose(file)");
+    assert_eq!(
+        run_line_scope("fread_line(file)", &mut scope)
+            .unwrap()
+            .unwrap_err()
+            .description,
+        "File is closed"
+    );
+
+    eval!(scope, "fwrite(fopen(\"log.txt\", \"a\"), \"!\")");
+    eval!(
+        scope,
+        "fwrite_bytes(fopen(\"bin\", \"w\"), fread_bytes(fopen(\"log.txt\", \"r\"), 2))"
+    );
+    assert_eq!(
+        file_system.get_file("/log.txt").unwrap(),
+        b"first\r\nsecond\nthird!"
+    );
+    assert_eq!(file_system.get_file("/bin").unwrap(), b"fi");
+}
+
+#[test]
+fn test_fs_functions() {
+    let file_system = MemoryFileSystem::new();
+    file_system.add_file("/data/a.txt", b"hello");
+    let mut scope = Scope::with_stdlib();
+    scope
+        .get_engine_mut()
+        .set_file_system(Rc::new(file_system.clone()));
+
+    assert!(eval!(scope, "exists(\"data/a.txt\")").as_bool().value);
+    assert!(!eval!(scope, "exists(\"data/b.txt\")").as_bool().value);
+    assert!(eval!(scope, "is_dir(\"data\")").as_bool().value);
+    assert_eq!(eval!(scope, "file_size(\"data/a.txt\")").as_int().number, 5);
+    eval!(scope, "mkdir(\"data/sub/inner\")");
+    eval!(scope, "rename(\"data/a.txt\", \"data/sub/b.txt\")");
+    assert_eq!(eval!(scope, "len(list_dir(\"data\"))").as_int().number, 1);
+    assert_eq!(
+        eval!(scope, "to_str(list_dir(\"data/sub\"))").as_str().text,
+        "[\"b.txt\", \"inner\"]"
+    );
+    eval!(scope, "remove(\"data/sub/inner\")");
+    assert!(!eval!(scope, "is_dir(\"data/sub/inner\")").as_bool().value);
+    assert_eq!(file_system.get_file("/data/sub/b.txt").unwrap(), b"hello");
+    assert!(run_line_scope("remove(\"data\")", &mut scope)
+        .unwrap()
+        .is_err());
+
+    assert_eq!(
+        eval!(scope, "path_join(\"dir\", \"sub\", \"file.ep\")")
+            .as_str()
+            .text,
+        "dir/sub/file.ep"
+    );
+    assert_eq!(
+        eval!(scope, "basename(\"dir/file.ep\")").as_str().text,
+        "file.ep"
+    );
+    assert_eq!(
+        eval!(scope, "dirname(\"dir/file.ep\")").as_str().text,
+        "dir"
+    );
+    assert_eq!(
+        eval!(scope, "extension(\"dir/file.ep\")").as_str().text,
+        "ep"
+    );
+    assert_eq!(eval!(scope, "extension(\"dir/file\")").as_str().text, "");
+}
@@ -0,0 +1,121 @@
+use easy_prog::stdlib::{json_to_value, to_display_string};
+use easy_prog::types::{MapKey, Scope};
+
+#[test]
+fn test_json_parse() {
+    let value = json_to_value(
+        0,
+        0,
+        "{\"name\": \"easy\", \"tags\": [1, 2.5, -3e2, true, null], \"nested\": {}}",
+    )
+    .unwrap();
+    let map = value.as_map();
+    let items = map.items.borrow();
+    let keys: Vec<&MapKey> = items.entries().iter().map(|(key, _)| key).collect();
+    assert_eq!(
+        keys,
+        [
+            &MapKey::Str("name".to_string()),
+            &MapKey::Str("tags".to_string()),
+            &MapKey::Str("nested".to_string())
+        ]
+    );
+    assert_eq!(
+        items
+            .get(&MapKey::Str("name".to_string()))
+            .unwrap()
+            .as_str()
+            .text,
+        "easy"
+    );
+
+    let tags = items.get(&MapKey::Str("tags".to_string())).unwrap();
+    assert_eq!(to_display_string(tags), "[1, 2.5, -300.0, true, Void]");
+    assert_eq!(tags.as_list().items.borrow()[0].as_int().number, 1);
+
+    let text = json_to_value(0, 0, "\"a\\n\\u00e9\\ud83d\\ude00\\/\"").unwrap();
+    assert_eq!(text.as_str().text, "a\né😀/");
+
+    let error = |json: &str| json_to_value(0, 0, json).unwrap_err().description;
+    assert_eq!(
+        error("{\n  \"a\": 1,\n  \"b\" 2\n}"),
+        "Invalid JSON on line 3 column 7: Unexpected character '2'"
+    );
+    assert_eq!(
+        error("[1, 2,]"),
+        "Invalid JSON on line 1 column 7: Unexpected character ']'"
+    );
+    assert_eq!(
+        error("[1"),
+        "Invalid JSON on line 1 column 3: Unexpected end of JSON"
+    );
+    assert_eq!(
+        error("01"),
+        "Invalid JSON on line 1 column 2: Unexpected character '1'"
+    );
+    assert_eq!(
+        error("\"abc"),
+        "Invalid JSON on line 1 column 5: Unterminated string"
+    );
+    assert_eq!(
+        error("\"\\x\""),
+        "Invalid JSON on line 1 column 3: Invalid escape sequence"
+    );
+    assert_eq!(
+        error("1e999"),
+        "Invalid JSON on line 1 column 1: Number is out of range"
+    );
+    assert_eq!(
+        error(&"[".repeat(1000)),
+        "Invalid JSON on line 1 column 513: Nesting is too deep"
+    );
+}
+
+#[test]
+fn test_json_stringify() {
+    let mut scope = Scope::with_stdlib();
+
+    assert_eq!(
+        eval!(
+            scope,
+            "json_stringify([\"a\" = [1, 2.0, true, print()], 5 = \"q\\\"\"])"
+        )
+        .as_str()
+        .text,
+        "{\"a\":[1,2.0,true,null],\"5\":\"q\\\"\"}"
+    );
+    assert_eq!(
+        eval!(
+            scope,
+            "json_stringify([\"a\" = [1], \"b\" = [=], \"c\" = []], 2)"
+        )
+        .as_str()
+        .text,
+        "{\n  \"a\": [\n    1\n  ],\n  \"b\": {},\n  \"c\": []\n}"
+    );
+    assert_eq!(
+        eval!(
+            scope,
+            "json_stringify(json_parse(\"[1, {\\\"x\\\": -0.5}]\"))"
+        )
+        .as_str()
+        .text,
+        "[1,{\"x\":-0.5}]"
+    );
+    assert_eq!(
+        error!(scope, "json_stringify([{print()}])"),
+        "`Func` can't be converted to JSON"
+    );
+    assert_eq!(
+        error!(scope, "json_stringify(mult(1e308, 10.0))"),
+        "NaN and infinity can't be converted to JSON"
+    );
+    assert_eq!(
+        error!(scope, "json_stringify(1, 17)"),
+        "Indent should be from 0 to 16"
+    );
+    assert_eq!(
+        error!(scope, "json_parse(\"[\")"),
+        "Invalid JSON on line 1 column 2: Unexpected end of JSON"
+    );
+}
@@ -133,3 +133,287 @@ fn test_comments() {
         ]
     );
 }
+
+#[test]
+fn test_string_escapes() {
+    test_tokens!(
+        r#"print("a\"b\\c\n\t\r\0")"#,
+        vec![
+            Token::new(TokenType::Identifier, 1, 1, "print"),
+            Token::new(TokenType::Lparen, 1, 6, "("),
+            Token::new(TokenType::String, 1, 7, "a\"b\\c\n\t\r\0"),
+            Token::new(TokenType::Rparen, 1, 24, ")"),
+        ]
+    );
+    test_tokens!(
+        r#"print("\x41\xff\u{1F600}\u{44f}")"#,
+        vec![
+            Token::new(TokenType::Identifier, 1, 1, "print"),
+            Token::new(TokenType::Lparen, 1, 6, "("),
+            Token::new(TokenType::String, 1, 7, "A\u{ff}\u{1F600}я"),
+            Token::new(TokenType::Rparen, 1, 33, ")"),
+        ]
+    );
+}
+
+#[test]
+fn test_string_escape_error() {
+    test_error!(
+        r#"print("ab\q")"#,
+        LexerError {
+            line: 1,
+            column: 10,
+            description: "Unknown escape sequence '\\q'".to_string()
+        }
+    );
+    test_error!(
+        "print(1,\n  \"\\x4g\")",
+        LexerError {
+            line: 2,
+            column: 4,
+            description: "Invalid escape sequence '\\x4g', expected 2 hex digits".to_string()
+        }
+    );
+    test_error!(
+        r#"print("\u{}")"#,
+        LexerError {
+            line: 1,
+            column: 8,
+            description: "Invalid escape sequence '\\u{}', expected 1 to 6 hex digits".to_string()
+        }
+    );
+    test_error!(
+        r#"print("\u{D800}")"#,
+        LexerError {
+            line: 1,
+            column: 8,
+            description: "Invalid escape sequence '\\u{D800}', D800 isn't a Unicode scalar value"
+                .to_string()
+        }
+    );
+}
+
+#[test]
+fn test_multiline_strings() {
+    test_tokens!(
+        "print(\"\"\"a\n\"b\"\\t\"\"\", 1)",
+        vec![
+            Token::new(TokenType::Identifier, 1, 1, "print"),
+            Token::new(TokenType::Lparen, 1, 6, "("),
+            Token::new(TokenType::String, 1, 7, "a\n\"b\"\t"),
+            Token::new(TokenType::Comma, 2, 9, ","),
+            Token::new(TokenType::Number, 2, 11, "1"),
+            Token::new(TokenType::Rparen, 2, 12, ")"),
+        ]
+    );
+    test_tokens!(
+        "print(\"\"\"\n    Hello,\n\n      world!\n    \"\"\")",
+        vec![
+            Token::new(TokenType::Identifier, 1, 1, "print"),
+            Token::new(TokenType::Lparen, 1, 6, "("),
+            Token::new(TokenType::String, 1, 7, "Hello,\n\n  world!\n"),
+            Token::new(TokenType::Rparen, 5, 8, ")"),
+        ]
+    );
+    test_error!(
+        "print(\"\"\"\n  a\n  \\q\"\"\")",
+        LexerError {
+            line: 3,
+            column: 3,
+            description: "Unknown escape sequence '\\q'".to_string()
+        }
+    );
+    test_error!(
+        "print(1, \"\"\"a\n)",
+        LexerError {
+            line: 1,
+            column: 10,
+            description: "Unterminated multi-line string literal".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_raw_strings() {
+    test_tokens!(
+        r#"print(r"C:\new\x", r"""a"b\n""")"#,
+        vec![
+            Token::new(TokenType::Identifier, 1, 1, "print"),
+            Token::new(TokenType::Lparen, 1, 6, "("),
+            Token::new(TokenType::String, 1, 7, "C:\\new\\x"),
+            Token::new(TokenType::Comma, 1, 18, ","),
+            Token::new(TokenType::String, 1, 20, "a\"b\\n"),
+            Token::new(TokenType::Rparen, 1, 32, ")"),
+        ]
+    );
+    test_error!(
+        "print(r\"abc\n\")",
+        LexerError {
+            line: 1,
+            column: 7,
+            description: "Unterminated string literal".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_list_brackets() {
+    test_tokens!(
+        "print([1, []])",
+        vec![
+            Token::new(TokenType::Identifier, 1, 1, "print"),
+            Token::new(TokenType::Lparen, 1, 6, "("),
+            Token::new(TokenType::Lbracket, 1, 7, "["),
+            Token::new(TokenType::Number, 1, 8, "1"),
+            Token::new(TokenType::Comma, 1, 9, ","),
+            Token::new(TokenType::Lbracket, 1, 11, "["),
+            Token::new(TokenType::Rbracket, 1, 12, "]"),
+            Token::new(TokenType::Rbracket, 1, 13, "]"),
+            Token::new(TokenType::Rparen, 1, 14, ")"),
+        ]
+    );
+    test_error!(
+        "print([1)",
+        LexerError {
+            line: 1,
+            column: 9,
+            description: "Unclosed bracket in line 1 column 7".to_string()
+        }
+    );
+    test_error!(
+        "print([",
+        LexerError {
+            line: 1,
+            column: 7,
+            description: "Unterminated left bracket (']')".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_format_string() {
+    test_tokens!(
+        "print(f\"{a:>3} {{}} {add(b, 1)}\")",
+        vec![
+            Token::new(TokenType::Identifier, 1, 1, "print"),
+            Token::new(TokenType::Lparen, 1, 6, "("),
+            Token::new(TokenType::Identifier, 1, 7, "format"),
+            Token::new(TokenType::Lparen, 1, 7, "("),
+            Token::new(TokenType::String, 1, 7, "{:>3} {{}} {}"),
+            Token::new(TokenType::Comma, 1, 7, ","),
+            Token::new(TokenType::Identifier, 1, 10, "a"),
+            Token::new(TokenType::Comma, 1, 7, ","),
+            Token::new(TokenType::Identifier, 1, 22, "add"),
+            Token::new(TokenType::Lparen, 1, 25, "("),
+            Token::new(TokenType::Identifier, 1, 26, "b"),
+            Token::new(TokenType::Comma, 1, 27, ","),
+            Token::new(TokenType::Number, 1, 29, "1"),
+            Token::new(TokenType::Rparen, 1, 30, ")"),
+            Token::new(TokenType::Rparen, 1, 7, ")"),
+            Token::new(TokenType::Rparen, 1, 33, ")"),
+        ]
+    );
+    test_tokens!(
+        "print(f\"\"\"\n    a {x}\n    b {add(\n      y, 1)}\n    \"\"\")",
+        vec![
+            Token::new(TokenType::Identifier, 1, 1, "print"),
+            Token::new(TokenType::Lparen, 1, 6, "("),
+            Token::new(TokenType::Identifier, 1, 7, "format"),
+            Token::new(TokenType::Lparen, 1, 7, "("),
+            Token::new(TokenType::String, 1, 7, "a {}\nb {}\n"),
+            Token::new(TokenType::Comma, 1, 7, ","),
+            Token::new(TokenType::Identifier, 2, 8, "x"),
+            Token::new(TokenType::Comma, 1, 7, ","),
+            Token::new(TokenType::Identifier, 3, 8, "add"),
+            Token::new(TokenType::Lparen, 3, 11, "("),
+            Token::new(TokenType::Identifier, 4, 7, "y"),
+            Token::new(TokenType::Comma, 4, 8, ","),
+            Token::new(TokenType::Number, 4, 10, "1"),
+            Token::new(TokenType::Rparen, 4, 11, ")"),
+            Token::new(TokenType::Rparen, 1, 7, ")"),
+            Token::new(TokenType::Rparen, 5, 8, ")"),
+        ]
+    );
+    test_error!(
+        "print(f\"{}\")",
+        LexerError {
+            line: 1,
+            column: 9,
+            description: "Empty expression in format string literal".to_string()
+        }
+    );
+    test_error!(
+        "print(1,\n  f\"a {b} }\")",
+        LexerError {
+            line: 2,
+            column: 11,
+            description: "Unmatched `}` in format string literal".to_string()
+        }
+    );
+    test_error!(
+        "print(f\"\"\"\n    {x}\n    {to_str(\n      \"\\\\q\")}\n    \"\"\")",
+        LexerError {
+            line: 4,
+            column: 8,
+            description: "Unknown escape sequence '\\q'".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_map_equals() {
+    test_tokens!(
+        "print([a = 1], [=])",
+        vec![
+            Token::new(TokenType::Identifier, 1, 1, "print"),
+            Token::new(TokenType::Lparen, 1, 6, "("),
+            Token::new(TokenType::Lbracket, 1, 7, "["),
+            Token::new(TokenType::Identifier, 1, 8, "a"),
+            Token::new(TokenType::Equals, 1, 10, "="),
+            Token::new(TokenType::Number, 1, 12, "1"),
+            Token::new(TokenType::Rbracket, 1, 13, "]"),
+            Token::new(TokenType::Comma, 1, 14, ","),
+            Token::new(TokenType::Lbracket, 1, 16, "["),
+            Token::new(TokenType::Equals, 1, 17, "="),
+            Token::new(TokenType::Rbracket, 1, 18, "]"),
+            Token::new(TokenType::Rparen, 1, 19, ")"),
+        ]
+    );
+    test_error!(
+        "print([a = ,])",
+        LexerError {
+            line: 1,
+            column: 10,
+            description: "Unexpected type after equals sign ('=')".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_sign_identifiers() {
+    test_tokens!(
+        "print(+x, +, -y, +5, -7)",
+        vec![
+            Token::new(TokenType::Identifier, 1, 1, "print"),
+            Token::new(TokenType::Lparen, 1, 6, "("),
+            Token::new(TokenType::Identifier, 1, 7, "+x"),
+            Token::new(TokenType::Comma, 1, 9, ","),
+            Token::new(TokenType::Identifier, 1, 11, "+"),
+            Token::new(TokenType::Comma, 1, 12, ","),
+            Token::new(TokenType::Identifier, 1, 14, "-y"),
+            Token::new(TokenType::Comma, 1, 16, ","),
+            Token::new(TokenType::Number, 1, 18, "+5"),
+            Token::new(TokenType::Comma, 1, 20, ","),
+            Token::new(TokenType::Number, 1, 22, "-7"),
+            Token::new(TokenType::Rparen, 1, 24, ")"),
+        ]
+    );
+    test_tokens!(
+        "+()",
+        vec![
+            Token::new(TokenType::Identifier, 1, 1, "+"),
+            Token::new(TokenType::Lparen, 1, 2, "("),
+            Token::new(TokenType::Rparen, 1, 3, ")"),
+        ]
+    );
+}
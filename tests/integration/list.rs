@@ -0,0 +1,57 @@
+use easy_prog::types::{Int, List, Scope, Type};
+use std::rc::Rc;
+
+#[test]
+fn test_list_functions() {
+    let mut scope = Scope::with_stdlib();
+    scope.set_variable(
+        "list",
+        Rc::new(List::new(vec![Rc::new(Int::new(1)), Rc::new(Int::new(2))])),
+    );
+
+    let literal = eval!(scope, "concat([1, \"a\"], [[2.5]])");
+    assert_eq!(literal.get_type(), Type::List);
+    assert_eq!(format!("{:?}", literal), "[1, \"a\", [2.5]]");
+
+    eval!(scope, "push(list, 3, 4)");
+    eval!(scope, "insert(list, 0, 0)");
+    eval!(scope, "set(list, 1, \"one\")");
+    assert_eq!(
+        format!("{:?}", scope.get_variable("list").unwrap()),
+        "[0, \"one\", 2, 3, 4]"
+    );
+    assert_eq!(eval!(scope, "len(list)").as_int().number, 5);
+    assert_eq!(eval!(scope, "get(list, 1)").as_str().text, "one");
+    assert_eq!(eval!(scope, "pop(list)").as_int().number, 4);
+    assert_eq!(eval!(scope, "list_remove(list, 0)").as_int().number, 0);
+    assert_eq!(
+        format!("{:?}", eval!(scope, "slice(list, 1, 10)")),
+        "[2, 3]"
+    );
+    assert!(eval!(scope, "contains(list, 3)").as_bool().value);
+    assert!(!eval!(scope, "contains(list, \"3\")").as_bool().value);
+    assert_eq!(
+        eval!(scope, "join(\", \", [\"a\", \"b\"])").as_str().text,
+        "a, b"
+    );
+
+    // Lists are shared by reference, `copy` makes a new list.
+    eval!(scope, "set(\"alias\", list)");
+    eval!(scope, "set(\"copied\", copy(list))");
+    eval!(scope, "push(alias, 5)");
+    assert_eq!(eval!(scope, "len(list)").as_int().number, 4);
+    assert_eq!(eval!(scope, "len(copied)").as_int().number, 3);
+    assert!(eval!(scope, "eq([1, [2]], [1, [2]])").as_bool().value);
+    assert!(!eval!(scope, "eq([1, [2]], [1, [2.0]])").as_bool().value);
+
+    assert_eq!(error!(scope, "get(list, 4)"), "Index out of range");
+    assert_eq!(error!(scope, "pop([])"), "List is empty");
+    assert_eq!(
+        error!(scope, "push(list, [list])"),
+        "List can't contain itself"
+    );
+    assert_eq!(
+        error!(scope, "get(\"text\", 0)"),
+        "First argument of this function should be `List(list)`"
+    );
+}
@@ -1,3 +1,39 @@
+/// Runs `code` in `scope`, panics if it fails, returns its value.
+macro_rules! eval {
+    ($scope: ident, $code: expr) => {
+        easy_prog::runner::run_line_scope($code, &mut $scope)
+            .unwrap()
+            .unwrap()
+    };
+}
+
+/// Runs `code` in `scope`, panics if it succeeds, returns error description.
+macro_rules! error {
+    ($scope: ident, $code: expr) => {
+        easy_prog::runner::run_line_scope($code, &mut $scope)
+            .unwrap()
+            .unwrap_err()
+            .description
+    };
+}
+
+pub mod bool;
+pub mod convert;
+pub mod debugger;
+pub mod deterministic;
+pub mod engine;
+pub mod env;
+pub mod filesystem;
+pub mod json;
 pub mod lexer;
+pub mod list;
+pub mod map;
+pub mod math;
 pub mod parser;
+pub mod process;
+pub mod project;
+pub mod random;
+pub mod snapshot;
+pub mod string;
+pub mod time;
 pub mod types;
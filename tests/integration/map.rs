@@ -0,0 +1,79 @@
+use easy_prog::types::{Scope, Type};
+
+#[test]
+fn test_map_functions() {
+    let mut scope = Scope::with_stdlib();
+
+    eval!(scope, "set(\"key\", \"hp\")");
+    eval!(
+        scope,
+        "set(\"map\", [\"name\" = \"Bob\", key = 10, 3 = [1]])"
+    );
+    let map = scope.get_variable("map").unwrap();
+    assert_eq!(map.get_type(), Type::Map);
+    assert_eq!(
+        format!("{:?}", map),
+        "[\"name\" = \"Bob\", \"hp\" = 10, 3 = [1]]"
+    );
+    assert_eq!(format!("{:?}", eval!(scope, "map_new()")), "[=]");
+
+    // Existing keys keep their position, new keys are added to the end.
+    eval!(scope, "map_set(map, \"hp\", 7)");
+    eval!(scope, "map_set(map, 0, [=])");
+    assert_eq!(
+        format!("{:?}", eval!(scope, "map_keys(map)")),
+        "[\"name\", \"hp\", 3, 0]"
+    );
+    assert_eq!(
+        format!("{:?}", eval!(scope, "map_values(map)")),
+        "[\"Bob\", 7, [1], [=]]"
+    );
+    assert_eq!(eval!(scope, "map_len(map)").as_int().number, 4);
+    assert_eq!(eval!(scope, "map_get(map, \"hp\")").as_int().number, 7);
+    assert_eq!(eval!(scope, "map_get(map, \"mp\", 5)").as_int().number, 5);
+    assert_eq!(eval!(scope, "map_get(map, \"mp\")").get_type(), Type::Void);
+    assert!(eval!(scope, "map_has(map, 3)").as_bool().value);
+    assert!(!eval!(scope, "map_has(map, \"3\")").as_bool().value);
+    assert_eq!(
+        eval!(scope, "map_remove(map, \"name\")").as_str().text,
+        "Bob"
+    );
+    assert_eq!(
+        eval!(scope, "map_remove(map, \"name\")").get_type(),
+        Type::Void
+    );
+    assert_eq!(
+        format!("{:?}", eval!(scope, "map_keys(map)")),
+        "[\"hp\", 3, 0]"
+    );
+
+    // Maps are shared by reference and equal regardless of order.
+    eval!(scope, "set(\"copied\", copy(map))");
+    eval!(scope, "map_set(copied, \"hp\", 0)");
+    assert_eq!(eval!(scope, "map_get(map, \"hp\")").as_int().number, 7);
+    assert!(
+        eval!(scope, "eq([1 = 1, 2 = 2], [2 = 2, 1 = 1])")
+            .as_bool()
+            .value
+    );
+    assert!(!eval!(scope, "eq([1 = 1], [1 = 1, 2 = 2])").as_bool().value);
+    assert!(!eval!(scope, "eq([=], [])").as_bool().value);
+
+    assert_eq!(
+        error!(scope, "map_set(map, \"self\", [map])"),
+        "Map can't contain itself"
+    );
+    assert_eq!(
+        error!(scope, "map_get(map, 1.5)"),
+        "Second argument of this function should be `Int(key)` or `Str(key)`"
+    );
+    eval!(scope, "set(\"key\", 1.5)");
+    assert_eq!(
+        error!(scope, "print([key = 1])"),
+        "Map key should be `Int` or `Str`"
+    );
+    assert_eq!(
+        error!(scope, "map_len([1])"),
+        "First argument of this function should be `Map(map)`"
+    );
+}
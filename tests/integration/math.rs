@@ -0,0 +1,192 @@
+use easy_prog::runner::run_line_scope;
+use easy_prog::types::{Float, Int, Scope, Type};
+use std::rc::Rc;
+
+#[test]
+fn test_float_arithmetic() {
+    let mut scope = Scope::with_stdlib();
+
+    assert_eq!(eval!(scope, "add(2, 3)").as_int().number, 5);
+    assert_eq!(eval!(scope, "add(2, 0.5)").as_float().number, 2.5);
+    assert_eq!(eval!(scope, "subt(0.5, 2)").as_float().number, -1.5);
+    assert_eq!(eval!(scope, "mult(1.5, 1.5)").get_type(), Type::Float);
+    assert_eq!(eval!(scope, "div(7, 2)").as_float().number, 3.5);
+    assert_eq!(eval!(scope, "idiv(7, 2)").as_int().number, 3);
+    assert_eq!(
+        eval!(scope, "parse_float(\" 2.5e1 \")").as_float().number,
+        25.0
+    );
+    assert!(!eval!(scope, "eq(1, 1.0)").as_bool().value);
+    assert!(eval!(scope, "eq(1.0, 1.0)").as_bool().value);
+    assert_eq!(
+        run_line_scope("div(1.5, 0)", &mut scope)
+            .unwrap()
+            .unwrap_err()
+            .description,
+        "Division by zero"
+    );
+    assert_eq!(
+        run_line_scope("add(1, \"2\")", &mut scope)
+            .unwrap()
+            .unwrap_err()
+            .description,
+        "Second argument of this function should be `Int(term)` or `Float(term)`"
+    );
+}
+
+#[test]
+fn test_checked_arithmetic() {
+    let mut scope = Scope::with_stdlib();
+    scope.set_variable("max", Rc::new(Int::new(i64::MAX)));
+    scope.set_variable("min", Rc::new(Int::new(i64::MIN)));
+
+    for code in [
+        "add(max, 1)",
+        "subt(min, 1)",
+        "mult(max, 2)",
+        "idiv(min, -1)",
+    ] {
+        assert_eq!(
+            run_line_scope(code, &mut scope)
+                .unwrap()
+                .unwrap_err()
+                .description,
+            "Integer overflow"
+        );
+    }
+
+    assert_eq!(
+        eval!(scope, "wrapping_add(max, 1)").as_int().number,
+        i64::MIN
+    );
+    assert_eq!(eval!(scope, "wrapping_mul(max, 2)").as_int().number, -2);
+    assert_eq!(
+        eval!(scope, "saturating_sub(min, 1)").as_int().number,
+        i64::MIN
+    );
+    assert_eq!(
+        eval!(scope, "saturating_mul(max, 2)").as_int().number,
+        i64::MAX
+    );
+}
+
+#[test]
+fn test_math_functions() {
+    let mut scope = Scope::with_stdlib();
+    scope.set_variable("min_int", Rc::new(Int::new(i64::MIN)));
+    scope.set_variable("nan", Rc::new(Float::new(f64::NAN)));
+
+    assert_eq!(eval!(scope, "add(1, 2, 3, 4)").as_int().number, 10);
+    assert_eq!(eval!(scope, "mult(2, 3, 0.5)").as_float().number, 3.0);
+    assert!(!eval!(scope, "and(1, 1, 0)").as_bool().value);
+    assert!(eval!(scope, "or(0, 0, 1)").as_bool().value);
+    assert!(eval!(scope, "lt(1, 1.5)").as_bool().value);
+    assert!(eval!(scope, "ge(2, 2)").as_bool().value);
+    assert!(!eval!(scope, "not(5)").as_bool().value);
+    assert_eq!(eval!(scope, "mod(-7, 3)").as_int().number, -1);
+    assert_eq!(eval!(scope, "mod(min_int, -1)").as_int().number, 0);
+    assert_eq!(eval!(scope, "pow(3, 4)").as_int().number, 81);
+    assert_eq!(eval!(scope, "pow(2, 0.5)").as_float().number, 2f64.sqrt());
+    assert_eq!(eval!(scope, "abs(-2.5)").as_float().number, 2.5);
+    assert_eq!(eval!(scope, "min(3, 1.5, 2)").as_float().number, 1.5);
+    assert_eq!(eval!(scope, "max(3, 1.5, 2)").as_int().number, 3);
+    assert_eq!(eval!(scope, "clamp(15, 0, 10)").as_int().number, 10);
+    assert_eq!(eval!(scope, "gcd(12, -18, 30)").as_int().number, 6);
+    assert_eq!(
+        eval!(scope, "bxor(band(12, 10), bor(1, 2))")
+            .as_int()
+            .number,
+        11
+    );
+    assert_eq!(eval!(scope, "shl(1, 62)").as_int().number, 1 << 62);
+    assert_eq!(eval!(scope, "shr(-8, 1)").as_int().number, -4);
+
+    assert_eq!(error!(scope, "pow(2, 63)"), "Integer overflow");
+    assert_eq!(error!(scope, "abs(min_int)"), "Integer overflow");
+    assert_eq!(error!(scope, "gcd(min_int, 0)"), "Integer overflow");
+    assert_eq!(
+        error!(scope, "pow(2, -1)"),
+        "Exponent should not be negative"
+    );
+    assert_eq!(error!(scope, "mod(1, 0)"), "Division by zero");
+    assert_eq!(
+        error!(scope, "clamp(1, 2, 0)"),
+        "Minimum is greater than maximum"
+    );
+    assert_eq!(error!(scope, "shl(1, 64)"), "Shift should be from 0 to 63");
+    assert_eq!(error!(scope, "lt(nan, 1)"), "NaN can't be compared");
+    assert_eq!(error!(scope, "ge(1, nan)"), "NaN can't be compared");
+    assert_eq!(error!(scope, "max(1, nan)"), "NaN can't be compared");
+    assert_eq!(
+        error!(scope, "add(1)"),
+        "This function takes at least 2 arguments, 1 given"
+    );
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn test_bigint() {
+    use easy_prog::bigint::BigInt;
+
+    let a = BigInt::parse("-123456789012345678901234567890").unwrap();
+    let b = BigInt::from_i64(987654321);
+    assert_eq!(
+        (&a * &b).to_string(),
+        "-121932631124828532112482853211126352690"
+    );
+    assert_eq!((&a + &b).to_string(), "-123456789012345678900246913569");
+    let (quotient, remainder) = a.div_rem(&b).unwrap();
+    assert_eq!(quotient.to_string(), "-124999998873437499901");
+    assert_eq!(remainder.to_string(), "-574845669");
+    assert_eq!(&(&quotient * &b) + &remainder, a);
+    assert_eq!(BigInt::from_i64(i64::MIN).to_i64(), Some(i64::MIN));
+    assert_eq!(
+        (&BigInt::from_i64(i64::MAX) + &BigInt::from_i64(1)).to_i64(),
+        None
+    );
+
+    let mut scope = Scope::with_stdlib();
+    scope.set_variable("max", Rc::new(Int::new(i64::MAX)));
+    let result = run_line_scope("mult(bigint(max), max)", &mut scope)
+        .unwrap()
+        .unwrap();
+    assert_eq!(result.get_type(), Type::BigInt);
+    assert_eq!(
+        result.as_bigint().to_string(),
+        "85070591730234615847396907784232501249"
+    );
+    assert_eq!(
+        run_line_scope("bigint_to_int(idiv(mult(bigint(max), 4), 8))", &mut scope)
+            .unwrap()
+            .unwrap()
+            .as_int()
+            .number,
+        i64::MAX / 2
+    );
+    assert_eq!(
+        run_line_scope("bigint_to_str(pow(bigint(2), 100))", &mut scope)
+            .unwrap()
+            .unwrap()
+            .as_str()
+            .text,
+        "1267650600228229401496703205376"
+    );
+    run_line_scope("set(\"huge\", mult(bigint(1000000), 1000000))", &mut scope)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        run_line_scope("bigint_to_int(pow(bigint(-1), huge))", &mut scope)
+            .unwrap()
+            .unwrap()
+            .as_int()
+            .number,
+        1
+    );
+    assert_eq!(
+        run_line_scope("pow(bigint(2), huge)", &mut scope)
+            .unwrap()
+            .unwrap_err()
+            .description,
+        "Result is too large"
+    );
+}
@@ -14,3 +14,113 @@ fn test_hello_world() {
         "Hello, World!"
     );
 }
+
+#[test]
+fn test_int_literals() {
+    let node = parse("print(0xFF, -0b1010, 0o17, +1_000_000, -0x8000_0000_0000_0000)").unwrap();
+    let values: Vec<i64> = node.body[0]
+        .as_call_func()
+        .args
+        .iter()
+        .map(|arg| arg.as_int_const().value.number)
+        .collect();
+    assert_eq!(values, [255, -10, 15, 1_000_000, i64::MIN]);
+
+    let error = parse("print(1,\n 0x8000_0000_0000_0000)").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 2));
+    assert_eq!(error.description, "Integer value overflowed");
+    assert_eq!(
+        parse("print(0b102)").unwrap_err().description,
+        "Invalid digit '2' in binary integer literal '0b102'"
+    );
+    assert_eq!(
+        parse("print(1__0)").unwrap_err().description,
+        "Digit separator '_' should be between digits in integer literal '1__0'"
+    );
+    assert_eq!(
+        parse("print(0x)").unwrap_err().description,
+        "Expected digits in integer literal '0x'"
+    );
+
+    let node = parse("+(), print(+x, -)").unwrap();
+    assert_eq!(node.body[0].as_call_func().name, "+");
+    let args = &node.body[1].as_call_func().args;
+    assert_eq!(args[0].as_variable().name, "+x");
+    assert_eq!(args[1].as_variable().name, "-");
+}
+
+#[test]
+fn test_float_literals() {
+    let node = parse("print(1.5, -0.25, 1e3, 2.5E-2, +1_000.000_1, 0b1e)");
+    assert_eq!(
+        node.unwrap_err().description,
+        "Invalid digit 'e' in binary integer literal '0b1e'"
+    );
+
+    let node = parse("print(1.5, -0.25, 1e3, 2.5E-2, +1_000.000_1, 0xE)").unwrap();
+    let args = node.body[0].as_call_func().args;
+    let values: Vec<f64> = args[..5]
+        .iter()
+        .map(|arg| arg.as_float_const().value.number)
+        .collect();
+    assert_eq!(values, [1.5, -0.25, 1000.0, 0.025, 1000.0001]);
+    assert_eq!(args[5].as_int_const().value.number, 14);
+
+    assert_eq!(
+        parse("print(1.)").unwrap_err().description,
+        "Invalid float literal '1.'"
+    );
+    assert_eq!(
+        parse("print(1e999)").unwrap_err().description,
+        "Float value overflowed"
+    );
+}
+
+#[test]
+fn test_list_literals() {
+    let node = parse("print([1, \"a\", [x], {print()}], [])").unwrap();
+    let args = node.body[0].as_call_func().args;
+    let items = args[0].as_list().items;
+    assert_eq!(items.len(), 4);
+    assert_eq!(items[0].as_int_const().value.number, 1);
+    assert_eq!(items[1].as_str_const().value.text, "a");
+    assert_eq!(items[2].as_list().items[0].as_variable().name, "x");
+    assert!(args[1].as_list().items.is_empty());
+
+    assert_eq!(
+        parse("print([1,])").unwrap_err().description,
+        "Unexpected token type, expected expression"
+    );
+}
+
+#[test]
+fn test_map_literals() {
+    let node = parse("print([\"a\" = 1, b = [c = {print()}]], [=])").unwrap();
+    let args = node.body[0].as_call_func().args;
+    let items = args[0].as_map().items;
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].0.as_str_const().value.text, "a");
+    assert_eq!(items[0].1.as_int_const().value.number, 1);
+    assert_eq!(items[1].0.as_variable().name, "b");
+    assert_eq!(items[1].1.as_map().items[0].0.as_variable().name, "c");
+    assert!(args[1].as_map().items.is_empty());
+
+    assert_eq!(
+        parse("print([\"a\" = 1, 2])").unwrap_err().description,
+        "Unexpected token type, expected equals sign ('=')"
+    );
+    assert_eq!(
+        parse("print([\"a\" = 1, 1.5 = 2])")
+            .unwrap_err()
+            .description,
+        "Map key should be string, integer or identifier"
+    );
+
+    // List, that starts with empty map, isn't a map.
+    let node = parse("print([[=]], [[=], 1])").unwrap();
+    let args = node.body[0].as_call_func().args;
+    assert!(args[0].as_list().items[0].as_map().items.is_empty());
+    let items = args[1].as_list().items;
+    assert!(items[0].as_map().items.is_empty());
+    assert_eq!(items[1].as_int_const().value.number, 1);
+}
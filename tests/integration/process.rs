@@ -0,0 +1,139 @@
+use easy_prog::engine::{Permission, Policy};
+use easy_prog::filesystem::{FileSystem, JailFileSystem, MemoryFileSystem};
+use easy_prog::runner::run_line_scope;
+use easy_prog::stdlib::add_core;
+use easy_prog::types::{MapKey, NativeExceptionCode, Scope, Variant};
+use std::rc::Rc;
+
+fn get_field(result: &Rc<dyn Variant>, name: &str) -> Rc<dyn Variant> {
+    result
+        .as_map()
+        .items
+        .borrow()
+        .get(&MapKey::Str(name.to_string()))
+        .unwrap()
+        .clone()
+}
+
+#[test]
+fn test_process_permission() {
+    let mut scope = Scope::with_stdlib();
+    assert!(!scope
+        .get_engine()
+        .get_policy()
+        .is_allowed(Permission::ProcessSpawn));
+    let error = run_line_scope("run_process(\"sh\")", &mut scope)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(error.code, NativeExceptionCode::PermissionDenied);
+    assert_eq!(
+        error.description,
+        "Permission denied: process spawn is not allowed"
+    );
+
+    let mut scope = Scope::empty();
+    add_core(&mut scope);
+    assert!(scope.get_function("exit").is_some());
+    assert!(scope.get_function("run_process").is_none());
+    assert!(Policy::allow_all().is_allowed(Permission::ProcessSpawn));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_process() {
+    let mut scope = Scope::with_stdlib();
+    scope.get_engine_mut().set_policy(Policy::allow_all());
+
+    let result = eval!(
+        scope,
+        "run_process(\"sh\", [\"-c\", \"echo out; echo err >&2; exit 3\"])"
+    );
+    assert_eq!(get_field(&result, "code").as_int().number, 3);
+    assert_eq!(get_field(&result, "stdout").as_str().text, "out\n");
+    assert_eq!(get_field(&result, "stderr").as_str().text, "err\n");
+
+    let result = eval!(scope,
+        "run_process(\"sh\", [\"-c\", \"cat; echo $EASY_PROG_TEST; pwd\"], [\"stdin\" = \"input \", \"env\" = [\"EASY_PROG_TEST\" = \"value\"], \"cwd\" = \"/\"])"
+    );
+    assert_eq!(get_field(&result, "code").as_int().number, 0);
+    assert_eq!(
+        get_field(&result, "stdout").as_str().text,
+        "input value\n/\n"
+    );
+
+    assert_eq!(
+        error!(
+            scope,
+            "run_process(\"sh\", [\"-c\", \"sleep 5\"], [\"timeout_ms\" = 50])"
+        ),
+        "Process 'sh' timed out after 50 ms"
+    );
+    // Background child keeps output open after shell exits.
+    assert_eq!(
+        error!(
+            scope,
+            "run_process(\"sh\", [\"-c\", \"sleep 5 &\"], [\"timeout_ms\" = 200])"
+        ),
+        "Process 'sh' timed out after 200 ms"
+    );
+    assert_eq!(
+        error!(scope, "run_process(\"easy_prog_missing_program\")"),
+        "Program 'easy_prog_missing_program' not found"
+    );
+    assert_eq!(
+        error!(
+            scope,
+            "run_process(\"sh\", [], [\"cwd\" = \"/easy_prog_missing_dir\"])"
+        ),
+        "Directory '/easy_prog_missing_dir' doesn't exist"
+    );
+    assert_eq!(
+        error!(scope, "run_process(\"sh\", [1])"),
+        "Arguments of process should be `Str`"
+    );
+    assert_eq!(
+        error!(scope, "run_process(\"sh\", [], [\"timeout\" = 1])"),
+        "Unknown option 'timeout'"
+    );
+    assert_eq!(
+        error!(scope, "run_process(\"sh\", [], [\"stdin\" = 1])"),
+        "Option 'stdin' should be `Str`"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_process_file_system() {
+    let root = std::env::temp_dir().join(format!("easy_prog_process_jail_{}", std::process::id()));
+    std::fs::create_dir_all(root.join("work")).unwrap();
+    let jail = JailFileSystem::new(root.to_str().unwrap());
+    let host_root = jail.get_root().to_str().unwrap().to_string();
+    let mut scope = Scope::with_stdlib();
+    scope.get_engine_mut().set_policy(Policy::allow_all());
+    scope.get_engine_mut().set_file_system(Rc::new(jail));
+
+    for (cwd, expected) in [
+        ("/", host_root.clone()),
+        ("work", host_root.clone() + "/work"),
+    ] {
+        let result = eval!(
+            scope,
+            &format!("run_process(\"pwd\", [], [\"cwd\" = \"{}\"])", cwd)
+        );
+        assert_eq!(get_field(&result, "stdout").as_str().text, expected + "\n");
+    }
+
+    assert_eq!(
+        error!(scope, "run_process(\"pwd\", [], [\"cwd\" = \"/missing\"])"),
+        "Directory '/missing' doesn't exist"
+    );
+    std::fs::remove_dir_all(&root).unwrap();
+
+    let memory = MemoryFileSystem::new();
+    memory.create_dir("/data").unwrap();
+    scope.get_engine_mut().set_file_system(Rc::new(memory));
+    assert_eq!(
+        error!(scope, "run_process(\"pwd\", [], [\"cwd\" = \"/data\"])"),
+        "Directory '/data' isn't on host disk"
+    );
+}
@@ -0,0 +1,202 @@
+use std::rc::Rc;
+
+use easy_prog::engine::{Engine, Permission, Policy};
+use easy_prog::filesystem::MemoryFileSystem;
+use easy_prog::project::{parse_manifest, resolve_module, run_project_scope, ProjectError};
+use easy_prog::runner::run_line_scope;
+use easy_prog::types::{Int, Scope};
+
+#[test]
+fn test_parse_manifest() {
+    let manifest = parse_manifest(
+        "# Comment\nname = \"mod\"\nversion = \"0.1.0\" # Trailing\n\n[dependencies]\nutils = \"libs/utils\"\n",
+    )
+    .unwrap();
+    assert_eq!(manifest.name, "mod");
+    assert_eq!(manifest.version, "0.1.0");
+    assert_eq!(manifest.entry, "main.ep");
+    assert_eq!(
+        manifest.dependencies,
+        vec![("utils".to_string(), "libs/utils".to_string())]
+    );
+}
+
+#[test]
+fn test_parse_manifest_error() {
+    assert_eq!(
+        parse_manifest("name = \"mod\"\nversion = 1").unwrap_err(),
+        ProjectError::new(2, 11, "Expected string value in double quotes")
+    );
+    assert_eq!(
+        parse_manifest("name = \"mod\"\n[package]").unwrap_err(),
+        ProjectError::new(2, 1, "Unknown section '[package]'")
+    );
+    assert_eq!(
+        parse_manifest("name = \"mod\"").unwrap_err(),
+        ProjectError::new(0, 0, "Missing required key 'version'")
+    );
+
+    for entry in ["../main.ep", "/main.ep", "src/../../main.ep"] {
+        assert_eq!(
+            parse_manifest(&format!(
+                "name = \"mod\"\nversion = \"0.1.0\"\nentry = \"{}\"",
+                entry
+            ))
+            .unwrap_err(),
+            ProjectError::new(
+                3,
+                9,
+                "Entry should be a relative path inside of project directory"
+            )
+        );
+    }
+}
+
+#[test]
+fn test_resolve_module() {
+    let mut engine = Engine::new();
+    assert!(resolve_module(&mut engine, "greeting").is_err());
+
+    engine.add_module_path("examples/project/vendor");
+    engine.add_dependency("counter", "examples/project/libs/counter");
+    assert_eq!(
+        resolve_module(&mut engine, "greeting").unwrap(),
+        "examples/project/vendor/greeting.ep"
+    );
+    assert_eq!(
+        resolve_module(&mut engine, "counter").unwrap(),
+        "examples/project/libs/counter/counter.ep"
+    );
+    assert_eq!(
+        resolve_module(&mut engine, "greeting.ep").unwrap(),
+        "examples/project/vendor/greeting.ep"
+    );
+}
+
+#[test]
+fn test_import_once() {
+    let dir = std::env::temp_dir().join(format!("easy_prog_import_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("counted.ep"),
+        "set(\"loads\", add(loads, 1)),\nimport(\"counted\")",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("broken.ep"),
+        "set(\"attempts\", add(attempts, 1)),\nmissing()",
+    )
+    .unwrap();
+
+    let mut scope = Scope::with_stdlib();
+    scope
+        .get_engine_mut()
+        .add_module_path(dir.to_str().unwrap());
+    scope.set_variable("loads", Rc::new(Int::new(0)));
+    scope.set_variable("attempts", Rc::new(Int::new(0)));
+
+    // Circular import is skipped, the second import does nothing.
+    run_line_scope("import(\"counted\")", &mut scope)
+        .unwrap()
+        .unwrap();
+    run_line_scope("import(\"counted\")", &mut scope)
+        .unwrap()
+        .unwrap();
+    assert_eq!(scope.get_variable("loads").unwrap().as_int().number, 1);
+
+    // Failed module isn't treated as loaded.
+    assert!(run_line_scope("import(\"broken\")", &mut scope)
+        .unwrap()
+        .is_err());
+    assert!(run_line_scope("import(\"broken\")", &mut scope)
+        .unwrap()
+        .is_err());
+    assert_eq!(scope.get_variable("attempts").unwrap().as_int().number, 2);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_import_file_system() {
+    let file_system = MemoryFileSystem::new();
+    file_system.add_file(
+        "/libs/utils/EasyProg.toml",
+        b"name = \"utils\"\nversion = \"0.1.0\"\nentry = \"lib.ep\"\n",
+    );
+    file_system.add_file("/libs/utils/lib.ep", b"set(\"loaded\", add(loaded, 1))");
+    let mut scope = Scope::with_stdlib();
+    scope.get_engine_mut().set_file_system(Rc::new(file_system));
+    scope
+        .get_engine_mut()
+        .add_dependency("utils", "/libs/utils");
+    scope.set_variable("loaded", Rc::new(Int::new(0)));
+
+    run_line_scope("import(\"utils\")", &mut scope)
+        .unwrap()
+        .unwrap();
+    run_line_scope("import(\"/libs/utils/lib.ep\")", &mut scope)
+        .unwrap()
+        .unwrap();
+    assert_eq!(scope.get_variable("loaded").unwrap().as_int().number, 1);
+}
+
+#[test]
+fn test_import_policy() {
+    let file_system = MemoryFileSystem::new();
+    file_system.add_file("/allowed/a.ep", b"set(\"loaded\", true)");
+    file_system.add_file("/secret/b.ep", b"set(\"loaded\", true)");
+    file_system.add_file("/secret/lib/EasyProg.toml", b"broken");
+    let mut scope = Scope::with_stdlib();
+    scope.get_engine_mut().set_file_system(Rc::new(file_system));
+    scope.get_engine_mut().add_module_path("/secret");
+    let mut policy = Policy::allow_all();
+    policy.deny(Permission::FileRead).allow_path("/allowed");
+    scope.get_engine_mut().set_policy(policy);
+
+    for code in ["import(\"/allowed/a.ep\")", "import(\"/missing.ep\")"] {
+        assert_eq!(
+            error!(scope, code),
+            "Permission denied: file read is not allowed"
+        );
+    }
+
+    scope
+        .get_engine_mut()
+        .get_policy_mut()
+        .allow(Permission::FileRead);
+    assert_eq!(
+        error!(scope, "import(\"/secret/b.ep\")"),
+        "Module error: Module file '/secret/b.ep' not found"
+    );
+    assert_eq!(
+        error!(scope, "import(\"lib\")"),
+        "Module error: Module 'lib' not found"
+    );
+    assert_eq!(scope.get_engine().get_module_paths(), &vec!["/secret"]);
+    assert!(scope.get_variable("loaded").is_none());
+
+    eval!(scope, "import(\"/allowed/a.ep\")");
+    assert!(scope.get_variable("loaded").unwrap().as_bool().value);
+}
+
+#[test]
+fn test_run_project_file_system() {
+    let file_system = MemoryFileSystem::new();
+    file_system.add_file(
+        "/game/EasyProg.toml",
+        b"name = \"game\"\nversion = \"0.1.0\"\nentry = \"src/start.ep\"\n",
+    );
+    file_system.add_file("/game/src/start.ep", b"set(\"started\", true)");
+    let mut scope = Scope::with_stdlib();
+    scope.get_engine_mut().set_file_system(Rc::new(file_system));
+
+    let mut policy = Policy::allow_all();
+    policy.allow_path("/other");
+    scope.get_engine_mut().set_policy(policy);
+    assert!(!run_project_scope("/game", &mut scope));
+    assert!(scope.get_variable("started").is_none());
+
+    scope.get_engine_mut().set_policy(Policy::allow_all());
+    assert!(run_project_scope("/game", &mut scope));
+    assert!(scope.get_variable("started").unwrap().as_bool().value);
+    assert_eq!(scope.get_engine().get_module_paths()[0], "/game");
+}
@@ -0,0 +1,96 @@
+use easy_prog::random::Random;
+use easy_prog::runner::run_line_scope;
+use easy_prog::types::Scope;
+
+#[test]
+fn test_random_generator() {
+    let mut a = Random::new(42);
+    let mut b = Random::new(42);
+    let sequence: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+    assert_eq!(sequence, (0..5).map(|_| b.next_u64()).collect::<Vec<u64>>());
+    assert_ne!(Random::new(43).next_u64(), sequence[0]);
+
+    a.set_seed(42);
+    assert_eq!(a.next_u64(), sequence[0]);
+    assert_eq!(a.get_seed(), 42);
+
+    for _ in 0..1000 {
+        let number = a.next_range(-3, 3);
+        assert!((-3..=3).contains(&number));
+    }
+
+    assert_eq!(a.next_range(7, 7), 7);
+    a.next_range(i64::MIN, i64::MAX);
+}
+
+#[test]
+fn test_random_functions() {
+    let mut scope = Scope::with_stdlib();
+
+    let first = eval!(scope, "rand_int(1, 1000000)").as_int().number;
+    eval!(scope, "rand_seed(0)");
+    assert_eq!(eval!(scope, "rand_int(1, 1000000)").as_int().number, first);
+    assert_eq!(
+        error!(scope, "rand_int(2, 1)"),
+        "Minimum is greater than maximum"
+    );
+
+    eval!(scope, "set(\"items\", [1, 2, 3, 4, 5, 6])");
+    let choice = eval!(scope, "rand_choice(items)").as_int().number;
+    assert!((1..=6).contains(&choice));
+    assert_eq!(error!(scope, "rand_choice([])"), "List is empty");
+
+    eval!(scope, "shuffle(items)");
+    let shuffled = format!("{:?}", scope.get_variable("items").unwrap().as_list());
+    let mut numbers: Vec<i64> = scope
+        .get_variable("items")
+        .unwrap()
+        .as_list()
+        .items
+        .borrow()
+        .iter()
+        .map(|item| item.as_int().number)
+        .collect();
+    numbers.sort();
+    assert_eq!(numbers, [1, 2, 3, 4, 5, 6]);
+
+    eval!(scope, "rand_seed(7)");
+    eval!(scope, "set(\"items\", [1, 2, 3, 4, 5, 6])");
+    eval!(scope, "rand_choice(items)");
+    eval!(scope, "shuffle(items)");
+    let mut other = Scope::with_stdlib();
+    other.get_engine_mut().set_random_seed(7);
+    run_line_scope("set(\"items\", [1, 2, 3, 4, 5, 6])", &mut other)
+        .unwrap()
+        .unwrap();
+    run_line_scope("rand_choice(items)", &mut other)
+        .unwrap()
+        .unwrap();
+    run_line_scope("shuffle(items)", &mut other)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        format!("{:?}", scope.get_variable("items").unwrap().as_list()),
+        format!("{:?}", other.get_variable("items").unwrap().as_list())
+    );
+    assert_ne!(
+        format!("{:?}", scope.get_variable("items").unwrap().as_list()),
+        shuffled
+    );
+}
+
+#[test]
+fn test_random_seed_lock() {
+    let mut scope = Scope::with_stdlib();
+    scope.get_engine_mut().set_random_seed(5);
+    scope.get_engine_mut().lock_random_seed(true);
+    let error = run_line_scope("rand_seed(1)", &mut scope)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(error.description, "Random seed is locked by host");
+    assert_eq!(scope.get_engine().get_random().get_seed(), 5);
+
+    scope.get_engine_mut().lock_random_seed(false);
+    run_line_scope("rand_seed(1)", &mut scope).unwrap().unwrap();
+    assert_eq!(scope.get_engine().get_random().get_seed(), 1);
+}
@@ -0,0 +1,218 @@
+use std::rc::Rc;
+
+use easy_prog::runner::run_line_scope;
+use easy_prog::snapshot::{load_scope, load_scope_file, save_scope, save_scope_file};
+use easy_prog::stdlib::print;
+use easy_prog::types::{
+    AstNode, Custom, Function, List, ListNode, Scope, SequenceNode, Str, Type, Variant,
+};
+
+const CUSTOM_TYPE_COUNTER: usize = 100;
+
+struct Counter {
+    value: i64,
+}
+
+impl Custom for Counter {
+    fn get_id(&self) -> usize {
+        CUSTOM_TYPE_COUNTER
+    }
+
+    fn custom_equals(&self, custom: &mut Rc<dyn Custom>) -> bool {
+        self.get_ptr() == custom.get_ptr()
+    }
+
+    fn snapshot(&self) -> Option<String> {
+        Some(self.value.to_string())
+    }
+}
+
+fn load_counter(data: &str) -> Option<Rc<dyn Custom>> {
+    Some(Rc::new(Counter {
+        value: data.parse().ok()?,
+    }))
+}
+
+fn run(code: &str, scope: &mut Scope) {
+    run_line_scope(code, scope).unwrap().unwrap();
+}
+
+#[test]
+fn test_snapshot_values() {
+    let mut scope = Scope::with_stdlib();
+    run("set(\"count\", 42)", &mut scope);
+    run("set(\"ratio\", 0.1)", &mut scope);
+    scope.set_variable("name", Rc::new(Str::new("mod \"A\"\n")));
+    run(
+        "set(\"items\", [1, \"two\", [3.5], [\"a\" = 1, 2 = \"b\"]])",
+        &mut scope,
+    );
+    run("set(\"shared\", [items, items])", &mut scope);
+    let snapshot = save_scope(&scope).unwrap();
+
+    let mut loaded = Scope::with_stdlib();
+    load_scope(&mut loaded, &snapshot).unwrap();
+    assert_eq!(loaded.get_variable("count").unwrap().as_int().number, 42);
+    assert_eq!(loaded.get_variable("ratio").unwrap().as_float().number, 0.1);
+    assert_eq!(
+        loaded.get_variable("name").unwrap().as_str().text,
+        "mod \"A\"\n"
+    );
+    run("set(\"text\", to_str(items))", &mut loaded);
+    run("set(\"text2\", to_str(items))", &mut scope);
+    assert_eq!(
+        loaded.get_variable("text").unwrap().as_str().text,
+        scope.get_variable("text2").unwrap().as_str().text
+    );
+
+    // Shared lists stay shared after loading.
+    let shared = loaded.get_variable("shared").unwrap().as_list();
+    let items = shared.items.borrow();
+    assert!(items[0].as_list().ptr_eq(&items[1].as_list()));
+    assert!(items[0]
+        .as_list()
+        .ptr_eq(&loaded.get_variable("items").unwrap().as_list()));
+}
+
+#[test]
+fn test_snapshot_functions() {
+    let mut scope = Scope::with_stdlib();
+    run(
+        "declfunc(\"bump\", {set(\"count\", add(count, 1))})",
+        &mut scope,
+    );
+    run("set(\"callback\", {set(\"flag\", 1.5)})", &mut scope);
+    run("set(\"count\", 1)", &mut scope);
+    let snapshot = save_scope(&scope).unwrap();
+
+    let mut loaded = Scope::with_stdlib();
+    load_scope(&mut loaded, &snapshot).unwrap();
+    run("bump()", &mut loaded);
+    run("bump()", &mut loaded);
+    assert_eq!(loaded.get_variable("count").unwrap().as_int().number, 3);
+    assert_eq!(
+        loaded.get_variable("callback").unwrap().get_type(),
+        Type::Func
+    );
+}
+
+#[test]
+fn test_snapshot_custom() {
+    let mut scope = Scope::with_stdlib();
+    let counter: Rc<dyn Custom> = Rc::new(Counter { value: 7 });
+    scope.set_variable("counter", Rc::new(counter));
+    let snapshot = save_scope(&scope).unwrap();
+
+    let mut loaded = Scope::with_stdlib();
+    assert_eq!(
+        load_scope(&mut loaded, &snapshot).unwrap_err().description,
+        "Can't load variable 'counter': No loader for custom type 100"
+    );
+    assert!(loaded.get_variable("counter").is_none());
+
+    loaded
+        .get_engine_mut()
+        .add_custom_loader(CUSTOM_TYPE_COUNTER, load_counter);
+    load_scope(&mut loaded, &snapshot).unwrap();
+    let custom = loaded.get_variable("counter").unwrap().as_custom();
+    assert_eq!(custom.get_id(), CUSTOM_TYPE_COUNTER);
+    assert_eq!(custom.snapshot().unwrap(), "7");
+}
+
+#[test]
+fn test_snapshot_errors() {
+    let mut scope = Scope::with_stdlib();
+    scope.set_variable("printer", Rc::new(Function::new_native(print)));
+    assert_eq!(
+        save_scope(&scope).unwrap_err().description,
+        "Can't save variable 'printer': Native function can't be saved"
+    );
+
+    let mut scope = Scope::with_stdlib();
+
+    for (snapshot, description) in [
+        ("[]", "Invalid snapshot: expected object"),
+        ("{\"format\": \"other\"}", "Invalid snapshot: unknown format"),
+        (
+            "{\"format\": \"easy_prog_snapshot\", \"version\": 2}",
+            "Unsupported snapshot version 2",
+        ),
+        (
+            "{\"format\": \"easy_prog_snapshot\", \"version\": 1, \"variables\": {\"a\": [\"Ref\", 0]}, \"functions\": {}}",
+            "Can't load variable 'a': Invalid snapshot: unknown collection id 0",
+        ),
+        (
+            "{\"format\": \"easy_prog_snapshot\", \"version\": 1, \"variables\": {\"a\": [\"List\", 0, [[\"Ref\", 0]]]}, \"functions\": {}}",
+            "Can't load variable 'a': Invalid snapshot: collection 0 contains itself",
+        ),
+    ] {
+        assert_eq!(
+            load_scope(&mut scope, snapshot).unwrap_err().description,
+            description
+        );
+    }
+}
+
+fn nested_list(depth: usize) -> Rc<dyn Variant> {
+    let mut value: Rc<dyn Variant> = Rc::new(List::new(Vec::new()));
+
+    for _ in 1..depth {
+        value = Rc::new(List::new(vec![value]));
+    }
+
+    value
+}
+
+fn nested_list_function(depth: usize) -> Function {
+    let mut node: Rc<dyn AstNode> = Rc::new(ListNode::new(1, 1, Vec::new()));
+
+    for _ in 1..depth {
+        node = Rc::new(ListNode::new(1, 1, vec![node]));
+    }
+
+    Function::new(SequenceNode::new(1, 1, vec![node]))
+}
+
+#[test]
+fn test_snapshot_depth() {
+    let mut scope = Scope::with_stdlib();
+    scope.set_variable("deep", nested_list(255));
+    scope.set_function("deep_func", nested_list_function(254));
+    let snapshot = save_scope(&scope).unwrap();
+
+    let mut loaded = Scope::with_stdlib();
+    load_scope(&mut loaded, &snapshot).unwrap();
+    assert!(loaded
+        .get_variable("deep")
+        .unwrap()
+        .equals(nested_list(255).as_ref()));
+    assert!(loaded.get_function("deep_func").is_some());
+
+    scope.set_variable("deep", nested_list(256));
+    assert_eq!(
+        save_scope(&scope).unwrap_err().description,
+        "Can't save variable 'deep': Nesting is too deep"
+    );
+
+    let mut scope = Scope::with_stdlib();
+    scope.set_function("deep_func", nested_list_function(255));
+    assert_eq!(
+        save_scope(&scope).unwrap_err().description,
+        "Can't save function 'deep_func': Nesting is too deep"
+    );
+}
+
+#[test]
+fn test_snapshot_file() {
+    let path = std::env::temp_dir().join(format!("easy_prog_snapshot_{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+    let mut scope = Scope::with_stdlib();
+    run("set(\"level\", 3)", &mut scope);
+    save_scope_file(&scope, path).unwrap();
+
+    let mut loaded = Scope::with_stdlib();
+    load_scope_file(&mut loaded, path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(loaded.get_variable("level").unwrap().as_int().number, 3);
+    assert!(load_scope_file(&mut loaded, path).is_err());
+}
@@ -0,0 +1,198 @@
+use easy_prog::runner::run_line_scope;
+use easy_prog::types::{Scope, Str};
+use std::rc::Rc;
+
+#[test]
+fn test_string_functions() {
+    let mut scope = Scope::with_stdlib();
+    scope.set_variable("text", Rc::new(Str::new("  Привет, мир!  ")));
+
+    assert_eq!(eval!(scope, "len(text)").as_int().number, 16);
+    assert_eq!(eval!(scope, "trim(text)").as_str().text, "Привет, мир!");
+    assert_eq!(
+        eval!(scope, "substr(trim(text), 8, 3)").as_str().text,
+        "мир"
+    );
+    assert_eq!(
+        eval!(scope, "substr(trim(text), 8, 100)").as_str().text,
+        "мир!"
+    );
+    assert_eq!(eval!(scope, "substr(trim(text), 12, 1)").as_str().text, "");
+    assert_eq!(eval!(scope, "find(text, \"мир\")").as_int().number, 10);
+    assert_eq!(eval!(scope, "find(text, \"world\")").as_int().number, -1);
+    assert_eq!(
+        eval!(scope, "upper(trim(text))").as_str().text,
+        "ПРИВЕТ, МИР!"
+    );
+    assert_eq!(eval!(scope, "lower(\"ABC\")").as_str().text, "abc");
+    assert_eq!(
+        eval!(scope, "concat(\"a\", \"б\", \"c\")").as_str().text,
+        "aбc"
+    );
+    assert_eq!(
+        eval!(scope, "replace(\"a-b-c\", \"-\", \"+\")")
+            .as_str()
+            .text,
+        "a+b+c"
+    );
+    assert_eq!(
+        eval!(scope, "to_str(split(\"a,b,,c\", \",\"))")
+            .as_str()
+            .text,
+        "[\"a\", \"b\", \"\", \"c\"]"
+    );
+    assert_eq!(
+        eval!(scope, "len(split(\"abc\", \",\"))").as_int().number,
+        1
+    );
+    assert_eq!(
+        eval!(scope, "join(\", \", \"a\", \"b\")").as_str().text,
+        "a, b"
+    );
+    assert!(
+        eval!(scope, "starts_with(\"prefix\", \"pre\")")
+            .as_bool()
+            .value
+    );
+    assert!(
+        !eval!(scope, "ends_with(\"prefix\", \"pre\")")
+            .as_bool()
+            .value
+    );
+    assert!(
+        eval!(scope, "contains(\"prefix\", \"efi\")")
+            .as_bool()
+            .value
+    );
+    assert_eq!(eval!(scope, "repeat(\"ab\", 3)").as_str().text, "ababab");
+
+    assert_eq!(
+        run_line_scope("substr(\"abc\", 4, 1)", &mut scope)
+            .unwrap()
+            .unwrap_err()
+            .description,
+        "Index out of range"
+    );
+    assert_eq!(
+        run_line_scope("repeat(\"abc\", -1)", &mut scope)
+            .unwrap()
+            .unwrap_err()
+            .description,
+        "Count should not be negative"
+    );
+    assert_eq!(
+        run_line_scope("repeat(\"abc\", 4611686018427387904)", &mut scope)
+            .unwrap()
+            .unwrap_err()
+            .description,
+        "Result string is too long"
+    );
+    assert_eq!(
+        run_line_scope("replace(\"abc\", \"\", \"x\")", &mut scope)
+            .unwrap()
+            .unwrap_err()
+            .description,
+        "`from` should not be empty"
+    );
+}
+
+#[test]
+fn test_format() {
+    let mut scope = Scope::with_stdlib();
+
+    scope.set_variable("name", Rc::new(Str::new("Bob")));
+    assert_eq!(
+        eval!(scope, "format(\"Player {} has {} HP\", name, 42)")
+            .as_str()
+            .text,
+        "Player Bob has 42 HP"
+    );
+    assert_eq!(
+        eval!(scope, "format(\"{1}-{0}-{}\", \"a\", \"b\")")
+            .as_str()
+            .text,
+        "b-a-a"
+    );
+    assert_eq!(
+        eval!(scope, "format(\"{name} has {hp}\", [\"hp\" = 7])")
+            .as_str()
+            .text,
+        "Bob has 7"
+    );
+    assert_eq!(
+        eval!(
+            scope,
+            "format(\"[{:>5}|{:<5}|{:*^7}]\", 42, \"ab\", \"mid\")"
+        )
+        .as_str()
+        .text,
+        "[   42|ab   |**mid**]"
+    );
+    assert_eq!(
+        eval!(scope, "format(\"{:08.2}|{:+}|{:05}\", 3.14159, 5, -42)")
+            .as_str()
+            .text,
+        "00003.14|+5|-0042"
+    );
+    assert_eq!(
+        eval!(scope, "format(\"{:x}|{:#X}|{:#b}|{:o}\", 255, 255, -5, 8)")
+            .as_str()
+            .text,
+        "ff|0xFF|-0b101|10"
+    );
+    assert_eq!(
+        eval!(
+            scope,
+            "format(\"{:?}|{}|{{}}|{:.2}\", \"q\", [\"q\"], \"abc\")"
+        )
+        .as_str()
+        .text,
+        "\"q\"|[\"q\"]|{}|ab"
+    );
+    assert_eq!(
+        eval!(
+            scope,
+            "f\"{name:>4}: {add(1, 2)} {{x}} {concat(\\\"a\\\", \\\"b\\\")}\""
+        )
+        .as_str()
+        .text,
+        " Bob: 3 {x} ab"
+    );
+
+    assert_eq!(
+        error!(scope, "format(\"{} {}\", 1)"),
+        "Not enough arguments for format string"
+    );
+    assert_eq!(
+        error!(scope, "format(\"{\")"),
+        "Unmatched `{` in format string"
+    );
+    assert_eq!(
+        error!(scope, "format(\"{missing}\")"),
+        "Unknown format argument 'missing'"
+    );
+    assert_eq!(
+        error!(scope, "format(\"{:q}\", 1)"),
+        "Invalid format specifier 'q'"
+    );
+    for template in [
+        "{:4611686018427387904}",
+        "{:.4611686018427387904}",
+        "{:99999999999999999999999}",
+        "{:65536}",
+    ] {
+        scope.set_variable("template", Rc::new(Str::new(template)));
+        assert_eq!(
+            error!(scope, "format(template, 1.5)"),
+            "Width and precision should not be greater than 65535"
+        );
+    }
+    assert_eq!(
+        eval!(scope, "len(format(\"{:65535}\", 1))").as_int().number,
+        65535
+    );
+    assert_eq!(
+        error!(scope, "format(\"{:x}\", 1.5)"),
+        "Radix format requires `Int` argument"
+    );
+}
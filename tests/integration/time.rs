@@ -0,0 +1,103 @@
+use std::rc::Rc;
+
+use easy_prog::clock::{Clock, ManualClock, SystemClock};
+use easy_prog::types::Scope;
+
+#[test]
+fn test_clocks() {
+    let clock = ManualClock::new(1000);
+    let shared = clock.clone();
+    assert_eq!(clock.now_ms(), 1000);
+    assert_eq!(clock.monotonic_ms(), 0);
+    shared.advance_ms(250);
+    assert_eq!(clock.now_ms(), 1250);
+    assert_eq!(clock.monotonic_ms(), 250);
+    clock.set_now_ms(0);
+    clock.sleep_ms(5);
+    assert_eq!(shared.now_ms(), 5);
+    assert_eq!(shared.monotonic_ms(), 255);
+    clock.advance_ms(u64::MAX);
+    assert_eq!(clock.now_ms(), i64::MAX);
+    assert_eq!(clock.monotonic_ms(), i64::MAX);
+    clock.advance_ms(1 << 63);
+    assert_eq!(clock.monotonic_ms(), i64::MAX);
+
+    let clock = SystemClock::new();
+    let start = clock.monotonic_ms();
+    clock.sleep_ms(2);
+    assert!(clock.monotonic_ms() >= start + 2);
+    assert!(clock.now_ms() > 1_600_000_000_000);
+}
+
+#[test]
+fn test_time_functions() {
+    let mut scope = Scope::with_stdlib();
+    let clock = ManualClock::new(1_700_000_000_123);
+    scope.get_engine_mut().set_clock(Rc::new(clock.clone()));
+
+    assert_eq!(eval!(scope, "now_ms()").as_int().number, 1_700_000_000_123);
+    assert_eq!(eval!(scope, "monotonic_ms()").as_int().number, 0);
+    eval!(scope, "sleep_ms(1500)");
+    assert_eq!(eval!(scope, "monotonic_ms()").as_int().number, 1500);
+    clock.advance_ms(500);
+    assert_eq!(eval!(scope, "now_ms()").as_int().number, 1_700_000_002_123);
+    assert_eq!(
+        error!(scope, "sleep_ms(-1)"),
+        "Duration should not be negative"
+    );
+
+    assert_eq!(
+        eval!(scope, "format_date(now_ms())").as_str().text,
+        "2023-11-14T22:13:22.123Z"
+    );
+    assert_eq!(
+        eval!(scope, "format_date(-1, \"%d.%m.%Y %H:%M:%S %%\")")
+            .as_str()
+            .text,
+        "31.12.1969 23:59:59 %"
+    );
+    assert_eq!(
+        eval!(scope, "format_date(951782400000, \"%Y-%m-%d\")")
+            .as_str()
+            .text,
+        "2000-02-29"
+    );
+    assert_eq!(
+        error!(scope, "format_date(0, \"%q\")"),
+        "Invalid date format specifier '%q'"
+    );
+
+    assert_eq!(
+        eval!(scope, "parse_date(\"2023-11-14T22:13:22.123Z\")")
+            .as_int()
+            .number,
+        1_700_000_002_123
+    );
+    assert_eq!(
+        eval!(scope, "parse_date(\"29.02.2000\", \"%d.%m.%Y\")")
+            .as_int()
+            .number,
+        951_782_400_000
+    );
+    assert_eq!(
+        eval!(
+            scope,
+            "parse_date(\"1969-12-31 23:59\", \"%Y-%m-%d %H:%M\")"
+        )
+        .as_int()
+        .number,
+        -60_000
+    );
+    assert_eq!(
+        error!(scope, "parse_date(\"29.02.2001\", \"%d.%m.%Y\")"),
+        "Invalid date"
+    );
+    assert_eq!(
+        error!(scope, "parse_date(\"2000-1-01\", \"%Y-%m-%d\")"),
+        "Date doesn't match format"
+    );
+    assert_eq!(
+        error!(scope, "parse_date(\"2000-01-01 \", \"%Y-%m-%d\")"),
+        "Date doesn't match format"
+    );
+}
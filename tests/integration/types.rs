@@ -29,3 +29,16 @@ fn test_scope_from_scope() {
     assert!(!scope.has_function(",test_func2"));
     assert!(other_scope.has_function(",test_func2"));
 }
+
+#[test]
+fn test_scope_engine() {
+    let scope = Scope::empty();
+    let mut child = Scope::from_scope(&scope);
+    child.get_engine_mut().set_strict_conditions(true);
+    assert!(scope.get_engine().is_strict_conditions());
+
+    let mut grandchild = Scope::from_scope(&child);
+    grandchild.get_engine_mut().set_strict_conditions(false);
+    assert!(!scope.get_engine().is_strict_conditions());
+    assert!(!child.get_engine().is_strict_conditions());
+}